@@ -0,0 +1,28 @@
+use bls12_381::{G2Affine, G2Projective, Scalar};
+
+use crate::{
+    bls12381_key2020::{Bls12381Group, Bls12381Key2020},
+    multicodec::MULTICODEC_BLS12381_G2_PUB_HEADER,
+};
+
+pub(crate) const SUITE_ID: &str = "Bls12381G2Key2020";
+pub(crate) const SUITE_CONTEXT: &str = "https://w3id.org/security/suites/bls12381-2020/v1";
+
+/// The G2 group of BLS12-381.
+pub struct G2;
+
+impl Bls12381Group for G2 {
+    const SUITE_ID: &'static str = SUITE_ID;
+    const SUITE_CONTEXT: &'static str = SUITE_CONTEXT;
+    const PUBLIC_KEY_HEADER: [u8; 2] = MULTICODEC_BLS12381_G2_PUB_HEADER;
+
+    fn public_key(secret_key: &Scalar) -> Vec<u8> {
+        G2Affine::from(G2Projective::generator() * secret_key)
+            .to_compressed()
+            .to_vec()
+    }
+}
+
+/// A BLS12-381 G2 `Bls12381G2Key2020` verification method, as used for BBS+
+/// signatures. The key is a compressed G2 point exported as `publicKeyBase58`.
+pub type Bls12381G2Key2020 = Bls12381Key2020<G2>;
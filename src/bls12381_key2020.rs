@@ -0,0 +1,183 @@
+use std::marker::PhantomData;
+
+use bls12_381::Scalar;
+use fi_common::{
+    error::Error,
+    keys::{KeyPair, VerificationKey},
+};
+use rand_core::{CryptoRngCore, OsRng};
+
+use crate::{
+    fingerprint::{self, KeyFingerprint},
+    multicodec,
+    util::{bls12381_secret_key_to_bytes, generate_bls12381_secret_key, multibase_encode},
+};
+
+/// The BLS12-381 group of the public keys of a `Bls12381G1Key2020` or
+/// `Bls12381G2Key2020` verification method.
+pub trait Bls12381Group {
+    const SUITE_ID: &'static str;
+    const SUITE_CONTEXT: &'static str;
+    /// The multicodec header of the public key.
+    const PUBLIC_KEY_HEADER: [u8; 2];
+
+    /// The compressed point of the public key of `secret_key`.
+    fn public_key(secret_key: &Scalar) -> Vec<u8>;
+}
+
+/// A BLS12-381 verification method whose key is a compressed point of the
+/// group `G`, exported as `publicKeyBase58`.
+pub struct Bls12381Key2020<G: Bls12381Group> {
+    _type: String,
+    id: Option<String>,
+    controller: Option<String>,
+    public_key_base58: String,
+    private_key_base58: Option<String>,
+    revoked: bool,
+    group: PhantomData<G>,
+}
+
+impl<G: Bls12381Group> Bls12381Key2020<G> {
+    pub fn new(
+        controller: Option<String>,
+        public_key_base58: String,
+        private_key_base58: Option<String>,
+        fingerprint: Option<String>,
+    ) -> Self {
+        let mut id: Option<String> = None;
+        if controller.is_some() && fingerprint.is_some() {
+            let ctrler = controller.clone().unwrap();
+            let fprint = fingerprint.clone().unwrap();
+            id = Some(format!("{}#{}", ctrler, fprint));
+        }
+
+        Bls12381Key2020 {
+            _type: String::from(G::SUITE_ID),
+            id,
+            controller,
+            public_key_base58,
+            private_key_base58,
+            revoked: false,
+            group: PhantomData,
+        }
+    }
+
+    /// Generates a key pair with the OS random number generator, see
+    /// [`Bls12381Key2020::generate_with_rng`].
+    pub fn generate() -> Self {
+        Bls12381Key2020::generate_with_rng(&mut OsRng)
+    }
+
+    /// Generates a key pair whose controller is its `did:key`. The private key
+    /// is the big endian secret key.
+    pub fn generate_with_rng(rng: &mut impl CryptoRngCore) -> Self {
+        let secret_key = generate_bls12381_secret_key(rng);
+        let public_key = G::public_key(&secret_key);
+
+        let fingerprint = multibase_encode(&G::PUBLIC_KEY_HEADER, &mut public_key.clone());
+
+        Bls12381Key2020::new(
+            Some(format!("did:key:{}", fingerprint)),
+            bs58::encode(public_key).into_string(),
+            Some(bs58::encode(bls12381_secret_key_to_bytes(&secret_key)).into_string()),
+            Some(fingerprint),
+        )
+    }
+}
+
+impl<G: Bls12381Group> VerificationKey for Bls12381Key2020<G> {
+    fn from_fingerprint(fingerprint: &str) -> Result<Self, Error> {
+        let (header, public_key) = multicodec::decode_fingerprint(fingerprint)?;
+        if header != G::PUBLIC_KEY_HEADER {
+            return Err(Error::new(
+                format!("Unsupported fingerprint {}", fingerprint).as_str(),
+            ));
+        }
+        multicodec::validate_public_key(&header, &public_key)?;
+
+        Ok(Bls12381Key2020::new(
+            Some(format!("did:key:{}", fingerprint)),
+            bs58::encode(public_key).into_string(),
+            None,
+            Some(String::from(fingerprint)),
+        ))
+    }
+
+    fn get_suite_id() -> &'static str
+    where
+        Self: Sized,
+    {
+        G::SUITE_ID
+    }
+
+    fn get_current_suite_id(&self) -> &'static str {
+        G::SUITE_ID
+    }
+
+    fn get_suite_context() -> &'static str
+    where
+        Self: Sized,
+    {
+        G::SUITE_CONTEXT
+    }
+
+    fn get_current_suite_context(&self) -> &'static str {
+        G::SUITE_CONTEXT
+    }
+
+    fn get_controller(&self) -> &Option<String> {
+        &self.controller
+    }
+
+    fn get_type(&self) -> String {
+        self._type.clone()
+    }
+
+    fn get_private_key_content(&self) -> &Option<String> {
+        &self.private_key_base58
+    }
+
+    fn get_public_key_content(&self) -> &String {
+        &self.public_key_base58
+    }
+
+    fn export(&self, public_key: bool, private_key: bool, include_context: bool) -> KeyPair {
+        KeyPair {
+            id: self.id.clone(),
+            _type: self._type.clone(),
+            context: match include_context {
+                true => Some(Vec::from([String::from(G::SUITE_CONTEXT)])),
+                false => None,
+            },
+            public_key_base58: match public_key {
+                true => Some(self.public_key_base58.clone()),
+                false => None,
+            },
+            private_key_base58: match private_key {
+                true => self.private_key_base58.clone(),
+                false => None,
+            },
+            private_key_multibase: None,
+            public_key_multibase: None,
+            revoked: Some(self.revoked),
+            controller: self.controller.clone(),
+            blockchain_account_id: None,
+            public_key_hex: None,
+            public_key_base64: None,
+            public_key_pem: None,
+            private_key_hex: None,
+            private_key_base64: None,
+            private_key_pem: None,
+            value: None,
+            ethereum_address: None,
+            public_key_jwk: None,
+            private_key_jwk: None,
+        }
+    }
+}
+
+impl<G: Bls12381Group> KeyFingerprint for Bls12381Key2020<G> {
+    fn get_fingerprint_key_bytes(&self) -> Result<([u8; 2], Vec<u8>), Error> {
+        fingerprint::base58_key_bytes(&G::PUBLIC_KEY_HEADER, &self.public_key_base58)
+    }
+}
@@ -0,0 +1,694 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::DateTime;
+use fi_common::{did::DidDocument, keys::KeyPair};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    ed25519_verification_key2020,
+    failure::{error_codes, Failure},
+    json_ld,
+    multicodec::MULTICODEC_ED25519_PUB_HEADER,
+    resolve_did_with_format,
+    signature::{self, SignatureKey},
+    util::{multibase_encode, MULTIBASE_BASE58BTC_HEADER},
+};
+
+pub(crate) const DATA_INTEGRITY_PROOF: &str = "DataIntegrityProof";
+pub(crate) const EDDSA_JCS_2022: &str = "eddsa-jcs-2022";
+// the suites that sign the RDF Dataset Canonicalization (RDFC-1.0) of the
+// JSON-LD document, see [`json_ld::canonicalize`]
+const EDDSA_RDFC_2022: &str = "eddsa-rdfc-2022";
+const ED25519_SIGNATURE_2020: &str = "Ed25519Signature2020";
+
+// the proof purposes, each named after its verification relationship
+const PROOF_PURPOSES: [&str; 4] = [
+    "assertionMethod",
+    "authentication",
+    "capabilityInvocation",
+    "capabilityDelegation",
+];
+
+error_codes! {
+    /// Error codes of creating and verifying a Data Integrity proof.
+    pub enum ProofError {
+        /// The document is not a JSON object or cannot be canonicalized.
+        InvalidDocument => "invalidDocument",
+        /// The proof is missing or malformed.
+        InvalidProof => "invalidProof",
+        /// The proof type or cryptosuite is not supported.
+        UnsupportedCryptosuite => "unsupportedCryptosuite",
+        /// The verification method cannot be resolved or is not an Ed25519 key.
+        InvalidVerificationMethod => "invalidVerificationMethod",
+        /// The verification method is not listed under the verification
+        /// relationship of the proof purpose.
+        InvalidProofPurpose => "invalidProofPurpose",
+        /// The proof value cannot be created or does not verify.
+        InvalidSignature => "invalidSignature",
+        /// The `expires` of the proof has passed.
+        ProofExpired => "proofExpired",
+        /// The `challenge` of the proof is not the expected challenge.
+        InvalidChallenge => "invalidChallenge",
+        /// The `domain` of the proof is not the expected domain.
+        InvalidDomain => "invalidDomain",
+    }
+}
+
+pub type ProofFailure = Failure<ProofError>;
+
+/// The Ed25519 cryptosuites of the proofs that can be created and verified.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Cryptosuite {
+    /// A `DataIntegrityProof` signing the JSON Canonicalization Scheme (RFC
+    /// 8785) of the document.
+    #[default]
+    EddsaJcs2022,
+    /// A `DataIntegrityProof` signing the RDFC-1.0 canonical form of the
+    /// JSON-LD document.
+    EddsaRdfc2022,
+    /// The `Ed25519Signature2020` proof, signed as `eddsa-rdfc-2022`. The
+    /// document must include the Ed25519 2020 suite context.
+    Ed25519Signature2020,
+}
+
+impl Cryptosuite {
+    // the `type` and `cryptosuite` of the proofs of the suite
+    fn proof_type(&self) -> (&'static str, Option<&'static str>) {
+        match self {
+            Cryptosuite::EddsaJcs2022 => (DATA_INTEGRITY_PROOF, Some(EDDSA_JCS_2022)),
+            Cryptosuite::EddsaRdfc2022 => (DATA_INTEGRITY_PROOF, Some(EDDSA_RDFC_2022)),
+            Cryptosuite::Ed25519Signature2020 => (ED25519_SIGNATURE_2020, None),
+        }
+    }
+}
+
+/// The options of a proof to create, see [`create_proof`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofOptions {
+    pub verification_method: String,
+    pub proof_purpose: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub challenge: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    /// The cryptosuite of the proof, `eddsa-jcs-2022` by default.
+    #[serde(skip)]
+    pub cryptosuite: Cryptosuite,
+}
+
+impl ProofOptions {
+    pub fn new(verification_method: &str, proof_purpose: &str) -> Self {
+        ProofOptions {
+            verification_method: String::from(verification_method),
+            proof_purpose: String::from(proof_purpose),
+            created: None,
+            expires: None,
+            challenge: None,
+            domain: None,
+            cryptosuite: Cryptosuite::default(),
+        }
+    }
+}
+
+/// The options of verifying a proof, see [`verify_proof_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProofVerificationOptions {
+    /// The challenge the proof must have been created with, e.g. the one sent
+    /// to the holder of a presentation.
+    pub challenge: Option<String>,
+    /// The domain the proof must have been created for.
+    pub domain: Option<String>,
+    /// The time to check `expires` at, in seconds since the Unix epoch. The
+    /// system time is used when it is `None`.
+    pub current_time: Option<u64>,
+}
+
+/// Secures `document` with a Data Integrity proof of the cryptosuite of
+/// `options` signed by `key_pair`, returning the document with the `proof`
+/// added.
+///
+/// An `eddsa-jcs-2022` proof repeats the `@context` of the document, as the
+/// cryptosuite requires. `eddsa-rdfc-2022` and `Ed25519Signature2020` proofs
+/// need a document whose `@context` is made of bundled contexts, see
+/// [`json_ld::load_context`], and which defines every term it uses. The
+/// `@context` of a document secured with an `Ed25519Signature2020` proof must
+/// include the Ed25519 2020 suite context that defines the proof.
+///
+/// The `verificationMethod` of `options` must name the public key of
+/// `key_pair`: a DID URL must dereference to it, and the fragment of any other
+/// URL must be its multibase fingerprint.
+pub fn create_proof(
+    document: &Value,
+    options: &ProofOptions,
+    key_pair: &dyn SignatureKey,
+) -> Result<Value, ProofFailure> {
+    let mut unsecured = document_object(document)?;
+    if unsecured.contains_key("proof") {
+        return Err(ProofFailure::new(
+            ProofError::InvalidDocument,
+            "Document is already secured with a proof",
+        ));
+    }
+    check_proof_purpose(&options.proof_purpose)?;
+    for (name, value) in [("created", &options.created), ("expires", &options.expires)] {
+        if let Some(value) = value {
+            parse_date_time(name, value)?;
+        }
+    }
+    check_signing_key(&options.verification_method, key_pair)?;
+
+    let mut proof = match serde_json::to_value(options) {
+        Ok(Value::Object(val)) => val,
+        _ => {
+            return Err(ProofFailure::new(
+                ProofError::InvalidProof,
+                "Proof options are not a JSON object",
+            ))
+        }
+    };
+    let (proof_type, cryptosuite) = options.cryptosuite.proof_type();
+    proof.insert(String::from("type"), Value::from(proof_type));
+    if let Some(cryptosuite) = cryptosuite {
+        proof.insert(String::from("cryptosuite"), Value::from(cryptosuite));
+    }
+    if options.cryptosuite == Cryptosuite::EddsaJcs2022 {
+        if let Some(context) = unsecured.get("@context") {
+            proof.insert(String::from("@context"), context.clone());
+        }
+    }
+
+    let hash_data = hash_data(&unsecured, &proof, options.cryptosuite)?;
+    let signature = match key_pair.sign(&hash_data) {
+        Ok(val) => val,
+        Err(failure) => {
+            return Err(ProofFailure::new(
+                ProofError::InvalidSignature,
+                failure.to_string().as_str(),
+            ))
+        }
+    };
+    proof.insert(
+        String::from("proofValue"),
+        Value::from(format!(
+            "{}{}",
+            MULTIBASE_BASE58BTC_HEADER,
+            bs58::encode(signature).into_string()
+        )),
+    );
+
+    unsecured.insert(String::from("proof"), Value::Object(proof));
+
+    Ok(Value::Object(unsecured))
+}
+
+/// Verifies the `eddsa-jcs-2022`, `eddsa-rdfc-2022` or `Ed25519Signature2020`
+/// proof of `document` and returns the verification method it was verified
+/// with, see
+/// [`verify_proof_with_options`].
+pub fn verify_proof(document: &Value) -> Result<KeyPair, ProofFailure> {
+    verify_proof_with_options(document, &ProofVerificationOptions::default())
+}
+
+/// Verifies the `eddsa-jcs-2022`, `eddsa-rdfc-2022` or `Ed25519Signature2020`
+/// proof of `document` and returns the verification method it was verified
+/// with.
+///
+/// The `verificationMethod` is resolved with [`resolve_did_with_format`] and must be
+/// listed under the verification relationship named by `proofPurpose` in the
+/// resolved DID document. The proof must not have expired at the current time
+/// of `options`, and must have the `challenge` and `domain` of `options` when
+/// they are set.
+pub fn verify_proof_with_options(
+    document: &Value,
+    options: &ProofVerificationOptions,
+) -> Result<KeyPair, ProofFailure> {
+    let mut unsecured = document_object(document)?;
+    let mut proof = match unsecured.remove("proof") {
+        Some(Value::Object(val)) => val,
+        Some(_) => {
+            return Err(ProofFailure::new(
+                ProofError::InvalidProof,
+                "Document must have a single proof object",
+            ))
+        }
+        None => {
+            return Err(ProofFailure::new(
+                ProofError::InvalidProof,
+                "Document has no proof",
+            ))
+        }
+    };
+
+    let cryptosuite = check_cryptosuite(&proof)?;
+
+    let signature = match proof.remove("proofValue") {
+        Some(Value::String(val)) => decode_proof_value(&val)?,
+        _ => {
+            return Err(ProofFailure::new(
+                ProofError::InvalidProof,
+                "Proof is missing 'proofValue'",
+            ))
+        }
+    };
+    check_proof_context(&unsecured, &proof)?;
+    check_proof_options(&proof, options)?;
+
+    let verification_method_id = get_proof_member(&proof, "verificationMethod")?;
+    let proof_purpose = get_proof_member(&proof, "proofPurpose")?;
+    let verification_method = resolve_verification_method(verification_method_id, proof_purpose)?;
+
+    let public_key = match signature::verification_method_public_key(&verification_method) {
+        Ok((MULTICODEC_ED25519_PUB_HEADER, val)) => val,
+        Ok(_) => {
+            return Err(ProofFailure::new(
+                ProofError::InvalidVerificationMethod,
+                format!("'{}' is not an Ed25519 key", verification_method_id).as_str(),
+            ))
+        }
+        Err(error) => {
+            return Err(ProofFailure::new(
+                ProofError::InvalidVerificationMethod,
+                error.to_string().as_str(),
+            ))
+        }
+    };
+
+    let hash_data = hash_data(&unsecured, &proof, cryptosuite)?;
+    match signature::verify(&public_key, &hash_data, &signature) {
+        Ok(_) => Ok(verification_method),
+        Err(failure) => Err(ProofFailure::new(
+            ProofError::InvalidSignature,
+            failure.to_string().as_str(),
+        )),
+    }
+}
+
+fn document_object(document: &Value) -> Result<Map<String, Value>, ProofFailure> {
+    match document {
+        Value::Object(val) => Ok(val.clone()),
+        _ => Err(ProofFailure::new(
+            ProofError::InvalidDocument,
+            "Document must be a JSON object",
+        )),
+    }
+}
+
+fn check_cryptosuite(proof: &Map<String, Value>) -> Result<Cryptosuite, ProofFailure> {
+    let proof_type = proof.get("type").and_then(Value::as_str);
+    let cryptosuite = proof.get("cryptosuite").and_then(Value::as_str);
+
+    for suite in [
+        Cryptosuite::EddsaJcs2022,
+        Cryptosuite::EddsaRdfc2022,
+        Cryptosuite::Ed25519Signature2020,
+    ] {
+        if suite.proof_type() == (proof_type.unwrap_or_default(), cryptosuite) {
+            return Ok(suite);
+        }
+    }
+
+    Err(ProofFailure::new(
+        ProofError::UnsupportedCryptosuite,
+        format!(
+            "Proof must be a '{}' of the '{}' or '{}' cryptosuite, or an '{}'",
+            DATA_INTEGRITY_PROOF, EDDSA_JCS_2022, EDDSA_RDFC_2022, ED25519_SIGNATURE_2020
+        )
+        .as_str(),
+    ))
+}
+
+fn get_proof_member<'a>(
+    proof: &'a Map<String, Value>,
+    name: &str,
+) -> Result<&'a str, ProofFailure> {
+    match proof.get(name).and_then(Value::as_str) {
+        Some(val) => Ok(val),
+        None => Err(ProofFailure::new(
+            ProofError::InvalidProof,
+            format!("Proof is missing '{}'", name).as_str(),
+        )),
+    }
+}
+
+// checks the `expires`, `challenge` and `domain` of a proof against the
+// verification options
+fn check_proof_options(
+    proof: &Map<String, Value>,
+    options: &ProofVerificationOptions,
+) -> Result<(), ProofFailure> {
+    if let Some(expires) = proof.get("expires") {
+        let expires = match expires.as_str() {
+            Some(val) => parse_date_time("expires", val)?,
+            None => {
+                return Err(ProofFailure::new(
+                    ProofError::InvalidProof,
+                    "Proof 'expires' must be a string",
+                ))
+            }
+        };
+
+        let now = match options.current_time {
+            Some(val) => val,
+            None => match SystemTime::now().duration_since(UNIX_EPOCH) {
+                Ok(val) => val.as_secs(),
+                Err(error) => {
+                    return Err(ProofFailure::new(
+                        ProofError::InvalidProof,
+                        error.to_string().as_str(),
+                    ))
+                }
+            },
+        };
+        if i128::from(now) >= i128::from(expires) {
+            return Err(ProofFailure::new(
+                ProofError::ProofExpired,
+                format!("Proof expired at {}", proof["expires"]).as_str(),
+            ));
+        }
+    }
+
+    if let Some(challenge) = &options.challenge {
+        if proof.get("challenge").and_then(Value::as_str) != Some(challenge.as_str()) {
+            return Err(ProofFailure::new(
+                ProofError::InvalidChallenge,
+                format!("Proof 'challenge' must be '{}'", challenge).as_str(),
+            ));
+        }
+    }
+
+    if let Some(domain) = &options.domain {
+        // the domain of a proof is a string or an array of strings
+        let matches = match proof.get("domain") {
+            Some(Value::String(val)) => val == domain,
+            Some(Value::Array(values)) => values.iter().any(|val| val.as_str() == Some(domain)),
+            _ => false,
+        };
+        if !matches {
+            return Err(ProofFailure::new(
+                ProofError::InvalidDomain,
+                format!("Proof 'domain' must be '{}'", domain).as_str(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// the seconds since the Unix epoch of the XML Schema `dateTimeStamp` of a
+// proof member, rounded up so that a fractional `expires` is not passed early
+fn parse_date_time(name: &str, value: &str) -> Result<i64, ProofFailure> {
+    match DateTime::parse_from_rfc3339(value) {
+        Ok(val) if val.timestamp_subsec_nanos() > 0 => Ok(val.timestamp() + 1),
+        Ok(val) => Ok(val.timestamp()),
+        Err(error) => Err(ProofFailure::new(
+            ProofError::InvalidProof,
+            format!("Proof '{}' is not a date time stamp: {}", name, error).as_str(),
+        )),
+    }
+}
+
+// the verification method must name the public key of the signing key
+fn check_signing_key(
+    verification_method_id: &str,
+    key_pair: &dyn SignatureKey,
+) -> Result<(), ProofFailure> {
+    let mut public_key = match key_pair.get_signature_key_bytes() {
+        Ok((val, _)) => val,
+        Err(failure) => {
+            return Err(ProofFailure::new(
+                ProofError::InvalidSignature,
+                failure.to_string().as_str(),
+            ))
+        }
+    };
+
+    let matches = if verification_method_id.starts_with("did:") {
+        let verification_method = match resolve_did_with_format(verification_method_id, None) {
+            Ok((_, Some(val))) => val,
+            Ok(_) => {
+                return Err(ProofFailure::new(
+                    ProofError::InvalidVerificationMethod,
+                    format!(
+                        "'{}' does not dereference to a verification method",
+                        verification_method_id
+                    )
+                    .as_str(),
+                ))
+            }
+            Err(error) => {
+                return Err(ProofFailure::new(
+                    ProofError::InvalidVerificationMethod,
+                    error.to_string().as_str(),
+                ))
+            }
+        };
+
+        match signature::verification_method_public_key(&verification_method) {
+            Ok((header, val)) => header == MULTICODEC_ED25519_PUB_HEADER && val == public_key,
+            Err(_error) => false,
+        }
+    } else {
+        let fingerprint = multibase_encode(&MULTICODEC_ED25519_PUB_HEADER, &mut public_key);
+
+        verification_method_id
+            .split_once('#')
+            .is_some_and(|(_, fragment)| fragment == fingerprint)
+    };
+
+    if matches {
+        return Ok(());
+    }
+
+    Err(ProofFailure::new(
+        ProofError::InvalidVerificationMethod,
+        format!(
+            "'{}' is not the verification method of the signing key",
+            verification_method_id
+        )
+        .as_str(),
+    ))
+}
+
+fn check_proof_purpose(proof_purpose: &str) -> Result<(), ProofFailure> {
+    if PROOF_PURPOSES.contains(&proof_purpose) {
+        return Ok(());
+    }
+
+    Err(ProofFailure::new(
+        ProofError::InvalidProofPurpose,
+        format!("Unsupported proof purpose '{}'", proof_purpose).as_str(),
+    ))
+}
+
+// the `@context` of the proof, if any, must be a prefix of the document's
+fn check_proof_context(
+    unsecured: &Map<String, Value>,
+    proof: &Map<String, Value>,
+) -> Result<(), ProofFailure> {
+    let proof_context = match proof.get("@context") {
+        Some(val) => as_context_array(val),
+        None => return Ok(()),
+    };
+    let document_context = match unsecured.get("@context") {
+        Some(val) => as_context_array(val),
+        None => Vec::new(),
+    };
+
+    if document_context.starts_with(&proof_context) {
+        return Ok(());
+    }
+
+    Err(ProofFailure::new(
+        ProofError::InvalidProof,
+        "Proof '@context' must match the start of the document '@context'",
+    ))
+}
+
+fn as_context_array(context: &Value) -> Vec<Value> {
+    match context {
+        Value::Array(values) => values.clone(),
+        val => Vec::from([val.clone()]),
+    }
+}
+
+// the SHA-256 of the canonical proof configuration followed by the SHA-256 of
+// the canonical document
+fn hash_data(
+    unsecured: &Map<String, Value>,
+    proof_config: &Map<String, Value>,
+    cryptosuite: Cryptosuite,
+) -> Result<Vec<u8>, ProofFailure> {
+    if cryptosuite == Cryptosuite::EddsaJcs2022 {
+        let mut hash_data = Sha256::digest(canonicalize(proof_config)?).to_vec();
+        hash_data.extend_from_slice(&Sha256::digest(canonicalize(unsecured)?));
+
+        return Ok(hash_data);
+    }
+
+    // the proof configuration is interpreted with the `@context` of the
+    // document, which an RDFC proof does not repeat
+    let context = match unsecured.get("@context") {
+        Some(val) => val.clone(),
+        None => {
+            return Err(ProofFailure::new(
+                ProofError::InvalidDocument,
+                "Document must have an '@context' to be canonicalized with RDFC-1.0",
+            ))
+        }
+    };
+    // the proof type must not be left to a vocabulary of the document, such as
+    // the one of the credentials v2 context
+    if cryptosuite == Cryptosuite::Ed25519Signature2020
+        && !as_context_array(&context)
+            .contains(&Value::from(ed25519_verification_key2020::SUITE_CONTEXT))
+    {
+        return Err(ProofFailure::new(
+            ProofError::InvalidDocument,
+            format!(
+                "Document '@context' must include '{}' for an '{}' proof",
+                ed25519_verification_key2020::SUITE_CONTEXT,
+                ED25519_SIGNATURE_2020
+            )
+            .as_str(),
+        ));
+    }
+    let mut proof_config = proof_config.clone();
+    proof_config.insert(String::from("@context"), context);
+
+    let mut hash_data = Sha256::digest(canonicalize_rdf(&proof_config)?).to_vec();
+    hash_data.extend_from_slice(&Sha256::digest(canonicalize_rdf(unsecured)?));
+
+    Ok(hash_data)
+}
+
+// canonicalizes a JSON object with the JSON Canonicalization Scheme, RFC 8785
+fn canonicalize(object: &Map<String, Value>) -> Result<Vec<u8>, ProofFailure> {
+    match serde_jcs::to_vec(object) {
+        Ok(val) => Ok(val),
+        Err(error) => Err(ProofFailure::new(
+            ProofError::InvalidDocument,
+            format!("Cannot canonicalize document: {}", error).as_str(),
+        )),
+    }
+}
+
+// canonicalizes a JSON-LD object with the RDF Dataset Canonicalization, RDFC-1.0
+fn canonicalize_rdf(object: &Map<String, Value>) -> Result<String, ProofFailure> {
+    match json_ld::canonicalize(&Value::Object(object.clone())) {
+        Ok(val) => Ok(val),
+        Err(error) => Err(ProofFailure::new(
+            ProofError::InvalidDocument,
+            format!("Cannot canonicalize document: {}", error).as_str(),
+        )),
+    }
+}
+
+fn decode_proof_value(proof_value: &str) -> Result<Vec<u8>, ProofFailure> {
+    let encoded = match proof_value.strip_prefix(MULTIBASE_BASE58BTC_HEADER) {
+        Some(val) => val,
+        None => {
+            return Err(ProofFailure::new(
+                ProofError::InvalidProof,
+                "Proof value must be multibase base58btc encoded (must start with 'z')",
+            ))
+        }
+    };
+
+    match bs58::decode(encoded).into_vec() {
+        Ok(val) => Ok(val),
+        Err(error) => Err(ProofFailure::new(
+            ProofError::InvalidProof,
+            format!("Proof value is not base58btc encoded: {}", error).as_str(),
+        )),
+    }
+}
+
+// resolves the DID of a verification method and returns the method if it is
+// listed under the verification relationship of `proof_purpose`
+fn resolve_verification_method(
+    verification_method_id: &str,
+    proof_purpose: &str,
+) -> Result<KeyPair, ProofFailure> {
+    check_proof_purpose(proof_purpose)?;
+
+    let did = match verification_method_id.split_once('#') {
+        Some((did, _)) => did,
+        None => {
+            return Err(ProofFailure::new(
+                ProofError::InvalidVerificationMethod,
+                format!(
+                    "'{}' is not a DID URL with a fragment",
+                    verification_method_id
+                )
+                .as_str(),
+            ))
+        }
+    };
+
+    let did_doc = match resolve_did_with_format(did, None) {
+        Ok((Some(val), _)) => val,
+        Ok(_) => {
+            return Err(ProofFailure::new(
+                ProofError::InvalidVerificationMethod,
+                format!("'{}' does not resolve to a DID document", did).as_str(),
+            ))
+        }
+        Err(error) => {
+            return Err(ProofFailure::new(
+                ProofError::InvalidVerificationMethod,
+                error.to_string().as_str(),
+            ))
+        }
+    };
+
+    let relationship = verification_relationship(&did_doc, proof_purpose).unwrap_or_default();
+    if !relationship.iter().any(|val| val == verification_method_id) {
+        return Err(ProofFailure::new(
+            ProofError::InvalidProofPurpose,
+            format!(
+                "'{}' is not authorized for '{}'",
+                verification_method_id, proof_purpose
+            )
+            .as_str(),
+        ));
+    }
+
+    let verification_method = did_doc
+        .verification_method
+        .unwrap_or_default()
+        .into_iter()
+        .find(|val| val.id.as_deref() == Some(verification_method_id));
+    match verification_method {
+        Some(val) => Ok(val),
+        None => Err(ProofFailure::new(
+            ProofError::InvalidVerificationMethod,
+            format!(
+                "'{}' is not a verification method of '{}'",
+                verification_method_id, did
+            )
+            .as_str(),
+        )),
+    }
+}
+
+fn verification_relationship<'a>(
+    did_doc: &'a DidDocument,
+    proof_purpose: &str,
+) -> Option<&'a [String]> {
+    let relationship = match proof_purpose {
+        "assertionMethod" => &did_doc.assertion_method,
+        "authentication" => &did_doc.authentication,
+        "capabilityInvocation" => &did_doc.capability_invocation,
+        "capabilityDelegation" => &did_doc.capability_delegation,
+        _ => return None,
+    };
+
+    relationship.as_deref()
+}
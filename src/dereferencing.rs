@@ -0,0 +1,261 @@
+use chrono::DateTime;
+use fi_common::{
+    did::{DidDocument, Service},
+    keys::KeyPair,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    did_url::DidUrl,
+    resolution::{
+        serialize_without_nulls, DidDocumentMetadata, DidResolutionMetadata, DidResolutionResult,
+        ResolutionError, ResolutionFailure, DID_LD_JSON_CONTENT_TYPE, DID_RESOLUTION_CONTEXT_URL,
+    },
+};
+
+/// Dereferencing metadata has the same properties as resolution metadata.
+pub type DidDereferencingMetadata = DidResolutionMetadata;
+
+/// The resource a DID URL dereferences to.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum ContentStream {
+    DidDocument(DidDocument),
+    VerificationMethod(Box<KeyPair>),
+    Service(Service),
+    ServiceEndpoint(String),
+}
+
+/// Result of the DID URL Dereferencing `dereference` function.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DidDereferencingResult {
+    #[serde(rename = "@context")]
+    pub context: String,
+    #[serde(rename = "dereferencingMetadata")]
+    pub dereferencing_metadata: DidDereferencingMetadata,
+    #[serde(rename = "contentStream", serialize_with = "serialize_without_nulls")]
+    pub content_stream: Option<ContentStream>,
+    #[serde(rename = "contentMetadata")]
+    pub content_metadata: DidDocumentMetadata,
+}
+
+impl DidDereferencingResult {
+    pub fn from_content(
+        content_stream: ContentStream,
+        content_metadata: DidDocumentMetadata,
+    ) -> Self {
+        DidDereferencingResult {
+            context: String::from(DID_RESOLUTION_CONTEXT_URL),
+            dereferencing_metadata: DidDereferencingMetadata {
+                content_type: Some(String::from(DID_LD_JSON_CONTENT_TYPE)),
+                error: None,
+                error_message: None,
+            },
+            content_stream: Some(content_stream),
+            content_metadata,
+        }
+    }
+
+    pub fn from_error(error: ResolutionError, error_message: &str) -> Self {
+        DidDereferencingResult {
+            context: String::from(DID_RESOLUTION_CONTEXT_URL),
+            dereferencing_metadata: DidDereferencingMetadata {
+                content_type: None,
+                error: Some(error),
+                error_message: Some(String::from(error_message)),
+            },
+            content_stream: None,
+            content_metadata: DidDocumentMetadata::default(),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.dereferencing_metadata.error.is_some()
+    }
+}
+
+impl From<ResolutionFailure> for DidDereferencingResult {
+    fn from(failure: ResolutionFailure) -> Self {
+        DidDereferencingResult::from_error(failure.error, failure.message.as_str())
+    }
+}
+
+/// Dereferences `did_url` against the already resolved DID document of its DID.
+///
+/// `service` and `relativeRef` select a service endpoint, `versionId` and
+/// `versionTime` are checked against the DID document metadata and a fragment
+/// selects a verification method, key agreement key or service. The `hl`
+/// parameter is not verified.
+pub(crate) fn dereference_did_document(
+    did_url: &DidUrl,
+    resolution: DidResolutionResult,
+) -> DidDereferencingResult {
+    if let Some(error) = resolution.did_resolution_metadata.error {
+        let message = resolution
+            .did_resolution_metadata
+            .error_message
+            .unwrap_or_default();
+        return DidDereferencingResult::from_error(error, message.as_str());
+    }
+
+    let did_document = match resolution.did_document {
+        Some(val) => val,
+        None => {
+            return DidDereferencingResult::from_error(
+                ResolutionError::NotFound,
+                format!("No DID document found for {}", did_url.did()).as_str(),
+            )
+        }
+    };
+    let did_document_metadata = resolution.did_document_metadata;
+
+    if let Some(version_id) = did_url.version_id() {
+        if did_document_metadata.version_id.as_deref() != Some(version_id) {
+            return not_found(did_url, "versionId");
+        }
+    }
+
+    if let Some(version_time) = did_url.version_time() {
+        let version_time = match DateTime::parse_from_rfc3339(version_time) {
+            Ok(val) => val,
+            Err(error) => {
+                return DidDereferencingResult::from_error(
+                    ResolutionError::InvalidDidUrl,
+                    format!(
+                        "The versionTime of '{}' is not a date time: {}",
+                        did_url, error
+                    )
+                    .as_str(),
+                )
+            }
+        };
+        if let Some(created) = did_document_metadata.created.as_deref() {
+            match DateTime::parse_from_rfc3339(created) {
+                Ok(val) if val <= version_time => {}
+                _ => return not_found(did_url, "versionTime"),
+            }
+        }
+    }
+
+    if let Some(service_id) = did_url.service() {
+        return dereference_service(did_url, &did_document, service_id);
+    }
+
+    if did_url.path().is_some_and(|val| val != "/") {
+        return not_found(did_url, "path");
+    }
+
+    let fragment = match did_url.decoded_fragment() {
+        Some(val) => val,
+        None => {
+            return DidDereferencingResult::from_content(
+                ContentStream::DidDocument(did_document),
+                did_document_metadata,
+            )
+        }
+    };
+
+    if let Some(key_pair) = find_key_pair(&did_document, &fragment) {
+        return DidDereferencingResult::from_content(
+            ContentStream::VerificationMethod(Box::new(key_pair)),
+            DidDocumentMetadata::default(),
+        );
+    }
+
+    if let Some(service) = find_service(&did_document, &fragment) {
+        return DidDereferencingResult::from_content(
+            ContentStream::Service(service),
+            DidDocumentMetadata::default(),
+        );
+    }
+
+    not_found(did_url, "fragment")
+}
+
+fn dereference_service(
+    did_url: &DidUrl,
+    did_document: &DidDocument,
+    service_id: &str,
+) -> DidDereferencingResult {
+    let service = match find_service(did_document, service_id) {
+        Some(val) => val,
+        None => return not_found(did_url, "service"),
+    };
+
+    let endpoint = match &service.service_endpoint {
+        Value::String(val) => val.clone(),
+        _ => {
+            return DidDereferencingResult::from_content(
+                ContentStream::Service(service),
+                DidDocumentMetadata::default(),
+            )
+        }
+    };
+
+    let mut endpoint = match did_url.relative_ref() {
+        Some(relative_ref) => join_relative_ref(&endpoint, relative_ref),
+        None => endpoint,
+    };
+    if let Some(fragment) = did_url.fragment() {
+        endpoint = format!("{}#{}", endpoint, fragment);
+    }
+
+    DidDereferencingResult::from_content(
+        ContentStream::ServiceEndpoint(endpoint),
+        DidDocumentMetadata::default(),
+    )
+}
+
+fn join_relative_ref(endpoint: &str, relative_ref: &str) -> String {
+    if relative_ref.starts_with('/') {
+        let authority_start = endpoint.find("://").map(|val| val + 3).unwrap_or(0);
+        let path_start = endpoint[authority_start..]
+            .find('/')
+            .map(|val| val + authority_start)
+            .unwrap_or(endpoint.len());
+
+        return format!("{}{}", &endpoint[..path_start], relative_ref);
+    }
+
+    format!("{}/{}", endpoint.trim_end_matches('/'), relative_ref)
+}
+
+fn matches_fragment(did_document: &DidDocument, id: &str, fragment: &str) -> bool {
+    id.eq(&format!("{}#{}", did_document.id, fragment)) || id.eq(&format!("#{}", fragment))
+}
+
+fn find_key_pair(did_document: &DidDocument, fragment: &str) -> Option<KeyPair> {
+    did_document
+        .verification_method
+        .iter()
+        .chain(did_document.key_agreement.iter())
+        .flatten()
+        .find(|key_pair| {
+            key_pair
+                .id
+                .as_ref()
+                .is_some_and(|id| matches_fragment(did_document, id, fragment))
+        })
+        .cloned()
+}
+
+fn find_service(did_document: &DidDocument, fragment: &str) -> Option<Service> {
+    did_document
+        .services
+        .iter()
+        .flatten()
+        .find(|service| matches_fragment(did_document, &service.id, fragment))
+        .cloned()
+}
+
+fn not_found(did_url: &DidUrl, component: &str) -> DidDereferencingResult {
+    DidDereferencingResult::from_error(
+        ResolutionError::NotFound,
+        format!(
+            "The {} of '{}' did not match any resource",
+            component, did_url
+        )
+        .as_str(),
+    )
+}
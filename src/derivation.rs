@@ -0,0 +1,165 @@
+use bip39::Mnemonic;
+use fi_common::error::Error;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+use crate::{
+    ed25519_verification_key2020::Ed25519VerificationKey2020,
+    x25519_key_agreement_key2020::X25519KeyAgreementKey2020,
+};
+
+// HMAC key of the SLIP-0010 Ed25519 master key
+const SLIP10_ED25519_CURVE: &[u8] = b"ed25519 seed";
+// offset of hardened child indexes
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+// SLIP-0010 seeds are between 128 and 512 bits
+const MIN_SEED_LENGTH: usize = 16;
+const MAX_SEED_LENGTH: usize = 64;
+
+/// An `Ed25519VerificationKey2020` key pair derived from a seed, together with
+/// its X25519 key agreement key. Both keys are controlled by the `did:key` of
+/// the Ed25519 key.
+pub struct DerivedKeyPair {
+    verification_key: Ed25519VerificationKey2020,
+    agreement_key: X25519KeyAgreementKey2020,
+}
+
+impl DerivedKeyPair {
+    pub fn get_verification_key(&self) -> &Ed25519VerificationKey2020 {
+        &self.verification_key
+    }
+
+    pub fn get_agreement_key(&self) -> &X25519KeyAgreementKey2020 {
+        &self.agreement_key
+    }
+}
+
+/// Derives the key pair whose Ed25519 seed is `seed`.
+pub fn derive_from_seed(seed: &[u8; 32]) -> Result<DerivedKeyPair, Error> {
+    let verification_key = Ed25519VerificationKey2020::from_seed(seed)?;
+    let agreement_key =
+        X25519KeyAgreementKey2020::from_ed25519_verification_key2020(&verification_key)?;
+
+    Ok(DerivedKeyPair {
+        verification_key,
+        agreement_key,
+    })
+}
+
+/// Derives the key pair at the SLIP-0010 `path`, e.g. `m/0'/1'`, of a BIP-39
+/// `mnemonic` and `passphrase`.
+pub fn derive_from_mnemonic(
+    mnemonic: &str,
+    passphrase: &str,
+    path: &str,
+) -> Result<DerivedKeyPair, Error> {
+    let mnemonic = match Mnemonic::parse(mnemonic) {
+        Ok(val) => val,
+        Err(error) => {
+            return Err(Error::new(
+                format!("Invalid BIP-39 mnemonic: {}", error).as_str(),
+            ))
+        }
+    };
+
+    derive_from_slip10_seed(&mnemonic.to_seed(passphrase), path)
+}
+
+/// Derives the key pair at the SLIP-0010 `path` of a 16 to 64 byte master
+/// `seed`.
+pub fn derive_from_slip10_seed(seed: &[u8], path: &str) -> Result<DerivedKeyPair, Error> {
+    derive_from_seed(&derive_slip10_private_key(seed, path)?)
+}
+
+/// Derives the Ed25519 private key at the SLIP-0010 `path` of a master `seed`.
+/// Ed25519 only has hardened derivation, so every path segment must be
+/// hardened with `'` or `H`.
+pub fn derive_slip10_private_key(seed: &[u8], path: &str) -> Result<[u8; 32], Error> {
+    if seed.len() < MIN_SEED_LENGTH || seed.len() > MAX_SEED_LENGTH {
+        return Err(Error::new(
+            format!(
+                "SLIP-0010 seed must be {} to {} bytes, found {}",
+                MIN_SEED_LENGTH,
+                MAX_SEED_LENGTH,
+                seed.len()
+            )
+            .as_str(),
+        ));
+    }
+
+    let (mut key, mut chain_code) = hmac_sha512(SLIP10_ED25519_CURVE, seed)?;
+
+    for index in parse_path(path)? {
+        let mut data = Vec::from([0u8]);
+        data.extend_from_slice(&key);
+        data.extend_from_slice(&index.to_be_bytes());
+
+        (key, chain_code) = hmac_sha512(&chain_code, &data)?;
+    }
+
+    Ok(key)
+}
+
+// returns the hardened child indexes of a derivation path
+fn parse_path(path: &str) -> Result<Vec<u32>, Error> {
+    let mut segments = path.split('/');
+    if segments.next() != Some("m") {
+        return Err(Error::new(
+            format!("Derivation path '{}' must start with 'm'", path).as_str(),
+        ));
+    }
+
+    let mut indexes = Vec::new();
+    for segment in segments {
+        let index = match segment
+            .strip_suffix('\'')
+            .or_else(|| segment.strip_suffix('H'))
+        {
+            Some(val) => val,
+            None => {
+                return Err(Error::new(
+                    format!(
+                        "Derivation path segment '{}' must be hardened, Ed25519 has no normal derivation",
+                        segment
+                    )
+                    .as_str(),
+                ))
+            }
+        };
+
+        // `u32::from_str` also accepts a leading '+'
+        if !index.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(Error::new(
+                format!("Invalid derivation path segment '{}'", segment).as_str(),
+            ));
+        }
+
+        match index.parse::<u32>() {
+            Ok(val) if val < HARDENED_OFFSET => indexes.push(val + HARDENED_OFFSET),
+            _ => {
+                return Err(Error::new(
+                    format!("Invalid derivation path segment '{}'", segment).as_str(),
+                ))
+            }
+        }
+    }
+
+    Ok(indexes)
+}
+
+// returns the left and right halves of the HMAC-SHA512 of data
+fn hmac_sha512(key: &[u8], data: &[u8]) -> Result<([u8; 32], [u8; 32]), Error> {
+    let mut mac = match Hmac::<Sha512>::new_from_slice(key) {
+        Ok(val) => val,
+        Err(error) => return Err(Error::new(error.to_string().as_str())),
+    };
+    mac.update(data);
+
+    let output = mac.finalize().into_bytes();
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+
+    Ok((left, right))
+}
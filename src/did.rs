@@ -3,71 +3,48 @@ use fi_common::{
     error::Error,
     keys::{AgreementKey, VerificationKey},
 };
+use serde_json::Value;
 
 use crate::{
     ed25519_verification_key2018, ed25519_verification_key2020,
+    json_web_key2020::{self, JsonWebKey2020},
+    multicodec::{self, MULTICODEC_ED25519_PUB_HEADER},
+    multikey::{self, Multikey},
+    resolution::ResolutionOptions,
     x25519_key_agreement_key2019::X25519KeyAgreementKey2019,
     x25519_key_agreement_key2020::X25519KeyAgreementKey2020,
 };
 
-pub struct DidDoc(DidDocument);
+pub struct DidDoc;
 
-impl KeyPairToDidDocument for DidDoc {
-    fn key_pair_to_did_doc(
-        key_pair: &Box<dyn VerificationKey>,
+impl DidDoc {
+    /// Builds the `did:key` document of `key_pair`, honouring the
+    /// `defaultContext` and `enableEncryptionKeyDerivation` resolution options.
+    pub fn key_pair_to_did_doc_with_options(
+        key_pair: &dyn VerificationKey,
         fingerprint: &str,
+        options: &ResolutionOptions,
     ) -> Result<DidDocument, Error> {
         let did = format!("did:key:{}", fingerprint);
 
-        let mut contexts: Vec<String> = Vec::from([String::from(DID_CONTEXT_URL)]);
-
-        let agreement_key: Box<dyn AgreementKey> = match key_pair.get_current_suite_id() {
-            ed25519_verification_key2018::SUITE_ID => {
-                let agreement_key =
-                    match X25519KeyAgreementKey2019::from_ed25519_verification_key2018(&key_pair) {
-                        Ok(val) => val,
-                        Err(error) => return Err(error),
-                    };
-
-                contexts.push(String::from(key_pair.get_current_suite_context()));
-                contexts.push(String::from(agreement_key.get_current_suite_context()));
-
-                Box::new(agreement_key)
-            }
-            ed25519_verification_key2020::SUITE_ID => {
-                let agreement_key =
-                    match X25519KeyAgreementKey2020::from_ed25519_verification_key2020(&key_pair) {
-                        Ok(val) => val,
-                        Err(error) => return Err(error),
-                    };
-                contexts.push(String::from(key_pair.get_current_suite_context()));
-                contexts.push(String::from(agreement_key.get_current_suite_context()));
-
-                Box::new(agreement_key)
-            }
-            _ => {
-                return Err(Error::new(
-                    format!(
-                        "Cannot derive key agreement key from verification key type {}",
-                        key_pair.get_type()
-                    )
-                    .as_str(),
-                ))
-            }
-        };
+        let mut contexts = default_contexts(options);
+        push_context(&mut contexts, key_pair.get_current_suite_context());
 
-        let public_ed_key = key_pair.export(true, false, false);
-        let public_dh_key = agreement_key.export(true, false, false);
+        // only Ed25519 keys have a derived X25519 key agreement key
+        let mut key_agreement = None;
+        if options.enable_encryption_key_derivation && is_ed25519_fingerprint(fingerprint) {
+            let agreement_key = derive_agreement_key(key_pair)?;
+            push_context(&mut contexts, agreement_key.get_current_suite_context());
 
-        let ed_id = public_ed_key.id.clone();
-        let public_ed_key_id = match ed_id {
-            None => String::from(""),
-            Some(val) => val,
-        };
+            key_agreement = Some(Vec::from([agreement_key.export(true, false, false)]));
+        }
+
+        let public_ed_key = key_pair.export(true, false, false);
+        let public_ed_key_id = public_ed_key.id.clone().unwrap_or_default();
 
         let did_doc = DidDocument {
             id: did,
-            key_agreement: Some(Vec::from([public_dh_key])),
+            key_agreement,
             context: contexts,
             verification_method: Some(Vec::from([public_ed_key])),
             assertion_method: Some(Vec::from([public_ed_key_id.clone()])),
@@ -79,4 +56,120 @@ impl KeyPairToDidDocument for DidDoc {
 
         Ok(did_doc)
     }
+
+    /// Builds the document of an encryption only `did:key`, e.g. an X25519
+    /// key, whose only verification relationship is `keyAgreement`.
+    pub fn agreement_key_to_did_doc_with_options(
+        key_pair: &dyn AgreementKey,
+        fingerprint: &str,
+        options: &ResolutionOptions,
+    ) -> Result<DidDocument, Error> {
+        let mut contexts = default_contexts(options);
+        push_context(&mut contexts, key_pair.get_current_suite_context());
+
+        Ok(DidDocument {
+            id: format!("did:key:{}", fingerprint),
+            key_agreement: Some(Vec::from([key_pair.export(true, false, false)])),
+            context: contexts,
+            verification_method: None,
+            assertion_method: None,
+            authentication: None,
+            capability_delegation: None,
+            capability_invocation: None,
+            services: None,
+        })
+    }
+
+    /// Builds the document of a `did:key` key pair, e.g. a generated one,
+    /// whose controller is its `did:key`.
+    pub fn key_pair_to_did_doc_from_controller(
+        key_pair: &dyn VerificationKey,
+        options: &ResolutionOptions,
+    ) -> Result<DidDocument, Error> {
+        let fingerprint = controller_fingerprint(key_pair.get_controller())?;
+
+        DidDoc::key_pair_to_did_doc_with_options(key_pair, fingerprint, options)
+    }
+
+    /// Builds the document of an encryption only `did:key` key pair whose
+    /// controller is its `did:key`.
+    pub fn agreement_key_to_did_doc_from_controller(
+        key_pair: &dyn AgreementKey,
+        options: &ResolutionOptions,
+    ) -> Result<DidDocument, Error> {
+        let fingerprint = controller_fingerprint(key_pair.get_controller())?;
+
+        DidDoc::agreement_key_to_did_doc_with_options(key_pair, fingerprint, options)
+    }
+}
+
+impl KeyPairToDidDocument for DidDoc {
+    fn key_pair_to_did_doc(
+        key_pair: &Box<dyn VerificationKey>,
+        fingerprint: &str,
+    ) -> Result<DidDocument, Error> {
+        DidDoc::key_pair_to_did_doc_with_options(
+            key_pair.as_ref(),
+            fingerprint,
+            &ResolutionOptions::default(),
+        )
+    }
+}
+
+fn derive_agreement_key(key_pair: &dyn VerificationKey) -> Result<Box<dyn AgreementKey>, Error> {
+    match key_pair.get_current_suite_id() {
+        ed25519_verification_key2018::SUITE_ID => Ok(Box::new(
+            X25519KeyAgreementKey2019::from_ed25519_verification_key2018(key_pair)?,
+        )),
+        ed25519_verification_key2020::SUITE_ID => Ok(Box::new(
+            X25519KeyAgreementKey2020::from_ed25519_verification_key2020(key_pair)?,
+        )),
+        multikey::SUITE_ID => Ok(Box::new(
+            Multikey::from_ed25519_verification_key(key_pair)?.to_x25519_key_agreement_key()?,
+        )),
+        json_web_key2020::SUITE_ID => Ok(Box::new(
+            JsonWebKey2020::from_ed25519_verification_key(key_pair)?
+                .to_x25519_key_agreement_key()?,
+        )),
+        _ => Err(Error::new(
+            format!(
+                "Cannot derive key agreement key from verification key type {}",
+                key_pair.get_type()
+            )
+            .as_str(),
+        )),
+    }
+}
+
+fn controller_fingerprint(controller: &Option<String>) -> Result<&str, Error> {
+    match controller
+        .as_deref()
+        .and_then(|val| val.strip_prefix("did:key:"))
+    {
+        Some(val) => Ok(val),
+        None => Err(Error::new("Key pair controller is not a 'did:key' DID")),
+    }
+}
+
+fn is_ed25519_fingerprint(fingerprint: &str) -> bool {
+    matches!(
+        multicodec::decode_fingerprint(fingerprint),
+        Ok((MULTICODEC_ED25519_PUB_HEADER, _))
+    )
+}
+
+fn default_contexts(options: &ResolutionOptions) -> Vec<Value> {
+    match &options.default_context {
+        Some(default_context) => default_context
+            .iter()
+            .map(|val| Value::from(val.as_str()))
+            .collect(),
+        None => Vec::from([Value::from(DID_CONTEXT_URL)]),
+    }
+}
+
+fn push_context(contexts: &mut Vec<Value>, context: &str) {
+    if !contexts.iter().any(|val| val.eq(context)) {
+        contexts.push(Value::from(context));
+    }
 }
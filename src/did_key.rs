@@ -0,0 +1,220 @@
+use fi_common::{
+    did::DidDocument,
+    error::Error,
+    keys::{AgreementKey, VerificationKey},
+};
+
+use crate::{
+    bls12381_g1_key2020::{self, Bls12381G1Key2020},
+    bls12381_g2_key2020::{self, Bls12381G2Key2020},
+    did::DidDoc,
+    did_url::DidUrl,
+    ecdsa_secp256k1_verification_key2019::{self, EcdsaSecp256k1VerificationKey2019},
+    ed25519_verification_key2018::{self, Ed25519VerificationKey2018},
+    ed25519_verification_key2020::{self, Ed25519VerificationKey2020},
+    json_web_key2020::{self, JsonWebKey2020},
+    jwk,
+    multicodec::{
+        self, MULTICODEC_BLS12381_G1_PUB_HEADER, MULTICODEC_BLS12381_G2_PUB_HEADER,
+        MULTICODEC_ED25519_PUB_HEADER, MULTICODEC_RSA_PUB_HEADER, MULTICODEC_SECP256K1_PUB_HEADER,
+        MULTICODEC_X25519_PUB_HEADER,
+    },
+    multikey::{self, Multikey},
+    registry::DidMethodResolver,
+    resolution::{DidResolutionResult, ResolutionError, ResolutionFailure, ResolutionOptions},
+    x25519_key_agreement_key2019::{self, X25519KeyAgreementKey2019},
+    x25519_key_agreement_key2020::{self, X25519KeyAgreementKey2020},
+};
+
+pub const DID_KEY_METHOD: &str = "key";
+
+/// The built-in `did:key` method resolver.
+pub struct DidKeyResolver;
+
+impl DidMethodResolver for DidKeyResolver {
+    fn method(&self) -> &str {
+        DID_KEY_METHOD
+    }
+
+    fn resolve(&self, did_url: &DidUrl, options: &ResolutionOptions) -> DidResolutionResult {
+        let content_type = match options.content_type() {
+            Ok(val) => val,
+            Err(failure) => return DidResolutionResult::from(failure),
+        };
+
+        match resolve_did_document(did_url, options) {
+            Ok(did_doc) => {
+                let mut result = DidResolutionResult::from_did_document(did_doc);
+                result.did_resolution_metadata.content_type = Some(String::from(content_type));
+
+                result
+            }
+            Err(failure) => DidResolutionResult::from(failure),
+        }
+    }
+}
+
+fn resolve_did_document(
+    did_url: &DidUrl,
+    options: &ResolutionOptions,
+) -> Result<DidDocument, ResolutionFailure> {
+    if did_url.method() != DID_KEY_METHOD {
+        return Err(ResolutionFailure::new(
+            ResolutionError::MethodNotSupported,
+            format!("DID method '{}' is not supported", did_url.method()).as_str(),
+        ));
+    }
+
+    let fingerprint = did_url.method_specific_id();
+    let (header, key_bytes) = multicodec::decode_fingerprint(fingerprint)?;
+    multicodec::validate_public_key(&header, &key_bytes)?;
+
+    let public_key_format = match options.public_key_format.as_deref() {
+        Some(val) => val,
+        None => default_public_key_format(&header, options),
+    };
+    options.check_public_key_format(public_key_format)?;
+
+    // an X25519 did:key is an encryption only DID without a verification key
+    let did_doc = if header == MULTICODEC_X25519_PUB_HEADER {
+        let agreement_key_pair = agreement_key_from_fingerprint(fingerprint, public_key_format)?;
+
+        DidDoc::agreement_key_to_did_doc_with_options(
+            agreement_key_pair.as_ref(),
+            fingerprint,
+            options,
+        )
+    } else {
+        let verification_key_pair =
+            verification_key_from_fingerprint(fingerprint, &header, public_key_format)?;
+
+        DidDoc::key_pair_to_did_doc_with_options(
+            verification_key_pair.as_ref(),
+            fingerprint,
+            options,
+        )
+    };
+
+    match did_doc {
+        Ok(val) => Ok(val),
+        Err(error) => Err(ResolutionFailure::from_error(
+            ResolutionError::InvalidPublicKey,
+            error,
+        )),
+    }
+}
+
+fn verification_key_from_fingerprint(
+    fingerprint: &str,
+    header: &[u8; 2],
+    public_key_format: &str,
+) -> Result<Box<dyn VerificationKey>, ResolutionFailure> {
+    let verification_key_pair: Result<Box<dyn VerificationKey>, Error> =
+        match (*header, public_key_format) {
+            (MULTICODEC_ED25519_PUB_HEADER, ed25519_verification_key2018::SUITE_ID) => {
+                Ed25519VerificationKey2018::from_fingerprint(fingerprint)
+                    .map(|val| Box::new(val) as Box<dyn VerificationKey>)
+            }
+            (MULTICODEC_ED25519_PUB_HEADER, ed25519_verification_key2020::SUITE_ID) => {
+                Ed25519VerificationKey2020::from_fingerprint(fingerprint)
+                    .map(|val| Box::new(val) as Box<dyn VerificationKey>)
+            }
+            (MULTICODEC_SECP256K1_PUB_HEADER, ecdsa_secp256k1_verification_key2019::SUITE_ID) => {
+                EcdsaSecp256k1VerificationKey2019::from_fingerprint(fingerprint)
+                    .map(|val| Box::new(val) as Box<dyn VerificationKey>)
+            }
+            (MULTICODEC_BLS12381_G1_PUB_HEADER, bls12381_g1_key2020::SUITE_ID) => {
+                Bls12381G1Key2020::from_fingerprint(fingerprint)
+                    .map(|val| Box::new(val) as Box<dyn VerificationKey>)
+            }
+            (MULTICODEC_BLS12381_G2_PUB_HEADER, bls12381_g2_key2020::SUITE_ID) => {
+                Bls12381G2Key2020::from_fingerprint(fingerprint)
+                    .map(|val| Box::new(val) as Box<dyn VerificationKey>)
+            }
+            (_, multikey::SUITE_ID) => Multikey::from_fingerprint(fingerprint)
+                .map(|val| Box::new(val) as Box<dyn VerificationKey>),
+            (header, json_web_key2020::SUITE_ID) if jwk::is_supported_key_type(&header) => {
+                JsonWebKey2020::from_fingerprint(fingerprint)
+                    .map(|val| Box::new(val) as Box<dyn VerificationKey>)
+            }
+            (_, public_key_format) => {
+                return Err(ResolutionFailure::new(
+                    ResolutionError::UnsupportedPublicKeyType,
+                    format!(
+                        "Public key format '{}' is not supported for the key type of {}",
+                        public_key_format, fingerprint
+                    )
+                    .as_str(),
+                ))
+            }
+        };
+
+    match verification_key_pair {
+        Ok(val) => Ok(val),
+        Err(error) => Err(ResolutionFailure::from_error(
+            ResolutionError::InvalidPublicKey,
+            error,
+        )),
+    }
+}
+
+fn agreement_key_from_fingerprint(
+    fingerprint: &str,
+    public_key_format: &str,
+) -> Result<Box<dyn AgreementKey>, ResolutionFailure> {
+    let agreement_key_pair: Result<Box<dyn AgreementKey>, Error> = match public_key_format {
+        x25519_key_agreement_key2019::SUITE_ID => {
+            X25519KeyAgreementKey2019::from_fingerprint(fingerprint)
+                .map(|val| Box::new(val) as Box<dyn AgreementKey>)
+        }
+        x25519_key_agreement_key2020::SUITE_ID => {
+            X25519KeyAgreementKey2020::from_fingerprint(fingerprint)
+                .map(|val| Box::new(val) as Box<dyn AgreementKey>)
+        }
+        multikey::SUITE_ID => Multikey::from_fingerprint(fingerprint)
+            .map(|val| Box::new(val) as Box<dyn AgreementKey>),
+        json_web_key2020::SUITE_ID => JsonWebKey2020::from_fingerprint(fingerprint)
+            .map(|val| Box::new(val) as Box<dyn AgreementKey>),
+        public_key_format => {
+            return Err(ResolutionFailure::new(
+                ResolutionError::UnsupportedPublicKeyType,
+                format!(
+                    "Public key format '{}' is not supported for X25519 keys",
+                    public_key_format
+                )
+                .as_str(),
+            ))
+        }
+    };
+
+    match agreement_key_pair {
+        Ok(val) => Ok(val),
+        Err(error) => Err(ResolutionFailure::from_error(
+            ResolutionError::InvalidPublicKey,
+            error,
+        )),
+    }
+}
+
+// the verification method type of a key type, falling back to `Multikey` for
+// key types without one, e.g. the NIST curves, and when that type is
+// experimental and experimental types are disabled
+fn default_public_key_format(header: &[u8; 2], options: &ResolutionOptions) -> &'static str {
+    match *header {
+        MULTICODEC_ED25519_PUB_HEADER => ed25519_verification_key2020::SUITE_ID,
+        MULTICODEC_RSA_PUB_HEADER => json_web_key2020::SUITE_ID,
+        MULTICODEC_X25519_PUB_HEADER if options.enable_experimental_public_key_types => {
+            x25519_key_agreement_key2020::SUITE_ID
+        }
+        MULTICODEC_SECP256K1_PUB_HEADER if options.enable_experimental_public_key_types => {
+            ecdsa_secp256k1_verification_key2019::SUITE_ID
+        }
+        MULTICODEC_BLS12381_G1_PUB_HEADER if options.enable_experimental_public_key_types => {
+            bls12381_g1_key2020::SUITE_ID
+        }
+        MULTICODEC_BLS12381_G2_PUB_HEADER if options.enable_experimental_public_key_types => {
+            bls12381_g2_key2020::SUITE_ID
+        }
+        _ => multikey::SUITE_ID,
+    }
+}
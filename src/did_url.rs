@@ -0,0 +1,247 @@
+use std::fmt::Display;
+
+use percent_encoding::percent_decode_str;
+
+use crate::resolution::{ResolutionError, ResolutionFailure};
+
+/// A DID URL as defined by the DID Core ABNF:
+/// `did:<method>:<method-specific-id>[/<path>][?<query>][#<fragment>]`.
+///
+/// The path, query and fragment are kept in their encoded form; the query
+/// parameters and the fragment are also available percent-decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DidUrl {
+    did: String,
+    method: String,
+    method_specific_id: String,
+    path: Option<String>,
+    query: Option<String>,
+    fragment: Option<String>,
+    query_parameters: Vec<(String, String)>,
+}
+
+impl DidUrl {
+    pub fn parse(did_url: &str) -> Result<Self, ResolutionFailure> {
+        let (rest, fragment) = match did_url.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment)),
+            None => (did_url, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let (did, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], Some(&rest[index..])),
+            None => (rest, None),
+        };
+
+        let (method, method_specific_id) = parse_did(did)?;
+
+        if let Some(path) = path {
+            if !path[1..]
+                .split('/')
+                .all(|segment| is_valid_component(segment, ":@"))
+            {
+                return Err(invalid_did_url(did_url, "path"));
+            }
+        }
+
+        let mut query_parameters: Vec<(String, String)> = Vec::new();
+        if let Some(query) = query {
+            if !is_valid_component(query, ":@/?") {
+                return Err(invalid_did_url(did_url, "query"));
+            }
+
+            for parameter in query.split('&').filter(|val| !val.is_empty()) {
+                let (name, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+                query_parameters.push((
+                    decode_component(did_url, name)?,
+                    decode_component(did_url, value)?,
+                ));
+            }
+        }
+
+        if let Some(fragment) = fragment {
+            if !is_valid_component(fragment, ":@/?") {
+                return Err(invalid_did_url(did_url, "fragment"));
+            }
+        }
+
+        Ok(DidUrl {
+            did: String::from(did),
+            method: String::from(method),
+            method_specific_id: String::from(method_specific_id),
+            path: path.map(String::from),
+            query: query.map(String::from),
+            fragment: fragment.map(String::from),
+            query_parameters,
+        })
+    }
+
+    /// The DID part of the URL, without path, query and fragment.
+    pub fn did(&self) -> &str {
+        &self.did
+    }
+
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    pub fn method_specific_id(&self) -> &str {
+        &self.method_specific_id
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment.as_deref()
+    }
+
+    /// The percent-decoded fragment.
+    pub fn decoded_fragment(&self) -> Option<String> {
+        self.fragment
+            .as_ref()
+            .map(|val| percent_decode_str(val).decode_utf8_lossy().into_owned())
+    }
+
+    /// All percent-decoded query parameters, in the order they appear.
+    pub fn query_parameters(&self) -> &Vec<(String, String)> {
+        &self.query_parameters
+    }
+
+    /// The first percent-decoded value of the query parameter `name`.
+    pub fn query_parameter(&self, name: &str) -> Option<&str> {
+        self.query_parameters
+            .iter()
+            .find(|(key, _)| key.eq(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn service(&self) -> Option<&str> {
+        self.query_parameter("service")
+    }
+
+    pub fn relative_ref(&self) -> Option<&str> {
+        self.query_parameter("relativeRef")
+    }
+
+    pub fn version_id(&self) -> Option<&str> {
+        self.query_parameter("versionId")
+    }
+
+    pub fn version_time(&self) -> Option<&str> {
+        self.query_parameter("versionTime")
+    }
+
+    pub fn hl(&self) -> Option<&str> {
+        self.query_parameter("hl")
+    }
+
+    /// Whether the URL is a plain DID, without path, query or fragment.
+    pub fn is_did(&self) -> bool {
+        self.path.is_none() && self.query.is_none() && self.fragment.is_none()
+    }
+}
+
+impl Display for DidUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.did)?;
+        if let Some(path) = &self.path {
+            f.write_str(path)?;
+        }
+        if let Some(query) = &self.query {
+            write!(f, "?{}", query)?;
+        }
+        if let Some(fragment) = &self.fragment {
+            write!(f, "#{}", fragment)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_did(did: &str) -> Result<(&str, &str), ResolutionFailure> {
+    let invalid_did = |reason: &str| {
+        ResolutionFailure::new(
+            ResolutionError::InvalidDid,
+            format!("'{}' is not a valid DID: {}", did, reason).as_str(),
+        )
+    };
+
+    let method_and_id = match did.strip_prefix("did:") {
+        Some(val) => val,
+        None => return Err(invalid_did("missing 'did:' scheme")),
+    };
+
+    let (method, method_specific_id) = match method_and_id.split_once(':') {
+        Some(val) => val,
+        None => return Err(invalid_did("missing method specific id")),
+    };
+
+    if method.is_empty()
+        || !method
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    {
+        return Err(invalid_did("invalid method name"));
+    }
+
+    if method_specific_id.is_empty()
+        || method_specific_id.ends_with(':')
+        || !is_valid_component(method_specific_id, ":")
+        || method_specific_id
+            .chars()
+            .any(|c| !c.is_ascii_alphanumeric() && !".-_%:".contains(c))
+    {
+        return Err(invalid_did("invalid method specific id"));
+    }
+
+    Ok((method, method_specific_id))
+}
+
+// Checks the RFC 3986 `pchar` set (plus `extra`), including that every `%` starts
+// a valid percent-encoded octet.
+fn is_valid_component(component: &str, extra: &str) -> bool {
+    let bytes = component.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        let c = bytes[index] as char;
+        if c == '%' {
+            if index + 2 >= bytes.len()
+                || !bytes[index + 1].is_ascii_hexdigit()
+                || !bytes[index + 2].is_ascii_hexdigit()
+            {
+                return false;
+            }
+            index += 3;
+            continue;
+        }
+
+        if !(c.is_ascii_alphanumeric() || "-._~!$&'()*+,;=".contains(c) || extra.contains(c)) {
+            return false;
+        }
+        index += 1;
+    }
+
+    true
+}
+
+fn decode_component(did_url: &str, component: &str) -> Result<String, ResolutionFailure> {
+    match percent_decode_str(component).decode_utf8() {
+        Ok(val) => Ok(val.into_owned()),
+        Err(_error) => Err(invalid_did_url(did_url, "query")),
+    }
+}
+
+fn invalid_did_url(did_url: &str, component: &str) -> ResolutionFailure {
+    ResolutionFailure::new(
+        ResolutionError::InvalidDidUrl,
+        format!("'{}' has an invalid {}", did_url, component).as_str(),
+    )
+}
@@ -0,0 +1,228 @@
+use fi_common::{
+    error::Error,
+    keys::{KeyPair, VerificationKey},
+};
+use rand_core::{CryptoRngCore, OsRng};
+use serde_json::Value;
+
+use crate::{
+    fingerprint::KeyFingerprint,
+    jwk,
+    multicodec::{self, MULTICODEC_SECP256K1_PRIV_HEADER, MULTICODEC_SECP256K1_PUB_HEADER},
+    util::multibase_encode,
+};
+
+pub(crate) const SUITE_ID: &str = "EcdsaSecp256k1VerificationKey2019";
+pub(crate) const SUITE_CONTEXT: &str = "https://w3id.org/security/suites/secp256k1-2019/v1";
+
+/// A secp256k1 `EcdsaSecp256k1VerificationKey2019` verification method.
+///
+/// The key content is the multibase encoded compressed public key, i.e. the
+/// `did:key` fingerprint, while the key is exported as `publicKeyJwk` and
+/// `privateKeyJwk`.
+pub struct EcdsaSecp256k1VerificationKey2019 {
+    _type: String,
+    id: Option<String>,
+    controller: Option<String>,
+    public_key_multibase: String,
+    private_key_multibase: Option<String>,
+    public_key_jwk: Value,
+    private_key_jwk: Option<Value>,
+    revoked: bool,
+}
+
+impl EcdsaSecp256k1VerificationKey2019 {
+    pub fn new(
+        controller: Option<String>,
+        public_key_multibase: String,
+        private_key_multibase: Option<String>,
+        fingerprint: Option<String>,
+    ) -> Result<Self, Error> {
+        let (header, public_key) = multicodec::decode_fingerprint(&public_key_multibase)?;
+        if header != MULTICODEC_SECP256K1_PUB_HEADER {
+            return Err(Error::new(
+                format!(
+                    "'publicKeyMultibase' has invalid header bytes: '{}'.",
+                    public_key_multibase
+                )
+                .as_str(),
+            ));
+        }
+        multicodec::validate_public_key(&header, &public_key)?;
+        let public_key_jwk = jwk::public_key_jwk(&header, &public_key)?;
+
+        let private_key_jwk = match &private_key_multibase {
+            Some(private_key_multibase) => {
+                let (private_key_header, private_key) =
+                    multicodec::decode_fingerprint(private_key_multibase)?;
+                if private_key_header != MULTICODEC_SECP256K1_PRIV_HEADER {
+                    return Err(Error::new(
+                        format!(
+                            "'privateKeyMultibase' has invalid header bytes: '{}'.",
+                            private_key_multibase
+                        )
+                        .as_str(),
+                    ));
+                }
+
+                let secret_key = match k256::SecretKey::from_slice(&private_key) {
+                    Ok(val) => val,
+                    Err(_error) => {
+                        return Err(Error::new(
+                            "'privateKeyMultibase' is not a valid secp256k1 private key",
+                        ))
+                    }
+                };
+                if secret_key.public_key().to_sec1_bytes().as_ref() != public_key.as_slice() {
+                    return Err(Error::new(
+                        "'privateKeyMultibase' does not belong to 'publicKeyMultibase'",
+                    ));
+                }
+
+                Some(jwk::private_key_jwk(&header, &public_key, &private_key)?)
+            }
+            None => None,
+        };
+
+        let mut id: Option<String> = None;
+        if controller.is_some() && fingerprint.is_some() {
+            let ctrler = controller.clone().unwrap();
+            let fprint = fingerprint.clone().unwrap();
+            id = Some(format!("{}#{}", ctrler, fprint));
+        }
+
+        Ok(EcdsaSecp256k1VerificationKey2019 {
+            _type: String::from(SUITE_ID),
+            id,
+            controller,
+            public_key_multibase,
+            private_key_multibase,
+            public_key_jwk,
+            private_key_jwk,
+            revoked: false,
+        })
+    }
+
+    /// Generates a key pair with the OS random number generator, see
+    /// [`EcdsaSecp256k1VerificationKey2019::generate_with_rng`].
+    pub fn generate() -> Result<Self, Error> {
+        EcdsaSecp256k1VerificationKey2019::generate_with_rng(&mut OsRng)
+    }
+
+    /// Generates a key pair whose controller is its `did:key`.
+    pub fn generate_with_rng(rng: &mut impl CryptoRngCore) -> Result<Self, Error> {
+        let secret_key = k256::SecretKey::random(rng);
+
+        let fingerprint = multibase_encode(
+            &MULTICODEC_SECP256K1_PUB_HEADER,
+            &mut secret_key.public_key().to_sec1_bytes().to_vec(),
+        );
+
+        EcdsaSecp256k1VerificationKey2019::new(
+            Some(format!("did:key:{}", fingerprint)),
+            fingerprint.clone(),
+            Some(multibase_encode(
+                &MULTICODEC_SECP256K1_PRIV_HEADER,
+                &mut secret_key.to_bytes().to_vec(),
+            )),
+            Some(fingerprint),
+        )
+    }
+
+    pub fn get_public_key_jwk(&self) -> &Value {
+        &self.public_key_jwk
+    }
+
+    pub fn get_private_key_jwk(&self) -> &Option<Value> {
+        &self.private_key_jwk
+    }
+}
+
+impl VerificationKey for EcdsaSecp256k1VerificationKey2019 {
+    fn from_fingerprint(fingerprint: &str) -> Result<Self, Error> {
+        EcdsaSecp256k1VerificationKey2019::new(
+            Some(format!("did:key:{}", fingerprint)),
+            String::from(fingerprint),
+            None,
+            Some(String::from(fingerprint)),
+        )
+    }
+
+    fn get_suite_id() -> &'static str
+    where
+        Self: Sized,
+    {
+        SUITE_ID
+    }
+
+    fn get_current_suite_id(&self) -> &'static str {
+        SUITE_ID
+    }
+
+    fn get_suite_context() -> &'static str
+    where
+        Self: Sized,
+    {
+        SUITE_CONTEXT
+    }
+
+    fn get_current_suite_context(&self) -> &'static str {
+        SUITE_CONTEXT
+    }
+
+    fn get_controller(&self) -> &Option<String> {
+        &self.controller
+    }
+
+    fn get_type(&self) -> String {
+        self._type.clone()
+    }
+
+    fn get_private_key_content(&self) -> &Option<String> {
+        &self.private_key_multibase
+    }
+
+    fn get_public_key_content(&self) -> &String {
+        &self.public_key_multibase
+    }
+
+    fn export(&self, public_key: bool, private_key: bool, include_context: bool) -> KeyPair {
+        KeyPair {
+            id: self.id.clone(),
+            _type: self._type.clone(),
+            context: match include_context {
+                true => Some(Vec::from([String::from(SUITE_CONTEXT)])),
+                false => None,
+            },
+            public_key_base58: None,
+            private_key_base58: None,
+            private_key_multibase: None,
+            public_key_multibase: None,
+            revoked: Some(self.revoked),
+            controller: self.controller.clone(),
+            blockchain_account_id: None,
+            public_key_hex: None,
+            public_key_base64: None,
+            public_key_pem: None,
+            private_key_hex: None,
+            private_key_base64: None,
+            private_key_pem: None,
+            value: None,
+            ethereum_address: None,
+            public_key_jwk: match public_key {
+                true => Some(self.public_key_jwk.clone()),
+                false => None,
+            },
+            private_key_jwk: match private_key {
+                true => self.private_key_jwk.clone(),
+                false => None,
+            },
+        }
+    }
+}
+
+impl KeyFingerprint for EcdsaSecp256k1VerificationKey2019 {
+    fn get_fingerprint_key_bytes(&self) -> Result<([u8; 2], Vec<u8>), Error> {
+        Ok(multicodec::decode_fingerprint(&self.public_key_multibase)?)
+    }
+}
@@ -1,7 +1,20 @@
+use ed25519_dalek::{hazmat::ExpandedSecretKey, VerifyingKey};
 use fi_common::{
     error::Error,
     keys::{KeyPair, VerificationKey},
 };
+use rand_core::{CryptoRngCore, OsRng};
+
+use crate::{
+    ed25519_verification_key2020::Ed25519VerificationKey2020,
+    fingerprint::{self, KeyFingerprint},
+    multicodec::{self, MULTICODEC_ED25519_PRIV_HEADER, MULTICODEC_ED25519_PUB_HEADER},
+    signature::{copy_expanded_secret_key, SignatureError, SignatureFailure, SignatureKey},
+    util::{
+        base58_decode, ed25519_private_key_to_public_key, generate_ed25519_key_pair,
+        multibase_encode, multibase_to_base58,
+    },
+};
 
 pub(crate) const SUITE_ID: &str = "Ed25519VerificationKey2018";
 pub(crate) const SUITE_CONTEXT: &str = "https://w3id.org/security/suites/ed25519-2018/v1";
@@ -12,6 +25,7 @@ pub struct Ed25519VerificationKey2018 {
     controller: Option<String>,
     public_key_base58: String,
     private_key_base58: Option<String>,
+    expanded_secret_key: Option<ExpandedSecretKey>,
     revoked: bool,
 }
 
@@ -29,49 +43,104 @@ impl Ed25519VerificationKey2018 {
             id = Some(format!("{}#{}", ctrler, fprint));
         }
 
-        return Ed25519VerificationKey2018 {
+        Ed25519VerificationKey2018 {
             _type: String::from(SUITE_ID),
             id,
             controller,
             private_key_base58,
             public_key_base58,
+            expanded_secret_key: None,
             revoked: false,
-        };
+        }
+    }
+
+    /// Generates a key pair with the OS random number generator, see
+    /// [`Ed25519VerificationKey2018::generate_with_rng`].
+    pub fn generate() -> Self {
+        Ed25519VerificationKey2018::generate_with_rng(&mut OsRng)
+    }
+
+    /// Generates a key pair whose controller is its `did:key`. The private key
+    /// is the seed followed by the public key.
+    pub fn generate_with_rng(rng: &mut impl CryptoRngCore) -> Self {
+        let (seed, public_key) = generate_ed25519_key_pair(rng);
+
+        let mut private_key = seed.to_vec();
+        private_key.extend_from_slice(&public_key);
+
+        Ed25519VerificationKey2018::from_key_bytes(&public_key, private_key)
+    }
+
+    /// The key pair of raw private key bytes, whose controller is its
+    /// `did:key`. The private key is either a 32 byte seed or a 64 byte
+    /// libsodium `crypto_sign` secret key (the seed followed by the public
+    /// key). A seed is stored followed by its public key. An expanded secret
+    /// key is imported with
+    /// [`Ed25519VerificationKey2018::from_expanded_secret_key`].
+    pub fn from_private_key_bytes(private_key: &[u8]) -> Result<Self, Error> {
+        let public_key = ed25519_private_key_to_public_key(private_key)?;
+
+        let mut private_key = private_key.to_vec();
+        if private_key.len() == public_key.len() {
+            private_key.extend_from_slice(&public_key);
+        }
+
+        Ok(Ed25519VerificationKey2018::from_key_bytes(
+            &public_key,
+            private_key,
+        ))
+    }
+
+    /// The key pair of a 64 byte expanded secret key, i.e. the clamped scalar
+    /// followed by the nonce prefix, whose controller is its `did:key`. Such a
+    /// key has no seed, so it is kept apart from the private key: it signs,
+    /// but it is not exported and has no X25519 private key.
+    pub fn from_expanded_secret_key(expanded_secret_key: &[u8; 64]) -> Self {
+        let expanded_secret_key = ExpandedSecretKey::from_bytes(expanded_secret_key);
+        let public_key = VerifyingKey::from(&expanded_secret_key).to_bytes();
+
+        let fingerprint =
+            multibase_encode(&MULTICODEC_ED25519_PUB_HEADER, &mut public_key.to_vec());
+        let mut key_pair = Ed25519VerificationKey2018::new(
+            Some(format!("did:key:{}", fingerprint)),
+            bs58::encode(public_key).into_string(),
+            None,
+            Some(fingerprint),
+        );
+        key_pair.expanded_secret_key = Some(expanded_secret_key);
+
+        key_pair
+    }
+
+    fn from_key_bytes(public_key: &[u8; 32], private_key: Vec<u8>) -> Self {
+        let fingerprint =
+            multibase_encode(&MULTICODEC_ED25519_PUB_HEADER, &mut public_key.to_vec());
+
+        Ed25519VerificationKey2018::new(
+            Some(format!("did:key:{}", fingerprint)),
+            bs58::encode(public_key).into_string(),
+            Some(bs58::encode(private_key).into_string()),
+            Some(fingerprint),
+        )
     }
 }
 
 impl VerificationKey for Ed25519VerificationKey2018 {
     fn from_fingerprint(fingerprint: &str) -> Result<Self, Error> {
-        if !fingerprint[0..1].eq("z") {
+        let (header, public_key) = multicodec::decode_fingerprint(fingerprint)?;
+        if header != MULTICODEC_ED25519_PUB_HEADER {
             return Err(Error::new(
-                "`fingerprint` must be a multibase encoded string.",
-            ));
-        }
-
-        let buffer = bs58::decode(&fingerprint[1..]);
-        let decoded = match buffer.into_vec() {
-            Ok(val) => val,
-            Err(_error) => {
-                return Err(Error::new(
-                    "Couldn't convert decoded bs58 values into a vec",
-                ));
-            }
-        };
-
-        if decoded[0] == 0xed && decoded[1] == 0x01 {
-            let encoded_public_key_builder = bs58::encode(&decoded[2..]);
-            let encoded_public_key = encoded_public_key_builder.into_string();
-            return Ok(Ed25519VerificationKey2018::new(
-                None,
-                encoded_public_key,
-                None,
-                Some(String::from(fingerprint)),
+                format!("Unsupported fingerprint {}", fingerprint).as_str(),
             ));
         }
+        multicodec::validate_public_key(&header, &public_key)?;
 
-        return Err(Error::new(
-            format!("Unsupported fingerprint {}", fingerprint).as_str(),
-        ));
+        Ok(Ed25519VerificationKey2018::new(
+            Some(format!("did:key:{}", fingerprint)),
+            bs58::encode(public_key).into_string(),
+            None,
+            Some(String::from(fingerprint)),
+        ))
     }
 
     fn get_suite_id() -> &'static str
@@ -143,6 +212,7 @@ impl VerificationKey for Ed25519VerificationKey2018 {
             value: None,
             ethereum_address: None,
             public_key_jwk: None,
+            private_key_jwk: None,
         }
     }
 
@@ -150,3 +220,108 @@ impl VerificationKey for Ed25519VerificationKey2018 {
         self._type.clone()
     }
 }
+
+impl KeyFingerprint for Ed25519VerificationKey2018 {
+    fn get_fingerprint_key_bytes(&self) -> Result<([u8; 2], Vec<u8>), Error> {
+        fingerprint::base58_key_bytes(&MULTICODEC_ED25519_PUB_HEADER, &self.public_key_base58)
+    }
+}
+
+impl SignatureKey for Ed25519VerificationKey2018 {
+    fn get_signature_key_bytes(&self) -> Result<(Vec<u8>, Option<Vec<u8>>), SignatureFailure> {
+        let public_key = match bs58::decode(&self.public_key_base58).into_vec() {
+            Ok(val) => val,
+            Err(error) => {
+                return Err(SignatureFailure::new(
+                    SignatureError::InvalidPublicKey,
+                    error.to_string().as_str(),
+                ))
+            }
+        };
+
+        let private_key = match &self.private_key_base58 {
+            Some(private_key_base58) => match bs58::decode(private_key_base58).into_vec() {
+                Ok(val) => Some(val),
+                Err(error) => {
+                    return Err(SignatureFailure::new(
+                        SignatureError::InvalidPrivateKey,
+                        error.to_string().as_str(),
+                    ))
+                }
+            },
+            None => None,
+        };
+
+        Ok((public_key, private_key))
+    }
+
+    fn get_expanded_secret_key(&self) -> Option<&ExpandedSecretKey> {
+        self.expanded_secret_key.as_ref()
+    }
+}
+
+impl TryFrom<&Ed25519VerificationKey2020> for Ed25519VerificationKey2018 {
+    type Error = Error;
+
+    /// The same key pair with base58 keys, keeping the controller, id,
+    /// expanded secret key and revocation status.
+    fn try_from(key_pair: &Ed25519VerificationKey2020) -> Result<Self, Self::Error> {
+        let exported = key_pair.export(true, true, false);
+
+        let private_key_base58 = match &exported.private_key_multibase {
+            Some(val) => Some(multibase_to_base58(&MULTICODEC_ED25519_PRIV_HEADER, val)?),
+            None => None,
+        };
+
+        Ok(Ed25519VerificationKey2018 {
+            _type: String::from(SUITE_ID),
+            id: exported.id,
+            controller: exported.controller,
+            public_key_base58: multibase_to_base58(
+                &MULTICODEC_ED25519_PUB_HEADER,
+                key_pair.get_public_key_content(),
+            )?,
+            private_key_base58,
+            expanded_secret_key: copy_expanded_secret_key(key_pair.get_expanded_secret_key()),
+            revoked: exported.revoked.unwrap_or(false),
+        })
+    }
+}
+
+impl TryFrom<&KeyPair> for Ed25519VerificationKey2018 {
+    type Error = Error;
+
+    /// The key pair of an exported `KeyPair`, e.g. one loaded from a keystore.
+    fn try_from(key_pair: &KeyPair) -> Result<Self, Self::Error> {
+        if key_pair._type != SUITE_ID {
+            return Err(Error::new(
+                format!("Key pair type '{}' is not '{}'", key_pair._type, SUITE_ID).as_str(),
+            ));
+        }
+
+        let public_key_base58 = match &key_pair.public_key_base58 {
+            Some(val) => val.clone(),
+            None => return Err(Error::new("Key pair is missing 'publicKeyBase58'")),
+        };
+        let private_key = match &key_pair.private_key_base58 {
+            Some(val) => Some(base58_decode(val)?),
+            None => None,
+        };
+        multicodec::check_key_pair(
+            &MULTICODEC_ED25519_PUB_HEADER,
+            &base58_decode(&public_key_base58)?,
+            private_key.as_deref(),
+        )?;
+
+        let mut converted = Ed25519VerificationKey2018::new(
+            key_pair.controller.clone(),
+            public_key_base58,
+            key_pair.private_key_base58.clone(),
+            None,
+        );
+        converted.id = key_pair.id.clone();
+        converted.revoked = key_pair.revoked.unwrap_or(false);
+
+        Ok(converted)
+    }
+}
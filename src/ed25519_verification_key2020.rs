@@ -1,24 +1,31 @@
+use ed25519_dalek::{hazmat::ExpandedSecretKey, VerifyingKey};
 use fi_common::{
     error::Error,
     keys::{KeyPair, VerificationKey},
 };
+use rand_core::{CryptoRngCore, OsRng};
+
+use crate::{
+    ed25519_verification_key2018::Ed25519VerificationKey2018,
+    fingerprint::KeyFingerprint,
+    multicodec::{self, MULTICODEC_ED25519_PRIV_HEADER, MULTICODEC_ED25519_PUB_HEADER},
+    signature::{copy_expanded_secret_key, SignatureError, SignatureFailure, SignatureKey},
+    util::{
+        base58_to_multibase, ed25519_private_key_to_public_key, multibase_decode, multibase_encode,
+        ED25519_KEY_LENGTH, MULTIBASE_BASE58BTC_HEADER,
+    },
+};
 
 pub(crate) const SUITE_ID: &str = "Ed25519VerificationKey2020";
 pub(crate) const SUITE_CONTEXT: &str = "https://w3id.org/security/suites/ed25519-2020/v1";
 
-// multibase base58-btc header
-const MULTIBASE_BASE58BTC_HEADER: &str = "z";
-// multicodec ed25519-pub header as varint
-const MULTICODEC_ED25519_PUB_HEADER: [u8; 2] = [0xed, 0x01];
-// multicodec ed25519-priv header as varint
-const MULTICODEC_ED25519_PRIV_HEADER: [u8; 2] = [0xed, 0x01];
-
 pub struct Ed25519VerificationKey2020 {
     _type: String,
     id: Option<String>,
     controller: Option<String>,
     public_key_multibase: String,
     private_key_multibase: Option<String>,
+    expanded_secret_key: Option<ExpandedSecretKey>,
     revoked: bool,
 }
 
@@ -42,19 +49,21 @@ impl Ed25519VerificationKey2020 {
             ));
         }
 
-        if private_key_multibase.is_some()
-            && !Ed25519VerificationKey2020::is_valid_key_header(
-                &private_key_multibase.clone().unwrap(),
+        if let Some(private_key) = &private_key_multibase {
+            if !Ed25519VerificationKey2020::is_valid_key_header(
+                private_key,
                 &MULTICODEC_ED25519_PRIV_HEADER,
-            )
-        {
-            return Err(Error::new(
-                format!(
-                    "'publicKeyMultibase' has invalid header bytes: '{}'.",
-                    private_key_multibase.unwrap()
-                )
-                .as_str(),
-            ));
+            ) {
+                return Err(Error::new(
+                    format!(
+                        "'privateKeyMultibase' has invalid header bytes: '{}'.",
+                        private_key
+                    )
+                    .as_str(),
+                ));
+            }
+
+            Ed25519VerificationKey2020::check_private_key(&public_key_multibase, private_key)?;
         }
 
         let mut id: Option<String> = None;
@@ -70,29 +79,116 @@ impl Ed25519VerificationKey2020 {
             controller,
             public_key_multibase,
             private_key_multibase,
+            expanded_secret_key: None,
             revoked: false,
         })
     }
 
+    /// Generates a key pair with the OS random number generator, see
+    /// [`Ed25519VerificationKey2020::generate_with_rng`].
+    pub fn generate() -> Result<Self, Error> {
+        Ed25519VerificationKey2020::generate_with_rng(&mut OsRng)
+    }
+
+    /// Generates a key pair whose controller is its `did:key`, see
+    /// [`Ed25519VerificationKey2020::from_seed`].
+    pub fn generate_with_rng(rng: &mut impl CryptoRngCore) -> Result<Self, Error> {
+        let mut seed = [0u8; ED25519_KEY_LENGTH];
+        rng.fill_bytes(&mut seed);
+
+        Ed25519VerificationKey2020::from_seed(&seed)
+    }
+
+    /// The key pair of a 32 byte seed, whose controller is its `did:key`.
+    pub fn from_seed(seed: &[u8; 32]) -> Result<Self, Error> {
+        Ed25519VerificationKey2020::from_private_key_bytes(seed)
+    }
+
+    /// The key pair of raw private key bytes, whose controller is its
+    /// `did:key`. The private key is either a 32 byte seed or a 64 byte
+    /// libsodium `crypto_sign` secret key (the seed followed by the public
+    /// key). A seed is stored followed by its public key. An expanded secret
+    /// key is imported with
+    /// [`Ed25519VerificationKey2020::from_expanded_secret_key`].
+    pub fn from_private_key_bytes(private_key: &[u8]) -> Result<Self, Error> {
+        let public_key = ed25519_private_key_to_public_key(private_key)?;
+
+        let fingerprint =
+            multibase_encode(&MULTICODEC_ED25519_PUB_HEADER, &mut public_key.to_vec());
+        let mut private_key = private_key.to_vec();
+        if private_key.len() == ED25519_KEY_LENGTH {
+            private_key.extend_from_slice(&public_key);
+        }
+
+        Ed25519VerificationKey2020::new(
+            Some(format!("did:key:{}", fingerprint)),
+            fingerprint.clone(),
+            Some(multibase_encode(
+                &MULTICODEC_ED25519_PRIV_HEADER,
+                &mut private_key,
+            )),
+            Some(fingerprint),
+        )
+    }
+
+    /// The key pair of a 64 byte expanded secret key, i.e. the clamped scalar
+    /// followed by the nonce prefix, whose controller is its `did:key`. Such a
+    /// key has no seed, so it is kept apart from the private key: it signs,
+    /// but it is not exported and has no X25519 private key.
+    pub fn from_expanded_secret_key(expanded_secret_key: &[u8; 64]) -> Result<Self, Error> {
+        let expanded_secret_key = ExpandedSecretKey::from_bytes(expanded_secret_key);
+        let public_key = VerifyingKey::from(&expanded_secret_key).to_bytes();
+
+        let fingerprint =
+            multibase_encode(&MULTICODEC_ED25519_PUB_HEADER, &mut public_key.to_vec());
+        let mut key_pair = Ed25519VerificationKey2020::new(
+            Some(format!("did:key:{}", fingerprint)),
+            fingerprint.clone(),
+            None,
+            Some(fingerprint),
+        )?;
+        key_pair.expanded_secret_key = Some(expanded_secret_key);
+
+        Ok(key_pair)
+    }
+
+    // checks that the private key, in any of the forms of
+    // `from_private_key_bytes`, belongs to the public key
+    fn check_private_key(
+        public_key_multibase: &String,
+        private_key_multibase: &String,
+    ) -> Result<(), Error> {
+        let public_key = multibase_decode(&MULTICODEC_ED25519_PUB_HEADER, public_key_multibase)?;
+        let private_key = multibase_decode(&MULTICODEC_ED25519_PRIV_HEADER, private_key_multibase)?;
+
+        if ed25519_private_key_to_public_key(&private_key)?[..] != public_key[..] {
+            return Err(Error::new(
+                "'privateKeyMultibase' does not belong to 'publicKeyMultibase'",
+            ));
+        }
+
+        Ok(())
+    }
+
     fn is_valid_key_header(multibase_key: &String, expected_header: &[u8; 2]) -> bool {
-        if !multibase_key[0..1].eq(MULTIBASE_BASE58BTC_HEADER) {
+        if !multibase_key.starts_with(MULTIBASE_BASE58BTC_HEADER) {
             return false;
         }
 
-        let decoded = multibase::decode(&multibase_key);
+        let decoded = multibase::decode(multibase_key);
         let (_, decoded_key_bytes) = match decoded {
             Ok(val) => val,
             Err(_error) => return false,
         };
 
-        decoded_key_bytes[0] == expected_header[0] && decoded_key_bytes[1] == expected_header[1]
+        decoded_key_bytes.len() >= 2 && decoded_key_bytes[..2] == expected_header[..]
     }
 }
 
 impl VerificationKey for Ed25519VerificationKey2020 {
     fn from_fingerprint(fingerprint: &str) -> Result<Self, Error> {
         Ed25519VerificationKey2020::new(
-            None,
+            Some(format!("did:key:{}", fingerprint)),
             String::from(fingerprint),
             None,
             Some(String::from(fingerprint)),
@@ -166,6 +262,7 @@ impl VerificationKey for Ed25519VerificationKey2020 {
             value: None,
             ethereum_address: None,
             public_key_jwk: None,
+            private_key_jwk: None,
         }
     }
 
@@ -173,3 +270,105 @@ impl VerificationKey for Ed25519VerificationKey2020 {
         self._type.clone()
     }
 }
+
+impl KeyFingerprint for Ed25519VerificationKey2020 {
+    fn get_fingerprint_key_bytes(&self) -> Result<([u8; 2], Vec<u8>), Error> {
+        Ok(multicodec::decode_fingerprint(&self.public_key_multibase)?)
+    }
+}
+
+impl SignatureKey for Ed25519VerificationKey2020 {
+    fn get_signature_key_bytes(&self) -> Result<(Vec<u8>, Option<Vec<u8>>), SignatureFailure> {
+        let public_key =
+            match multibase_decode(&MULTICODEC_ED25519_PUB_HEADER, &self.public_key_multibase) {
+                Ok(val) => val,
+                Err(error) => {
+                    return Err(SignatureFailure::new(
+                        SignatureError::InvalidPublicKey,
+                        error.to_string().as_str(),
+                    ))
+                }
+            };
+
+        let private_key = match &self.private_key_multibase {
+            Some(private_key_multibase) => {
+                match multibase_decode(&MULTICODEC_ED25519_PRIV_HEADER, private_key_multibase) {
+                    Ok(val) => Some(val),
+                    Err(error) => {
+                        return Err(SignatureFailure::new(
+                            SignatureError::InvalidPrivateKey,
+                            error.to_string().as_str(),
+                        ))
+                    }
+                }
+            }
+            None => None,
+        };
+
+        Ok((public_key, private_key))
+    }
+
+    fn get_expanded_secret_key(&self) -> Option<&ExpandedSecretKey> {
+        self.expanded_secret_key.as_ref()
+    }
+}
+
+impl TryFrom<&Ed25519VerificationKey2018> for Ed25519VerificationKey2020 {
+    type Error = Error;
+
+    /// The same key pair with multibase keys, keeping the controller, id,
+    /// expanded secret key and revocation status. The private key must belong to the public key.
+    fn try_from(key_pair: &Ed25519VerificationKey2018) -> Result<Self, Self::Error> {
+        let exported = key_pair.export(true, true, false);
+
+        let private_key_multibase = match &exported.private_key_base58 {
+            Some(val) => Some(base58_to_multibase(&MULTICODEC_ED25519_PRIV_HEADER, val)?),
+            None => None,
+        };
+
+        let mut converted = Ed25519VerificationKey2020::new(
+            exported.controller,
+            base58_to_multibase(
+                &MULTICODEC_ED25519_PUB_HEADER,
+                key_pair.get_public_key_content(),
+            )?,
+            private_key_multibase,
+            None,
+        )?;
+        converted.id = exported.id;
+        converted.expanded_secret_key =
+            copy_expanded_secret_key(key_pair.get_expanded_secret_key());
+        converted.revoked = exported.revoked.unwrap_or(false);
+
+        Ok(converted)
+    }
+}
+
+impl TryFrom<&KeyPair> for Ed25519VerificationKey2020 {
+    type Error = Error;
+
+    /// The key pair of an exported `KeyPair`, e.g. one loaded from a keystore.
+    fn try_from(key_pair: &KeyPair) -> Result<Self, Self::Error> {
+        if key_pair._type != SUITE_ID {
+            return Err(Error::new(
+                format!("Key pair type '{}' is not '{}'", key_pair._type, SUITE_ID).as_str(),
+            ));
+        }
+
+        let public_key_multibase = match &key_pair.public_key_multibase {
+            Some(val) => val.clone(),
+            None => return Err(Error::new("Key pair is missing 'publicKeyMultibase'")),
+        };
+
+        let mut converted = Ed25519VerificationKey2020::new(
+            key_pair.controller.clone(),
+            public_key_multibase,
+            key_pair.private_key_multibase.clone(),
+            None,
+        )?;
+        converted.id = key_pair.id.clone();
+        converted.revoked = key_pair.revoked.unwrap_or(false);
+
+        Ok(converted)
+    }
+}
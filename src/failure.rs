@@ -0,0 +1,78 @@
+use std::fmt::Display;
+
+use fi_common::error::Error;
+
+/// Declares an error code enum whose variants are written as their camelCase
+/// codes by `as_str` and `Display`.
+macro_rules! error_codes {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident => $code:literal,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )*
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $code,)*
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
+
+pub(crate) use error_codes;
+
+/// An error code together with a human readable message.
+#[derive(Debug, Clone)]
+pub struct Failure<E> {
+    pub error: E,
+    pub message: String,
+}
+
+impl<E> Failure<E> {
+    pub fn new(error: E, message: &str) -> Self {
+        Failure {
+            error,
+            message: String::from(message),
+        }
+    }
+
+    pub fn from_error(error: E, source: Error) -> Self {
+        Failure {
+            error,
+            message: source.to_string(),
+        }
+    }
+}
+
+impl<E: Display> Display for Failure<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.error, self.message)
+    }
+}
+
+// the message of the error keeps the code, e.g. `invalidDid: ...`
+impl<E: Display> From<Failure<E>> for Error {
+    fn from(failure: Failure<E>) -> Self {
+        Error::new(failure.to_string().as_str())
+    }
+}
@@ -0,0 +1,100 @@
+use fi_common::error::Error;
+
+use crate::{
+    failure::{error_codes, Failure},
+    util::{multibase_encode, MULTIBASE_BASE58BTC_HEADER},
+};
+
+error_codes! {
+    /// The part of a presented fingerprint that does not match a key.
+    pub enum FingerprintError {
+        /// The fingerprint is not base58-btc multibase encoded.
+        InvalidMultibasePrefix => "invalidMultibasePrefix",
+        /// The multicodec header names another key type than the key's.
+        InvalidMulticodecHeader => "invalidMulticodecHeader",
+        /// The key bytes are not the key's public key.
+        InvalidKeyBytes => "invalidKeyBytes",
+    }
+}
+
+pub type FingerprintFailure = Failure<FingerprintError>;
+
+/// The multibase multicodec fingerprint of a key, i.e. the method specific id
+/// of its `did:key`.
+pub trait KeyFingerprint {
+    /// The multicodec public key header and raw public key bytes of the key.
+    fn get_fingerprint_key_bytes(&self) -> Result<([u8; 2], Vec<u8>), Error>;
+
+    fn fingerprint(&self) -> Result<String, Error> {
+        let (header, mut key_bytes) = self.get_fingerprint_key_bytes()?;
+
+        Ok(multibase_encode(&header, &mut key_bytes))
+    }
+
+    fn did_key(&self) -> Result<String, Error> {
+        Ok(format!("did:key:{}", self.fingerprint()?))
+    }
+
+    /// Checks that `fingerprint` is the fingerprint of the key.
+    fn verify_fingerprint(&self, fingerprint: &str) -> Result<(), FingerprintFailure> {
+        let (header, key_bytes) = match self.get_fingerprint_key_bytes() {
+            Ok(val) => val,
+            Err(error) => {
+                return Err(FingerprintFailure::new(
+                    FingerprintError::InvalidKeyBytes,
+                    error.to_string().as_str(),
+                ))
+            }
+        };
+
+        let encoded = match fingerprint.strip_prefix(MULTIBASE_BASE58BTC_HEADER) {
+            Some(val) => val,
+            None => {
+                return Err(FingerprintFailure::new(
+                    FingerprintError::InvalidMultibasePrefix,
+                    "Fingerprint must be multibase base58btc encoded (must start with 'z')",
+                ))
+            }
+        };
+        let decoded = match bs58::decode(encoded).into_vec() {
+            Ok(val) => val,
+            Err(error) => {
+                return Err(FingerprintFailure::new(
+                    FingerprintError::InvalidMultibasePrefix,
+                    format!("Fingerprint is not base58btc encoded: {}", error).as_str(),
+                ))
+            }
+        };
+
+        if decoded.len() < 2 || decoded[..2] != header {
+            return Err(FingerprintFailure::new(
+                FingerprintError::InvalidMulticodecHeader,
+                format!(
+                    "Fingerprint must have the multicodec header 0x{:02x}{:02x}",
+                    header[0], header[1]
+                )
+                .as_str(),
+            ));
+        }
+
+        if decoded[2..] != key_bytes[..] {
+            return Err(FingerprintFailure::new(
+                FingerprintError::InvalidKeyBytes,
+                "Fingerprint key bytes do not match the public key",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+// decodes a base58 public key of the key type named by the multicodec `header`
+pub(crate) fn base58_key_bytes(
+    header: &[u8; 2],
+    public_key_base58: &str,
+) -> Result<([u8; 2], Vec<u8>), Error> {
+    match bs58::decode(public_key_base58).into_vec() {
+        Ok(val) => Ok((*header, val)),
+        Err(error) => Err(Error::new(error.to_string().as_str())),
+    }
+}
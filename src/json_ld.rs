@@ -0,0 +1,235 @@
+use fi_common::error::Error;
+use oxjsonld::{JsonLdLoadDocumentOptions, JsonLdParser, JsonLdRemoteDocument};
+use oxrdf::{
+    dataset::{CanonicalizationAlgorithm, CanonicalizationHashAlgorithm},
+    Dataset, GraphName, NamedOrBlankNode, Quad, Term,
+};
+use serde_json::Value;
+
+use crate::{
+    bls12381_g2_key2020, ecdsa_secp256k1_verification_key2019, ed25519_verification_key2018,
+    ed25519_verification_key2020, json_web_key2020, multikey, resolution,
+    x25519_key_agreement_key2019, x25519_key_agreement_key2020,
+};
+
+// the JSON-LD contexts bundled with the crate, by URL. Contexts are never
+// fetched, so that canonicalizing a document does not depend on the network.
+// The suite contexts that `ssi_contexts` does not ship are kept in `contexts/`.
+const CONTEXTS: [(&str, &str); 19] = [
+    (
+        "https://www.w3.org/2018/credentials/v1",
+        ssi_contexts::CREDENTIALS_V1,
+    ),
+    (
+        "https://www.w3.org/2018/credentials/examples/v1",
+        ssi_contexts::CREDENTIALS_EXAMPLES_V1,
+    ),
+    // imported by the credentials examples v1 context
+    ("https://www.w3.org/ns/odrl.jsonld", ssi_contexts::ODRL),
+    (
+        "https://www.w3.org/ns/credentials/v2",
+        ssi_contexts::CREDENTIALS_V2,
+    ),
+    (
+        "https://www.w3.org/ns/credentials/examples/v2",
+        ssi_contexts::CREDENTIALS_EXAMPLES_V2,
+    ),
+    ("https://www.w3.org/ns/did/v1", ssi_contexts::DID_V1),
+    (
+        resolution::DID_RESOLUTION_CONTEXT_URL,
+        ssi_contexts::DID_RESOLUTION_V1,
+    ),
+    ("https://w3id.org/security/v1", ssi_contexts::SECURITY_V1),
+    ("https://w3id.org/security/v2", ssi_contexts::SECURITY_V2),
+    (
+        "https://w3id.org/security/data-integrity/v1",
+        ssi_contexts::W3ID_DATA_INTEGRITY_V1,
+    ),
+    (
+        "https://w3id.org/security/data-integrity/v2",
+        ssi_contexts::W3ID_DATA_INTEGRITY_V2,
+    ),
+    (
+        ed25519_verification_key2020::SUITE_CONTEXT,
+        ssi_contexts::W3ID_ED2020_V1,
+    ),
+    (multikey::SUITE_CONTEXT, ssi_contexts::W3ID_MULTIKEY_V1),
+    (
+        json_web_key2020::SUITE_CONTEXT,
+        ssi_contexts::W3ID_JWS2020_V1,
+    ),
+    (
+        ed25519_verification_key2018::SUITE_CONTEXT,
+        include_str!("../contexts/w3id-ed25519-signature-2018-v1.jsonld"),
+    ),
+    (
+        x25519_key_agreement_key2019::SUITE_CONTEXT,
+        include_str!("../contexts/w3id-x25519-key-agreement-2019-v1.jsonld"),
+    ),
+    (
+        x25519_key_agreement_key2020::SUITE_CONTEXT,
+        include_str!("../contexts/w3id-x25519-key-agreement-2020-v1.jsonld"),
+    ),
+    (
+        ecdsa_secp256k1_verification_key2019::SUITE_CONTEXT,
+        include_str!("../contexts/w3id-secp256k1-2019-v1.jsonld"),
+    ),
+    // the BBS+ context, which the G1 and G2 key suites share
+    (bls12381_g2_key2020::SUITE_CONTEXT, ssi_contexts::BBS_V1),
+];
+
+/// The bundled JSON-LD context document of `url`.
+pub fn load_context(url: &str) -> Result<&'static str, Error> {
+    match CONTEXTS.iter().find(|(val, _)| *val == url) {
+        Some((_, context)) => Ok(context),
+        None => Err(Error::new(
+            format!("JSON-LD context '{}' is not bundled", url).as_str(),
+        )),
+    }
+}
+
+/// Canonicalizes a JSON-LD document with the RDF Dataset Canonicalization
+/// algorithm RDFC-1.0, returning its canonical N-Quads.
+///
+/// Remote contexts are loaded with [`load_context`]. The document is
+/// canonicalized in safe mode: a term that the contexts do not define, a
+/// relative IRI or a `null` context fails instead of being dropped by the
+/// JSON-LD expansion, so that every value of the document is signed.
+pub fn canonicalize(document: &Value) -> Result<String, Error> {
+    if let Some(error) = find_null_context(document) {
+        return Err(error);
+    }
+
+    // undefined terms are expanded against the sentinel vocabulary and
+    // relative IRIs against the sentinel base, instead of being dropped
+    let mut document = document.clone();
+    if let Value::Object(object) = &mut document {
+        let mut contexts = vec![serde_json::json!({ "@vocab": UNDEFINED_VOCAB })];
+        match object.remove("@context") {
+            Some(Value::Array(val)) => contexts.extend(val),
+            Some(val) => contexts.push(val),
+            None => {}
+        };
+        object.insert(String::from("@context"), Value::Array(contexts));
+    }
+
+    let bytes = match serde_json::to_vec(&document) {
+        Ok(val) => val,
+        Err(error) => return Err(Error::new(error.to_string().as_str())),
+    };
+
+    let parser = match JsonLdParser::new().with_base_iri(UNDEFINED_BASE) {
+        Ok(val) => val,
+        Err(error) => return Err(Error::new(error.to_string().as_str())),
+    };
+    let parser = parser
+        .for_slice(&bytes)
+        .with_load_document_callback(load_document);
+
+    let mut dataset = Dataset::new();
+    for quad in parser {
+        match quad {
+            Ok(val) => {
+                if let Some(error) = find_undefined_term(&val) {
+                    return Err(error);
+                }
+                dataset.insert(&val)
+            }
+            Err(error) => {
+                return Err(Error::new(
+                    format!("Invalid JSON-LD document: {}", error).as_str(),
+                ))
+            }
+        };
+    }
+    dataset.canonicalize(CanonicalizationAlgorithm::Rdfc10 {
+        hash_algorithm: CanonicalizationHashAlgorithm::Sha256,
+    });
+
+    // the canonical N-Quads are sorted in code point order
+    let mut lines: Vec<String> = dataset.iter().map(|val| format!("{} .\n", val)).collect();
+    lines.sort();
+
+    Ok(lines.concat())
+}
+
+// the IRIs that undefined terms and relative IRIs expand to in safe mode
+const UNDEFINED_IRI: &str = "https://undefined.invalid/";
+const UNDEFINED_VOCAB: &str = "https://undefined.invalid/vocab#";
+const UNDEFINED_BASE: &str = "https://undefined.invalid/base/";
+
+// a `null` context clears the active context, and with it the sentinel
+// vocabulary that catches undefined terms
+fn find_null_context(value: &Value) -> Option<Error> {
+    match value {
+        Value::Object(object) => {
+            for (key, val) in object {
+                let is_null = match (key.as_str(), val) {
+                    ("@context", Value::Null) => true,
+                    ("@context", Value::Array(contexts)) => contexts.iter().any(Value::is_null),
+                    ("@vocab", Value::Null) => true,
+                    _ => false,
+                };
+                if is_null {
+                    return Some(Error::new(
+                        format!("A null '{}' is not allowed in the document", key).as_str(),
+                    ));
+                }
+                if let Some(error) = find_null_context(val) {
+                    return Some(error);
+                }
+            }
+            None
+        }
+        Value::Array(values) => values.iter().find_map(find_null_context),
+        _ => None,
+    }
+}
+
+fn find_undefined_term(quad: &Quad) -> Option<Error> {
+    let mut iris = Vec::new();
+    if let NamedOrBlankNode::NamedNode(val) = &quad.subject {
+        iris.push(val.as_str());
+    }
+    iris.push(quad.predicate.as_str());
+    match &quad.object {
+        Term::NamedNode(val) => iris.push(val.as_str()),
+        Term::Literal(val) => iris.push(val.datatype().as_str()),
+        _ => {}
+    };
+    if let GraphName::NamedNode(val) = &quad.graph_name {
+        iris.push(val.as_str());
+    }
+
+    for iri in iris {
+        if let Some(val) = iri.strip_prefix(UNDEFINED_VOCAB) {
+            return Some(Error::new(
+                format!("The term '{}' is not defined by the contexts", val).as_str(),
+            ));
+        }
+        if let Some(val) = iri.strip_prefix(UNDEFINED_BASE) {
+            return Some(Error::new(
+                format!("The relative IRI '{}' is not allowed", val).as_str(),
+            ));
+        }
+        if iri.starts_with(UNDEFINED_IRI) {
+            return Some(Error::new(
+                format!("The IRI '{}' is not allowed", iri).as_str(),
+            ));
+        }
+    }
+    None
+}
+
+fn load_document(
+    url: &str,
+    _options: &JsonLdLoadDocumentOptions,
+) -> Result<JsonLdRemoteDocument, Box<dyn std::error::Error + Send + Sync>> {
+    match load_context(url) {
+        Ok(val) => Ok(JsonLdRemoteDocument {
+            document: val.as_bytes().to_vec(),
+            document_url: String::from(url),
+        }),
+        Err(error) => Err(error.to_string().into()),
+    }
+}
@@ -0,0 +1,279 @@
+use fi_common::{
+    error::Error,
+    keys::{AgreementKey, KeyPair, VerificationKey},
+};
+use serde_json::Value;
+
+use crate::{
+    fingerprint::KeyFingerprint,
+    jwk, multicodec,
+    multikey::Multikey,
+    util::{multibase_decode, multibase_encode},
+};
+
+pub(crate) const SUITE_ID: &str = "JsonWebKey2020";
+pub(crate) const SUITE_CONTEXT: &str = "https://w3id.org/security/suites/jws-2020/v1";
+
+/// A `JsonWebKey2020` verification method, holding the key as `publicKeyJwk`.
+/// Ed25519 keys are `OKP` / `Ed25519` JWKs, X25519 keys are `OKP` / `X25519`
+/// JWKs, secp256k1 and NIST P-256/P-384/P-521 keys are `EC` JWKs of their
+/// curve and RSA keys are `RSA` JWKs.
+///
+/// The key content is the multibase fingerprint of the public key, so a
+/// `JsonWebKey2020` converts to the other suites like a `Multikey` does. The
+/// private key is exported as `privateKeyJwk`, which is the only JWK with the
+/// `d` member.
+pub struct JsonWebKey2020 {
+    _type: String,
+    id: Option<String>,
+    controller: Option<String>,
+    public_key_jwk: Value,
+    private_key_jwk: Option<Value>,
+    public_key_multibase: String,
+    private_key_multibase: Option<String>,
+    revoked: bool,
+}
+
+impl JsonWebKey2020 {
+    pub fn new(
+        controller: Option<String>,
+        public_key_jwk: Value,
+        private_key_jwk: Option<Value>,
+        fingerprint: Option<String>,
+    ) -> Result<Self, Error> {
+        let decoded = jwk::decode_jwk(&public_key_jwk)?;
+        if decoded.private_key.is_some() {
+            return Err(Error::new(
+                "'publicKeyJwk' must not contain the private key 'd'",
+            ));
+        }
+        let public_key_header = decoded.public_key_header;
+        let mut public_key = decoded.public_key;
+        let public_key_jwk = jwk::public_key_jwk(&public_key_header, &public_key)?;
+
+        let mut private_key_multibase: Option<String> = None;
+        let private_key_jwk = match private_key_jwk {
+            Some(private_key_jwk) => {
+                let decoded = jwk::decode_jwk(&private_key_jwk)?;
+                if decoded.public_key_header != public_key_header
+                    || decoded.public_key != public_key
+                {
+                    return Err(Error::new(
+                        "'privateKeyJwk' does not belong to 'publicKeyJwk'",
+                    ));
+                }
+                let mut private_key = match decoded.private_key {
+                    Some(val) => val,
+                    None => {
+                        return Err(Error::new("'privateKeyJwk' is missing the private key 'd'"))
+                    }
+                };
+                if multicodec::derive_public_key(&public_key_header, &private_key)? != public_key {
+                    return Err(Error::new(
+                        "'privateKeyJwk' private key 'd' does not belong to 'publicKeyJwk'",
+                    ));
+                }
+
+                let private_key_jwk =
+                    jwk::private_key_jwk(&public_key_header, &public_key, &private_key)?;
+                private_key_multibase = Some(multibase_encode(
+                    &decoded.private_key_header,
+                    &mut private_key,
+                ));
+
+                Some(private_key_jwk)
+            }
+            None => None,
+        };
+
+        let mut id: Option<String> = None;
+        if controller.is_some() && fingerprint.is_some() {
+            let ctrler = controller.clone().unwrap();
+            let fprint = fingerprint.clone().unwrap();
+            id = Some(format!("{}#{}", ctrler, fprint));
+        }
+
+        Ok(JsonWebKey2020 {
+            _type: String::from(SUITE_ID),
+            id,
+            controller,
+            public_key_jwk,
+            private_key_jwk,
+            public_key_multibase: multibase_encode(&public_key_header, &mut public_key),
+            private_key_multibase,
+            revoked: false,
+        })
+    }
+
+    /// Expresses a `Multikey` as a `JsonWebKey2020`, keeping its controller.
+    pub fn from_multikey(multikey: &Multikey) -> Result<Self, Error> {
+        let public_key_multibase = VerificationKey::get_public_key_content(multikey);
+        let header = multikey.get_public_key_header();
+        let public_key = multibase_decode(&header, public_key_multibase)?;
+
+        let private_key_jwk = match VerificationKey::get_private_key_content(multikey) {
+            Some(private_key_multibase) => {
+                let private_key = match multibase::decode(private_key_multibase) {
+                    Ok((_, val)) if val.len() > 2 => val[2..].to_vec(),
+                    Ok(_) => return Err(Error::new("'privateKeyMultibase' is too short")),
+                    Err(error) => return Err(Error::new(error.to_string().as_str())),
+                };
+
+                Some(jwk::private_key_jwk(&header, &public_key, &private_key)?)
+            }
+            None => None,
+        };
+
+        JsonWebKey2020::new(
+            VerificationKey::get_controller(multikey).clone(),
+            jwk::public_key_jwk(&header, &public_key)?,
+            private_key_jwk,
+            Some(public_key_multibase.clone()),
+        )
+    }
+
+    /// Expresses an `Ed25519VerificationKey2018`, `Ed25519VerificationKey2020`
+    /// or Ed25519 `Multikey` as a `JsonWebKey2020`.
+    pub fn from_ed25519_verification_key(key_pair: &dyn VerificationKey) -> Result<Self, Error> {
+        JsonWebKey2020::from_multikey(&Multikey::from_ed25519_verification_key(key_pair)?)
+    }
+
+    /// Expresses an `X25519KeyAgreementKey2019`, `X25519KeyAgreementKey2020`
+    /// or X25519 `Multikey` as a `JsonWebKey2020`.
+    pub fn from_x25519_key_agreement_key(key_pair: &dyn AgreementKey) -> Result<Self, Error> {
+        JsonWebKey2020::from_multikey(&Multikey::from_x25519_key_agreement_key(key_pair)?)
+    }
+
+    /// Derives the X25519 key agreement `JsonWebKey2020` of an Ed25519
+    /// `JsonWebKey2020`.
+    pub fn to_x25519_key_agreement_key(&self) -> Result<Self, Error> {
+        JsonWebKey2020::from_multikey(
+            &Multikey::from_ed25519_verification_key(self)?.to_x25519_key_agreement_key()?,
+        )
+    }
+
+    pub fn get_public_key_jwk(&self) -> &Value {
+        &self.public_key_jwk
+    }
+
+    pub fn get_private_key_jwk(&self) -> &Option<Value> {
+        &self.private_key_jwk
+    }
+
+    fn to_key_pair(&self, public_key: bool, private_key: bool, include_context: bool) -> KeyPair {
+        KeyPair {
+            id: self.id.clone(),
+            _type: self._type.clone(),
+            context: match include_context {
+                true => Some(Vec::from([String::from(SUITE_CONTEXT)])),
+                false => None,
+            },
+            public_key_base58: None,
+            private_key_base58: None,
+            private_key_multibase: None,
+            public_key_multibase: None,
+            revoked: Some(self.revoked),
+            controller: self.controller.clone(),
+            blockchain_account_id: None,
+            public_key_hex: None,
+            public_key_base64: None,
+            public_key_pem: None,
+            private_key_hex: None,
+            private_key_base64: None,
+            private_key_pem: None,
+            value: None,
+            ethereum_address: None,
+            public_key_jwk: match public_key {
+                true => Some(self.public_key_jwk.clone()),
+                false => None,
+            },
+            private_key_jwk: match private_key {
+                true => self.private_key_jwk.clone(),
+                false => None,
+            },
+        }
+    }
+}
+
+impl VerificationKey for JsonWebKey2020 {
+    fn from_fingerprint(fingerprint: &str) -> Result<Self, Error> {
+        JsonWebKey2020::from_multikey(&Multikey::from_fingerprint(fingerprint)?)
+    }
+
+    fn get_suite_id() -> &'static str
+    where
+        Self: Sized,
+    {
+        SUITE_ID
+    }
+
+    fn get_current_suite_id(&self) -> &'static str {
+        SUITE_ID
+    }
+
+    fn get_suite_context() -> &'static str
+    where
+        Self: Sized,
+    {
+        SUITE_CONTEXT
+    }
+
+    fn get_current_suite_context(&self) -> &'static str {
+        SUITE_CONTEXT
+    }
+
+    fn get_controller(&self) -> &Option<String> {
+        &self.controller
+    }
+
+    fn get_type(&self) -> String {
+        self._type.clone()
+    }
+
+    fn get_private_key_content(&self) -> &Option<String> {
+        &self.private_key_multibase
+    }
+
+    fn get_public_key_content(&self) -> &String {
+        &self.public_key_multibase
+    }
+
+    fn export(&self, public_key: bool, private_key: bool, include_context: bool) -> KeyPair {
+        self.to_key_pair(public_key, private_key, include_context)
+    }
+}
+
+impl AgreementKey for JsonWebKey2020 {
+    fn get_suite_context() -> &'static str
+    where
+        Self: Sized,
+    {
+        SUITE_CONTEXT
+    }
+
+    fn get_current_suite_context(&self) -> &'static str {
+        SUITE_CONTEXT
+    }
+
+    fn get_controller(&self) -> &Option<String> {
+        &self.controller
+    }
+
+    fn get_private_key_content(&self) -> &Option<String> {
+        &self.private_key_multibase
+    }
+
+    fn get_public_key_content(&self) -> &String {
+        &self.public_key_multibase
+    }
+
+    fn export(&self, public_key: bool, private_key: bool, include_context: bool) -> KeyPair {
+        self.to_key_pair(public_key, private_key, include_context)
+    }
+}
+
+impl KeyFingerprint for JsonWebKey2020 {
+    fn get_fingerprint_key_bytes(&self) -> Result<([u8; 2], Vec<u8>), Error> {
+        Ok(multicodec::decode_fingerprint(&self.public_key_multibase)?)
+    }
+}
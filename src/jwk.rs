@@ -0,0 +1,335 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use fi_common::error::Error;
+use rsa::{
+    pkcs1::{DecodeRsaPublicKey, EncodeRsaPublicKey},
+    traits::PublicKeyParts,
+    BigUint, RsaPublicKey,
+};
+use serde_json::{Map, Value};
+
+use crate::multicodec::{
+    self, MULTICODEC_ED25519_PRIV_HEADER, MULTICODEC_ED25519_PUB_HEADER,
+    MULTICODEC_P256_PRIV_HEADER, MULTICODEC_P256_PUB_HEADER, MULTICODEC_P384_PRIV_HEADER,
+    MULTICODEC_P384_PUB_HEADER, MULTICODEC_P521_PRIV_HEADER, MULTICODEC_P521_PUB_HEADER,
+    MULTICODEC_RSA_PRIV_HEADER, MULTICODEC_RSA_PUB_HEADER, MULTICODEC_SECP256K1_PRIV_HEADER,
+    MULTICODEC_SECP256K1_PUB_HEADER, MULTICODEC_X25519_PRIV_HEADER, MULTICODEC_X25519_PUB_HEADER,
+};
+
+// JWK key type of Ed25519 and X25519 keys, see RFC 8037
+const KTY_OKP: &str = "OKP";
+// JWK key type of elliptic curve keys, see RFC 7518
+const KTY_EC: &str = "EC";
+// JWK key type of RSA keys, see RFC 7518
+const KTY_RSA: &str = "RSA";
+
+// SEC1 tag of an uncompressed point
+const SEC1_UNCOMPRESSED_TAG: u8 = 0x04;
+
+struct Curve {
+    kty: &'static str,
+    crv: &'static str,
+    public_key_header: [u8; 2],
+    private_key_header: [u8; 2],
+    // length of the `x`, `y` and `d` members
+    key_length: usize,
+}
+
+const CURVES: [Curve; 6] = [
+    Curve {
+        kty: KTY_OKP,
+        crv: "Ed25519",
+        public_key_header: MULTICODEC_ED25519_PUB_HEADER,
+        private_key_header: MULTICODEC_ED25519_PRIV_HEADER,
+        key_length: 32,
+    },
+    Curve {
+        kty: KTY_OKP,
+        crv: "X25519",
+        public_key_header: MULTICODEC_X25519_PUB_HEADER,
+        private_key_header: MULTICODEC_X25519_PRIV_HEADER,
+        key_length: 32,
+    },
+    Curve {
+        kty: KTY_EC,
+        crv: "secp256k1",
+        public_key_header: MULTICODEC_SECP256K1_PUB_HEADER,
+        private_key_header: MULTICODEC_SECP256K1_PRIV_HEADER,
+        key_length: 32,
+    },
+    Curve {
+        kty: KTY_EC,
+        crv: "P-256",
+        public_key_header: MULTICODEC_P256_PUB_HEADER,
+        private_key_header: MULTICODEC_P256_PRIV_HEADER,
+        key_length: 32,
+    },
+    Curve {
+        kty: KTY_EC,
+        crv: "P-384",
+        public_key_header: MULTICODEC_P384_PUB_HEADER,
+        private_key_header: MULTICODEC_P384_PRIV_HEADER,
+        key_length: 48,
+    },
+    Curve {
+        kty: KTY_EC,
+        crv: "P-521",
+        public_key_header: MULTICODEC_P521_PUB_HEADER,
+        private_key_header: MULTICODEC_P521_PRIV_HEADER,
+        key_length: 66,
+    },
+];
+
+/// Raw key bytes of a JWK together with their multicodec headers. Elliptic
+/// curve public keys are compressed SEC1 points and RSA public keys are DER
+/// encoded PKCS#1 public keys.
+pub struct DecodedJwk {
+    pub public_key_header: [u8; 2],
+    pub public_key: Vec<u8>,
+    pub private_key_header: [u8; 2],
+    pub private_key: Option<Vec<u8>>,
+}
+
+/// Whether keys of the type named by the multicodec public key `header` can be
+/// expressed as a JWK.
+pub fn is_supported_key_type(header: &[u8; 2]) -> bool {
+    *header == MULTICODEC_RSA_PUB_HEADER
+        || CURVES.iter().any(|val| val.public_key_header.eq(header))
+}
+
+/// Builds the public JWK of the raw key bytes of the type named by the
+/// multicodec public key `header`.
+pub fn public_key_jwk(header: &[u8; 2], public_key: &[u8]) -> Result<Value, Error> {
+    if *header == MULTICODEC_RSA_PUB_HEADER {
+        return rsa_public_key_jwk(public_key);
+    }
+
+    let curve = find_curve_by_header(header)?;
+
+    let mut jwk = Map::new();
+    jwk.insert(String::from("kty"), Value::from(curve.kty));
+    jwk.insert(String::from("crv"), Value::from(curve.crv));
+
+    if curve.kty == KTY_EC {
+        let (x, y) = decompress_point(header, public_key)?;
+        jwk.insert(String::from("x"), Value::from(URL_SAFE_NO_PAD.encode(x)));
+        jwk.insert(String::from("y"), Value::from(URL_SAFE_NO_PAD.encode(y)));
+    } else {
+        let x = check_key_length("x", public_key, curve.key_length)?;
+        jwk.insert(String::from("x"), Value::from(URL_SAFE_NO_PAD.encode(x)));
+    }
+
+    Ok(Value::Object(jwk))
+}
+
+/// Builds the private JWK, i.e. the public JWK with the `d` member. Ed25519
+/// private keys given as seed and public key only keep the 32 byte seed.
+pub fn private_key_jwk(
+    header: &[u8; 2],
+    public_key: &[u8],
+    private_key: &[u8],
+) -> Result<Value, Error> {
+    if *header == MULTICODEC_RSA_PUB_HEADER {
+        return Err(Error::new("RSA private keys cannot be exported as a JWK"));
+    }
+
+    let curve = find_curve_by_header(header)?;
+    let mut jwk = public_key_jwk(header, public_key)?;
+
+    let d = match *header {
+        MULTICODEC_ED25519_PUB_HEADER if private_key.len() == 2 * curve.key_length => {
+            &private_key[..curve.key_length]
+        }
+        _ => check_key_length("d", private_key, curve.key_length)?,
+    };
+
+    if let Value::Object(members) = &mut jwk {
+        members.insert(String::from("d"), Value::from(URL_SAFE_NO_PAD.encode(d)));
+    }
+
+    Ok(jwk)
+}
+
+/// Decodes a public or private JWK. The private key is only set if the JWK has
+/// a `d` member.
+pub fn decode_jwk(jwk: &Value) -> Result<DecodedJwk, Error> {
+    let kty = get_member(jwk, "kty")?;
+    if kty == KTY_RSA {
+        return decode_rsa_jwk(jwk);
+    }
+
+    let crv = get_member(jwk, "crv")?;
+
+    let curve = match CURVES.iter().find(|val| val.crv == crv) {
+        Some(val) => val,
+        None => {
+            return Err(Error::new(
+                format!("Unsupported JWK curve '{}'", crv).as_str(),
+            ))
+        }
+    };
+    if curve.kty != kty {
+        return Err(Error::new(
+            format!("JWK key type of curve '{}' must be '{}'", crv, curve.kty).as_str(),
+        ));
+    }
+
+    let x = decode_member(jwk, "x")?;
+    check_key_length("x", &x, curve.key_length)?;
+
+    let public_key = if curve.kty == KTY_EC {
+        let y = decode_member(jwk, "y")?;
+        check_key_length("y", &y, curve.key_length)?;
+
+        compress_point(&curve.public_key_header, &x, &y)?
+    } else {
+        x
+    };
+
+    let private_key = match jwk.get("d") {
+        Some(_) => {
+            let private_key = decode_member(jwk, "d")?;
+            check_key_length("d", &private_key, curve.key_length)?;
+
+            Some(private_key)
+        }
+        None => None,
+    };
+
+    Ok(DecodedJwk {
+        public_key_header: curve.public_key_header,
+        public_key,
+        private_key_header: curve.private_key_header,
+        private_key,
+    })
+}
+
+fn find_curve_by_header(header: &[u8; 2]) -> Result<&'static Curve, Error> {
+    match CURVES.iter().find(|val| val.public_key_header.eq(header)) {
+        Some(val) => Ok(val),
+        None => Err(Error::new(
+            format!(
+                "Unsupported multicodec header 0x{:02x}{:02x} for a JWK",
+                header[0], header[1]
+            )
+            .as_str(),
+        )),
+    }
+}
+
+fn rsa_public_key_jwk(public_key: &[u8]) -> Result<Value, Error> {
+    let public_key = match RsaPublicKey::from_pkcs1_der(public_key) {
+        Ok(val) => val,
+        Err(_error) => {
+            return Err(Error::new(
+                "Public key is not a DER encoded PKCS#1 RSA public key",
+            ))
+        }
+    };
+
+    let mut jwk = Map::new();
+    jwk.insert(String::from("kty"), Value::from(KTY_RSA));
+    jwk.insert(
+        String::from("n"),
+        Value::from(URL_SAFE_NO_PAD.encode(public_key.n().to_bytes_be())),
+    );
+    jwk.insert(
+        String::from("e"),
+        Value::from(URL_SAFE_NO_PAD.encode(public_key.e().to_bytes_be())),
+    );
+
+    Ok(Value::Object(jwk))
+}
+
+fn decode_rsa_jwk(jwk: &Value) -> Result<DecodedJwk, Error> {
+    if jwk.get("d").is_some() {
+        return Err(Error::new("RSA private keys cannot be imported from a JWK"));
+    }
+
+    let n = BigUint::from_bytes_be(&decode_member(jwk, "n")?);
+    let e = BigUint::from_bytes_be(&decode_member(jwk, "e")?);
+
+    let public_key = match RsaPublicKey::new(n, e) {
+        Ok(val) => val,
+        Err(error) => {
+            return Err(Error::new(
+                format!("JWK is not a valid RSA public key: {}", error).as_str(),
+            ))
+        }
+    };
+
+    let public_key = match public_key.to_pkcs1_der() {
+        Ok(val) => val.as_bytes().to_vec(),
+        Err(error) => return Err(Error::new(error.to_string().as_str())),
+    };
+
+    Ok(DecodedJwk {
+        public_key_header: MULTICODEC_RSA_PUB_HEADER,
+        public_key,
+        private_key_header: MULTICODEC_RSA_PRIV_HEADER,
+        private_key: None,
+    })
+}
+
+// returns the affine coordinates of a compressed SEC1 point
+fn decompress_point(header: &[u8; 2], public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>), Error> {
+    let point = match multicodec::encode_sec1_point(header, public_key, false) {
+        Some(val) => val,
+        None => return Err(Error::new("Public key is not a valid curve point")),
+    };
+
+    if point.len() < 3 || point[0] != SEC1_UNCOMPRESSED_TAG {
+        return Err(Error::new("Public key is the identity point"));
+    }
+
+    let coordinate_length = (point.len() - 1) / 2;
+    Ok((
+        point[1..1 + coordinate_length].to_vec(),
+        point[1 + coordinate_length..].to_vec(),
+    ))
+}
+
+// returns the compressed SEC1 point of the affine coordinates
+fn compress_point(header: &[u8; 2], x: &[u8], y: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut uncompressed = Vec::from([SEC1_UNCOMPRESSED_TAG]);
+    uncompressed.extend_from_slice(x);
+    uncompressed.extend_from_slice(y);
+
+    match multicodec::encode_sec1_point(header, &uncompressed, true) {
+        Some(val) => Ok(val),
+        None => Err(Error::new("JWK 'x' and 'y' are not a curve point")),
+    }
+}
+
+fn check_key_length<'a>(member: &str, key: &'a [u8], length: usize) -> Result<&'a [u8], Error> {
+    if key.len() != length {
+        return Err(Error::new(
+            format!(
+                "JWK '{}' must be {} bytes, found {}",
+                member,
+                length,
+                key.len()
+            )
+            .as_str(),
+        ));
+    }
+
+    Ok(key)
+}
+
+fn get_member<'a>(jwk: &'a Value, member: &str) -> Result<&'a str, Error> {
+    match jwk.get(member).and_then(Value::as_str) {
+        Some(val) => Ok(val),
+        None => Err(Error::new(
+            format!("JWK is missing the '{}' member", member).as_str(),
+        )),
+    }
+}
+
+fn decode_member(jwk: &Value, member: &str) -> Result<Vec<u8>, Error> {
+    match URL_SAFE_NO_PAD.decode(get_member(jwk, member)?) {
+        Ok(val) => Ok(val),
+        Err(error) => Err(Error::new(
+            format!("JWK '{}' is not base64url encoded: {}", member, error).as_str(),
+        )),
+    }
+}
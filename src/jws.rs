@@ -0,0 +1,250 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use fi_common::keys::KeyPair;
+use serde_json::Value;
+
+use crate::{
+    ed25519_verification_key2018, ed25519_verification_key2020,
+    failure::{error_codes, Failure},
+    json_web_key2020,
+    multicodec::MULTICODEC_ED25519_PUB_HEADER,
+    multikey, resolve_did_with_format, signature,
+};
+
+// the JWS algorithms that can be verified, with the multicodec header of the
+// public keys they are verified with
+const JWS_ALGORITHMS: [(&str, [u8; 2]); 1] = [("EdDSA", MULTICODEC_ED25519_PUB_HEADER)];
+
+// the verification method types whose keys can verify a JWS
+const JWS_SUITES: [&str; 4] = [
+    ed25519_verification_key2018::SUITE_ID,
+    ed25519_verification_key2020::SUITE_ID,
+    multikey::SUITE_ID,
+    json_web_key2020::SUITE_ID,
+];
+
+// the header parameter of an unencoded payload, see RFC 7797
+const HEADER_B64: &str = "b64";
+
+error_codes! {
+    /// Error codes of verifying a JWS.
+    pub enum JwsError {
+        /// The JWS is not a compact serialization with a detached payload.
+        InvalidJws => "invalidJws",
+        /// The protected header is malformed or has unsupported critical
+        /// parameters.
+        InvalidHeader => "invalidHeader",
+        /// The `alg` of the header is not supported.
+        UnsupportedAlgorithm => "unsupportedAlgorithm",
+        /// The `kid` of the header is not a `did:key` URL of a verification method.
+        InvalidKeyId => "invalidKeyId",
+        /// The `alg` of the header cannot be used with the key of the `kid`.
+        IncompatibleAlgorithm => "incompatibleAlgorithm",
+        /// The signature does not verify.
+        InvalidSignature => "invalidSignature",
+    }
+}
+
+pub type JwsFailure = Failure<JwsError>;
+
+/// Verifies a detached compact JWS (`<header>..<signature>`) over `payload`
+/// and returns the verification method of the `kid` it was verified with.
+///
+/// The `kid` must be a `did:key` URL, which is dereferenced with
+/// [`resolve_did_with_format`]. With `"b64": false` the payload is signed unencoded as
+/// described in RFC 7797, in which case `crit` must name `b64`.
+pub fn verify_detached_jws(jws: &str, payload: &[u8]) -> Result<KeyPair, JwsFailure> {
+    let parts: Vec<&str> = jws.split('.').collect();
+    if parts.len() != 3 {
+        return Err(JwsFailure::new(
+            JwsError::InvalidJws,
+            "JWS must have three '.' separated parts",
+        ));
+    }
+    if !parts[1].is_empty() {
+        return Err(JwsFailure::new(
+            JwsError::InvalidJws,
+            "JWS payload must be detached",
+        ));
+    }
+
+    let header = decode_header(parts[0])?;
+    let alg = match header.get("alg").and_then(Value::as_str) {
+        Some(val) => val,
+        None => {
+            return Err(JwsFailure::new(
+                JwsError::InvalidHeader,
+                "JWS header is missing 'alg'",
+            ))
+        }
+    };
+    let kid = match header.get("kid").and_then(Value::as_str) {
+        Some(val) => val,
+        None => {
+            return Err(JwsFailure::new(
+                JwsError::InvalidKeyId,
+                "JWS header is missing 'kid'",
+            ))
+        }
+    };
+
+    let mut signing_input = format!("{}.", parts[0]).into_bytes();
+    if is_payload_encoded(&header)? {
+        signing_input.extend_from_slice(URL_SAFE_NO_PAD.encode(payload).as_bytes());
+    } else {
+        signing_input.extend_from_slice(payload);
+    }
+
+    let signature = match URL_SAFE_NO_PAD.decode(parts[2]) {
+        Ok(val) => val,
+        Err(error) => {
+            return Err(JwsFailure::new(
+                JwsError::InvalidJws,
+                format!("JWS signature is not base64url encoded: {}", error).as_str(),
+            ))
+        }
+    };
+
+    let verification_method = resolve_key_id(kid)?;
+    verify_signature(alg, &verification_method, &signing_input, &signature)?;
+
+    Ok(verification_method)
+}
+
+/// Dereferences a `did:key` URL to the verification method it names.
+pub(crate) fn resolve_key_id(kid: &str) -> Result<KeyPair, JwsFailure> {
+    if !kid.starts_with("did:key:") || !kid.contains('#') {
+        return Err(JwsFailure::new(
+            JwsError::InvalidKeyId,
+            format!("'{}' is not a did:key URL", kid).as_str(),
+        ));
+    }
+
+    match resolve_did_with_format(kid, None) {
+        Ok((_, Some(val))) => Ok(val),
+        Ok(_) => Err(JwsFailure::new(
+            JwsError::InvalidKeyId,
+            format!("'{}' does not dereference to a verification method", kid).as_str(),
+        )),
+        Err(error) => Err(JwsFailure::new(
+            JwsError::InvalidKeyId,
+            error.to_string().as_str(),
+        )),
+    }
+}
+
+/// Verifies a JWS `signature` of `signing_input` with the algorithm `alg` and
+/// the key of `verification_method`.
+pub(crate) fn verify_signature(
+    alg: &str,
+    verification_method: &KeyPair,
+    signing_input: &[u8],
+    signature: &[u8],
+) -> Result<(), JwsFailure> {
+    let key_header = match JWS_ALGORITHMS.iter().find(|(name, _)| *name == alg) {
+        Some((_, header)) => header,
+        None => {
+            return Err(JwsFailure::new(
+                JwsError::UnsupportedAlgorithm,
+                format!("Unsupported JWS algorithm '{}'", alg).as_str(),
+            ))
+        }
+    };
+
+    let incompatible = || {
+        JwsFailure::new(
+            JwsError::IncompatibleAlgorithm,
+            format!(
+                "JWS algorithm '{}' cannot be used with the {} key '{}'",
+                alg,
+                verification_method._type,
+                verification_method.id.as_deref().unwrap_or_default()
+            )
+            .as_str(),
+        )
+    };
+
+    if !JWS_SUITES.contains(&verification_method._type.as_str()) {
+        return Err(incompatible());
+    }
+
+    let (header, public_key) = match signature::verification_method_public_key(verification_method)
+    {
+        Ok(val) => val,
+        Err(error) => {
+            return Err(JwsFailure::new(
+                JwsError::InvalidKeyId,
+                error.to_string().as_str(),
+            ))
+        }
+    };
+    if header != *key_header {
+        return Err(incompatible());
+    }
+
+    match signature::verify(&public_key, signing_input, signature) {
+        Ok(_) => Ok(()),
+        Err(failure) => Err(JwsFailure::new(
+            JwsError::InvalidSignature,
+            failure.to_string().as_str(),
+        )),
+    }
+}
+
+fn decode_header(encoded: &str) -> Result<Value, JwsFailure> {
+    let decoded = match URL_SAFE_NO_PAD.decode(encoded) {
+        Ok(val) => val,
+        Err(error) => {
+            return Err(JwsFailure::new(
+                JwsError::InvalidHeader,
+                format!("JWS header is not base64url encoded: {}", error).as_str(),
+            ))
+        }
+    };
+
+    match serde_json::from_slice::<Value>(&decoded) {
+        Ok(val) if val.is_object() => Ok(val),
+        Ok(_) => Err(JwsFailure::new(
+            JwsError::InvalidHeader,
+            "JWS header must be a JSON object",
+        )),
+        Err(error) => Err(JwsFailure::new(
+            JwsError::InvalidHeader,
+            format!("JWS header is not JSON: {}", error).as_str(),
+        )),
+    }
+}
+
+// whether the payload is base64url encoded in the signing input, checking that
+// `b64` is the only critical header parameter
+fn is_payload_encoded(header: &Value) -> Result<bool, JwsFailure> {
+    let critical: Vec<&str> = match header.get("crit") {
+        Some(Value::Array(values)) => values.iter().filter_map(Value::as_str).collect(),
+        Some(_) => {
+            return Err(JwsFailure::new(
+                JwsError::InvalidHeader,
+                "JWS header 'crit' must be an array of strings",
+            ))
+        }
+        None => Vec::new(),
+    };
+    if let Some(name) = critical.iter().find(|val| **val != HEADER_B64) {
+        return Err(JwsFailure::new(
+            JwsError::InvalidHeader,
+            format!("Unsupported critical JWS header parameter '{}'", name).as_str(),
+        ));
+    }
+
+    match header.get(HEADER_B64) {
+        None => Ok(true),
+        Some(Value::Bool(true)) => Ok(true),
+        Some(Value::Bool(false)) if critical.contains(&HEADER_B64) => Ok(false),
+        Some(Value::Bool(false)) => Err(JwsFailure::new(
+            JwsError::InvalidHeader,
+            "JWS header 'crit' must contain 'b64' for an unencoded payload",
+        )),
+        Some(_) => Err(JwsFailure::new(
+            JwsError::InvalidHeader,
+            "JWS header 'b64' must be a boolean",
+        )),
+    }
+}
@@ -0,0 +1,414 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use fi_common::keys::KeyPair;
+use serde::{de::DeserializeOwned, Deserialize, Serialize, Serializer};
+use serde_json::{json, Value};
+
+use crate::{
+    failure::{error_codes, Failure},
+    fingerprint::KeyFingerprint,
+    jws::{self, JwsError, JwsFailure},
+    signature::SignatureKey,
+};
+
+// the JWS algorithm of the JWTs issued and verified
+const JWT_ALGORITHM: &str = "EdDSA";
+const JWT_TYPE: &str = "JWT";
+
+// the default clock skew allowed when validating time claims, in seconds
+const DEFAULT_LEEWAY: u64 = 60;
+
+// the magnitude below which every integer is exactly an f64, 2^53
+const MAX_EXACT_INTEGER: f64 = 9007199254740992.0;
+
+error_codes! {
+    /// Error codes of issuing and verifying a JWT.
+    pub enum JwtError {
+        /// The token is not a compact JWS with a JSON header and payload.
+        InvalidToken => "invalidToken",
+        /// The `kid` of the header is not a `did:key` URL of a verification method.
+        InvalidKeyId => "invalidKeyId",
+        /// The `alg` of the header is not `EdDSA` or not usable with the key.
+        UnsupportedAlgorithm => "unsupportedAlgorithm",
+        /// The signature does not verify.
+        InvalidSignature => "invalidSignature",
+        /// The token cannot be signed, e.g. the key pair has no private key.
+        SigningFailed => "signingFailed",
+        /// The claims are missing `iss` or do not have the expected types.
+        InvalidClaims => "invalidClaims",
+        /// The DID of the `kid` is not the `iss` of the claims.
+        IssuerMismatch => "issuerMismatch",
+        /// The `exp` of the claims has passed.
+        Expired => "expired",
+        /// The `nbf` of the claims has not been reached.
+        NotYetValid => "notYetValid",
+        /// The `iat` of the claims is in the future.
+        IssuedInFuture => "issuedInFuture",
+    }
+}
+
+pub type JwtFailure = Failure<JwtError>;
+
+impl From<JwsFailure> for JwtFailure {
+    fn from(failure: JwsFailure) -> Self {
+        let error = match failure.error {
+            JwsError::InvalidJws | JwsError::InvalidHeader => JwtError::InvalidToken,
+            JwsError::InvalidKeyId => JwtError::InvalidKeyId,
+            JwsError::UnsupportedAlgorithm | JwsError::IncompatibleAlgorithm => {
+                JwtError::UnsupportedAlgorithm
+            }
+            JwsError::InvalidSignature => JwtError::InvalidSignature,
+        };
+
+        JwtFailure {
+            error,
+            message: failure.message,
+        }
+    }
+}
+
+/// The registered claims of a JWT together with the typed `private` claims,
+/// which hold every other member of the payload, e.g. `aud`.
+///
+/// Times are NumericDate values, i.e. seconds since the Unix epoch, which may
+/// have a fraction.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct JwtClaims<T> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_numeric_date"
+    )]
+    pub exp: Option<f64>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_numeric_date"
+    )]
+    pub nbf: Option<f64>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_numeric_date"
+    )]
+    pub iat: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+    #[serde(flatten)]
+    pub private: T,
+}
+
+impl<T> JwtClaims<T> {
+    pub fn new(private: T) -> Self {
+        JwtClaims {
+            iss: None,
+            sub: None,
+            exp: None,
+            nbf: None,
+            iat: None,
+            jti: None,
+            private,
+        }
+    }
+}
+
+/// Options of validating the time claims of a JWT.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JwtValidationOptions {
+    /// The clock skew allowed for `exp`, `nbf` and `iat`, in seconds.
+    pub leeway: u64,
+    /// The time to validate at, in seconds since the Unix epoch. The system
+    /// time is used when it is `None`.
+    pub current_time: Option<u64>,
+}
+
+impl Default for JwtValidationOptions {
+    fn default() -> Self {
+        JwtValidationOptions {
+            leeway: DEFAULT_LEEWAY,
+            current_time: None,
+        }
+    }
+}
+
+/// A verified JWT: its claims and the verification method of its `kid`.
+#[derive(Debug, Clone)]
+pub struct VerifiedJwt<T> {
+    pub claims: JwtClaims<T>,
+    pub verification_method: KeyPair,
+}
+
+/// Issues an `EdDSA` JWT signed by `key_pair`, whose `kid` is the `did:key`
+/// URL of the key and whose `iss` is its `did:key`.
+///
+/// An `iss` already set in `claims` must be the `did:key` of the key pair.
+pub fn issue_jwt<T, K>(claims: &JwtClaims<T>, key_pair: &K) -> Result<String, JwtFailure>
+where
+    T: Serialize,
+    K: SignatureKey + KeyFingerprint,
+{
+    for (name, value) in [
+        ("exp", claims.exp),
+        ("nbf", claims.nbf),
+        ("iat", claims.iat),
+    ] {
+        // serde_json writes a non-finite number as `null`, which would drop
+        // the claim from the token
+        if value.is_some_and(|val| !val.is_finite()) {
+            return Err(JwtFailure::new(
+                JwtError::InvalidClaims,
+                format!("JWT claim '{}' must be a finite number", name).as_str(),
+            ));
+        }
+    }
+
+    let (did, fingerprint) = match (key_pair.did_key(), key_pair.fingerprint()) {
+        (Ok(did), Ok(fingerprint)) => (did, fingerprint),
+        (Err(error), _) | (_, Err(error)) => {
+            return Err(JwtFailure::new(
+                JwtError::SigningFailed,
+                error.to_string().as_str(),
+            ))
+        }
+    };
+
+    let mut payload = match serde_json::to_value(claims) {
+        Ok(Value::Object(val)) => val,
+        Ok(_) => {
+            return Err(JwtFailure::new(
+                JwtError::InvalidClaims,
+                "JWT claims must be a JSON object",
+            ))
+        }
+        Err(error) => {
+            return Err(JwtFailure::new(
+                JwtError::InvalidClaims,
+                error.to_string().as_str(),
+            ))
+        }
+    };
+    match payload.get("iss") {
+        None => {
+            payload.insert(String::from("iss"), Value::from(did.as_str()));
+        }
+        Some(iss) if iss.as_str() == Some(did.as_str()) => {}
+        Some(iss) => {
+            return Err(JwtFailure::new(
+                JwtError::IssuerMismatch,
+                format!("JWT issuer {} is not the key's DID '{}'", iss, did).as_str(),
+            ))
+        }
+    }
+
+    let header = json!({
+        "alg": JWT_ALGORITHM,
+        "typ": JWT_TYPE,
+        "kid": format!("{}#{}", did, fingerprint),
+    });
+    let signing_input = format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(header.to_string()),
+        URL_SAFE_NO_PAD.encode(Value::Object(payload).to_string())
+    );
+
+    let signature = match key_pair.sign(signing_input.as_bytes()) {
+        Ok(val) => val,
+        Err(failure) => {
+            return Err(JwtFailure::new(
+                JwtError::SigningFailed,
+                failure.to_string().as_str(),
+            ))
+        }
+    };
+
+    Ok(format!(
+        "{}.{}",
+        signing_input,
+        URL_SAFE_NO_PAD.encode(signature)
+    ))
+}
+
+/// Verifies an `EdDSA` JWT whose `kid` is a `did:key` URL, resolved with
+/// [`crate::resolve_did_with_format`], and validates its claims.
+///
+/// The DID of the `kid` must be the `iss` of the claims, and `exp`, `nbf` and
+/// `iat`, when present, must hold at the current time within the leeway of
+/// `options`.
+pub fn verify_jwt<T: DeserializeOwned>(
+    token: &str,
+    options: &JwtValidationOptions,
+) -> Result<VerifiedJwt<T>, JwtFailure> {
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return Err(JwtFailure::new(
+            JwtError::InvalidToken,
+            "JWT must have three '.' separated parts",
+        ));
+    }
+
+    let header: Value = decode_part(parts[0], "header", JwtError::InvalidToken)?;
+    if header.get("crit").is_some() {
+        return Err(JwtFailure::new(
+            JwtError::InvalidToken,
+            "JWT header must not have critical parameters",
+        ));
+    }
+    let alg = match header.get("alg").and_then(Value::as_str) {
+        Some(val) => val,
+        None => {
+            return Err(JwtFailure::new(
+                JwtError::InvalidToken,
+                "JWT header is missing 'alg'",
+            ))
+        }
+    };
+    if alg != JWT_ALGORITHM {
+        return Err(JwtFailure::new(
+            JwtError::UnsupportedAlgorithm,
+            format!("JWT algorithm must be '{}', found '{}'", JWT_ALGORITHM, alg).as_str(),
+        ));
+    }
+    let kid = match header.get("kid").and_then(Value::as_str) {
+        Some(val) => val,
+        None => {
+            return Err(JwtFailure::new(
+                JwtError::InvalidKeyId,
+                "JWT header is missing 'kid'",
+            ))
+        }
+    };
+
+    let signature = match URL_SAFE_NO_PAD.decode(parts[2]) {
+        Ok(val) => val,
+        Err(error) => {
+            return Err(JwtFailure::new(
+                JwtError::InvalidToken,
+                format!("JWT signature is not base64url encoded: {}", error).as_str(),
+            ))
+        }
+    };
+
+    let verification_method = jws::resolve_key_id(kid)?;
+    let signing_input = format!("{}.{}", parts[0], parts[1]);
+    jws::verify_signature(
+        alg,
+        &verification_method,
+        signing_input.as_bytes(),
+        &signature,
+    )?;
+
+    let claims: JwtClaims<T> = decode_part(parts[1], "payload", JwtError::InvalidClaims)?;
+    let iss = match &claims.iss {
+        Some(val) => val,
+        None => {
+            return Err(JwtFailure::new(
+                JwtError::InvalidClaims,
+                "JWT claims are missing 'iss'",
+            ))
+        }
+    };
+    if kid.split_once('#').map(|(did, _)| did) != Some(iss.as_str()) {
+        return Err(JwtFailure::new(
+            JwtError::IssuerMismatch,
+            format!("JWT key '{}' does not belong to the issuer '{}'", kid, iss).as_str(),
+        ));
+    }
+
+    validate_times(&claims, options)?;
+
+    Ok(VerifiedJwt {
+        claims,
+        verification_method,
+    })
+}
+
+// decodes the base64url JSON `name` part of a token, reporting a malformed
+// part as `error`
+fn decode_part<V: DeserializeOwned>(
+    encoded: &str,
+    name: &str,
+    error: JwtError,
+) -> Result<V, JwtFailure> {
+    let decoded = match URL_SAFE_NO_PAD.decode(encoded) {
+        Ok(val) => val,
+        Err(source) => {
+            return Err(JwtFailure::new(
+                JwtError::InvalidToken,
+                format!("JWT {} is not base64url encoded: {}", name, source).as_str(),
+            ))
+        }
+    };
+
+    match serde_json::from_slice(&decoded) {
+        Ok(val) => Ok(val),
+        Err(source) => Err(JwtFailure::new(
+            error,
+            format!("Invalid JWT {}: {}", name, source).as_str(),
+        )),
+    }
+}
+
+// a NumericDate without a fraction is written as an integer, e.g. `1704067200`
+// rather than `1704067200.0`
+fn serialize_numeric_date<S: Serializer>(
+    value: &Option<f64>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(val) if val.fract() == 0.0 && val.abs() < MAX_EXACT_INTEGER => {
+            serializer.serialize_i64(*val as i64)
+        }
+        Some(val) => serializer.serialize_f64(*val),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn validate_times<T>(
+    claims: &JwtClaims<T>,
+    options: &JwtValidationOptions,
+) -> Result<(), JwtFailure> {
+    let now = match options.current_time {
+        Some(val) => val as f64,
+        None => match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(val) => val.as_secs_f64(),
+            Err(error) => {
+                return Err(JwtFailure::new(
+                    JwtError::InvalidClaims,
+                    error.to_string().as_str(),
+                ))
+            }
+        },
+    };
+
+    let leeway = options.leeway as f64;
+
+    // the token must not be accepted on or after `exp`
+    if let Some(exp) = claims.exp {
+        if now >= exp + leeway {
+            return Err(JwtFailure::new(
+                JwtError::Expired,
+                format!("JWT expired at {}", exp).as_str(),
+            ));
+        }
+    }
+    if let Some(nbf) = claims.nbf {
+        if now + leeway < nbf {
+            return Err(JwtFailure::new(
+                JwtError::NotYetValid,
+                format!("JWT is not valid before {}", nbf).as_str(),
+            ));
+        }
+    }
+    if let Some(iat) = claims.iat {
+        if now + leeway < iat {
+            return Err(JwtFailure::new(
+                JwtError::IssuedInFuture,
+                format!("JWT is issued in the future at {}", iat).as_str(),
+            ));
+        }
+    }
+
+    Ok(())
+}
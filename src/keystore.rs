@@ -0,0 +1,462 @@
+use std::{fs, io::Write, path::Path};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chacha20poly1305::{
+    aead::{Aead, Payload},
+    KeyInit, XChaCha20Poly1305, XNonce,
+};
+use fi_common::{error::Error, keys::KeyPair};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::failure::{error_codes, Failure};
+
+/// Version of the keystore format written by this crate.
+pub const KEYSTORE_VERSION: u32 = 1;
+
+const KDF_ALGORITHM: &str = "argon2id";
+const CIPHER_ALGORITHM: &str = "xchacha20poly1305";
+
+const SALT_LENGTH: usize = 16;
+const KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 24;
+
+// the Argon2id costs read from a keystore are capped at this multiple of the
+// defaults, so that a crafted header cannot exhaust memory or time
+const MAX_COST_FACTOR: u32 = 16;
+
+#[cfg(unix)]
+const KEYSTORE_FILE_MODE: u32 = 0o600;
+
+error_codes! {
+    /// Error codes of keystore operations.
+    pub enum KeystoreError {
+        /// The keystore is not valid keystore JSON.
+        InvalidFormat => "invalidFormat",
+        /// The keystore was written with an unknown format version.
+        UnsupportedVersion => "unsupportedVersion",
+        /// The password is wrong, or the header or ciphertext was tampered with.
+        DecryptionFailed => "decryptionFailed",
+        /// The decrypted key pair is not a key of the requested suite.
+        InvalidKeyPair => "invalidKeyPair",
+        /// The keystore file could not be read or written.
+        Io => "io",
+    }
+}
+
+pub type KeystoreFailure = Failure<KeystoreError>;
+
+/// Argon2id cost parameters of the password-derived key. The defaults are the
+/// `argon2` crate defaults, and each cost is at most 16 times its default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeystoreOptions {
+    /// Memory size in KiB.
+    pub memory_cost: u32,
+    /// Number of iterations.
+    pub time_cost: u32,
+    /// Degree of parallelism.
+    pub parallelism: u32,
+}
+
+impl Default for KeystoreOptions {
+    fn default() -> Self {
+        KeystoreOptions {
+            memory_cost: Params::DEFAULT_M_COST,
+            time_cost: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KdfParams {
+    pub algorithm: String,
+    pub memory_cost: u32,
+    pub time_cost: u32,
+    pub parallelism: u32,
+    pub salt: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CipherParams {
+    pub algorithm: String,
+    pub nonce: String,
+}
+
+/// The plain text part of a keystore. It is the additional authenticated data
+/// of the ciphertext, so it cannot be changed without failing decryption.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeystoreHeader {
+    pub version: u32,
+    pub did: String,
+    pub suite: String,
+    pub kdf: KdfParams,
+    pub cipher: CipherParams,
+}
+
+/// A password encrypted exported `KeyPair`, including its private key.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Keystore {
+    #[serde(flatten)]
+    header: KeystoreHeader,
+    ciphertext: String,
+}
+
+impl Keystore {
+    /// Encrypts `key_pair`, e.g. `export(true, true, true)` of a key, whose
+    /// controller is stored as the DID of the keystore.
+    pub fn encrypt(
+        key_pair: &KeyPair,
+        password: &[u8],
+        options: &KeystoreOptions,
+    ) -> Result<Self, KeystoreFailure> {
+        let did = match &key_pair.controller {
+            Some(val) => val.clone(),
+            None => {
+                return Err(KeystoreFailure::new(
+                    KeystoreError::InvalidKeyPair,
+                    "Key pair must have a controller",
+                ))
+            }
+        };
+
+        let mut salt = [0u8; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+
+        let header = KeystoreHeader {
+            version: KEYSTORE_VERSION,
+            did,
+            suite: key_pair._type.clone(),
+            kdf: KdfParams {
+                algorithm: String::from(KDF_ALGORITHM),
+                memory_cost: options.memory_cost,
+                time_cost: options.time_cost,
+                parallelism: options.parallelism,
+                salt: URL_SAFE_NO_PAD.encode(salt),
+            },
+            cipher: CipherParams {
+                algorithm: String::from(CIPHER_ALGORITHM),
+                nonce: URL_SAFE_NO_PAD.encode(nonce),
+            },
+        };
+
+        let plaintext = match serde_json::to_vec(key_pair) {
+            Ok(val) => Zeroizing::new(val),
+            Err(error) => {
+                return Err(KeystoreFailure::new(
+                    KeystoreError::InvalidKeyPair,
+                    error.to_string().as_str(),
+                ))
+            }
+        };
+
+        let cipher = header.cipher(password)?;
+        let ciphertext = match cipher.encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &plaintext,
+                aad: &header.aad()?,
+            },
+        ) {
+            Ok(val) => val,
+            Err(_error) => {
+                return Err(KeystoreFailure::new(
+                    KeystoreError::InvalidKeyPair,
+                    "Key pair could not be encrypted",
+                ))
+            }
+        };
+
+        Ok(Keystore {
+            header,
+            ciphertext: URL_SAFE_NO_PAD.encode(ciphertext),
+        })
+    }
+
+    /// Decrypts the exported `KeyPair`.
+    pub fn decrypt(&self, password: &[u8]) -> Result<KeyPair, KeystoreFailure> {
+        let cipher = self.header.cipher(password)?;
+        let nonce = decode_member("nonce", &self.header.cipher.nonce, NONCE_LENGTH)?;
+        let ciphertext = match URL_SAFE_NO_PAD.decode(&self.ciphertext) {
+            Ok(val) => val,
+            Err(error) => {
+                return Err(KeystoreFailure::new(
+                    KeystoreError::InvalidFormat,
+                    format!("Keystore 'ciphertext' is not base64url encoded: {}", error).as_str(),
+                ))
+            }
+        };
+
+        let plaintext = match cipher.decrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad: &self.header.aad()?,
+            },
+        ) {
+            Ok(val) => Zeroizing::new(val),
+            Err(_error) => {
+                return Err(KeystoreFailure::new(
+                    KeystoreError::DecryptionFailed,
+                    "Wrong password, or the keystore has been tampered with",
+                ))
+            }
+        };
+
+        let key_pair: KeyPair = match serde_json::from_slice(&plaintext) {
+            Ok(val) => val,
+            Err(error) => {
+                return Err(KeystoreFailure::new(
+                    KeystoreError::InvalidKeyPair,
+                    error.to_string().as_str(),
+                ))
+            }
+        };
+
+        if key_pair._type != self.header.suite {
+            return Err(KeystoreFailure::new(
+                KeystoreError::InvalidKeyPair,
+                "Key pair type does not match the keystore suite",
+            ));
+        }
+
+        Ok(key_pair)
+    }
+
+    /// Decrypts the key pair as a key of the suite `T`, e.g.
+    /// `Ed25519VerificationKey2020`.
+    pub fn decrypt_key<T>(&self, password: &[u8]) -> Result<T, KeystoreFailure>
+    where
+        T: for<'a> TryFrom<&'a KeyPair, Error = Error>,
+    {
+        let key_pair = self.decrypt(password)?;
+
+        match T::try_from(&key_pair) {
+            Ok(val) => Ok(val),
+            Err(error) => Err(KeystoreFailure::new(
+                KeystoreError::InvalidKeyPair,
+                error.to_string().as_str(),
+            )),
+        }
+    }
+
+    /// Encrypts the key pair again with a new password and options, e.g. to
+    /// rotate the password or to upgrade the format version.
+    pub fn reencrypt(
+        &self,
+        password: &[u8],
+        new_password: &[u8],
+        options: &KeystoreOptions,
+    ) -> Result<Self, KeystoreFailure> {
+        Keystore::encrypt(&self.decrypt(password)?, new_password, options)
+    }
+
+    pub fn get_header(&self) -> &KeystoreHeader {
+        &self.header
+    }
+
+    pub fn to_json(&self) -> Result<String, KeystoreFailure> {
+        match serde_json::to_string_pretty(self) {
+            Ok(val) => Ok(val),
+            Err(error) => Err(KeystoreFailure::new(
+                KeystoreError::InvalidFormat,
+                error.to_string().as_str(),
+            )),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, KeystoreFailure> {
+        // the version is checked first, so that newer formats are reported as such
+        let value: serde_json::Value = match serde_json::from_str(json) {
+            Ok(val) => val,
+            Err(error) => {
+                return Err(KeystoreFailure::new(
+                    KeystoreError::InvalidFormat,
+                    error.to_string().as_str(),
+                ))
+            }
+        };
+        match value.get("version").and_then(serde_json::Value::as_u64) {
+            Some(val) if val == KEYSTORE_VERSION as u64 => {}
+            Some(val) => {
+                return Err(KeystoreFailure::new(
+                    KeystoreError::UnsupportedVersion,
+                    format!("Unsupported keystore version {}", val).as_str(),
+                ))
+            }
+            None => {
+                return Err(KeystoreFailure::new(
+                    KeystoreError::InvalidFormat,
+                    "Keystore is missing the 'version' member",
+                ))
+            }
+        }
+
+        let keystore: Keystore = match serde_json::from_value(value) {
+            Ok(val) => val,
+            Err(error) => {
+                return Err(KeystoreFailure::new(
+                    KeystoreError::InvalidFormat,
+                    error.to_string().as_str(),
+                ))
+            }
+        };
+
+        if keystore.header.kdf.algorithm != KDF_ALGORITHM
+            || keystore.header.cipher.algorithm != CIPHER_ALGORITHM
+        {
+            return Err(KeystoreFailure::new(
+                KeystoreError::InvalidFormat,
+                format!(
+                    "Keystore must use '{}' and '{}'",
+                    KDF_ALGORITHM, CIPHER_ALGORITHM
+                )
+                .as_str(),
+            ));
+        }
+
+        Ok(keystore)
+    }
+
+    /// Writes the keystore to `path`, which is only readable and writable by
+    /// its owner on Unix.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), KeystoreFailure> {
+        let json = self.to_json()?;
+
+        match write_private_file(path.as_ref(), json.as_bytes()) {
+            Ok(val) => Ok(val),
+            Err(error) => Err(KeystoreFailure::new(
+                KeystoreError::Io,
+                error.to_string().as_str(),
+            )),
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, KeystoreFailure> {
+        match fs::read_to_string(path) {
+            Ok(val) => Keystore::from_json(&val),
+            Err(error) => Err(KeystoreFailure::new(
+                KeystoreError::Io,
+                error.to_string().as_str(),
+            )),
+        }
+    }
+}
+
+impl KeystoreHeader {
+    fn aad(&self) -> Result<Vec<u8>, KeystoreFailure> {
+        match serde_json::to_vec(self) {
+            Ok(val) => Ok(val),
+            Err(error) => Err(KeystoreFailure::new(
+                KeystoreError::InvalidFormat,
+                error.to_string().as_str(),
+            )),
+        }
+    }
+
+    // the cipher keyed with the password-derived key
+    fn cipher(&self, password: &[u8]) -> Result<XChaCha20Poly1305, KeystoreFailure> {
+        self.kdf.check_costs()?;
+        let params = match Params::new(
+            self.kdf.memory_cost,
+            self.kdf.time_cost,
+            self.kdf.parallelism,
+            Some(KEY_LENGTH),
+        ) {
+            Ok(val) => val,
+            Err(error) => {
+                return Err(KeystoreFailure::new(
+                    KeystoreError::InvalidFormat,
+                    format!("Invalid Argon2id parameters: {}", error).as_str(),
+                ))
+            }
+        };
+        let salt = decode_member("salt", &self.kdf.salt, SALT_LENGTH)?;
+
+        let mut key = Zeroizing::new([0u8; KEY_LENGTH]);
+        if let Err(error) = Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(password, &salt, key.as_mut())
+        {
+            return Err(KeystoreFailure::new(
+                KeystoreError::InvalidFormat,
+                format!("Key derivation failed: {}", error).as_str(),
+            ));
+        }
+
+        match XChaCha20Poly1305::new_from_slice(key.as_ref()) {
+            Ok(val) => Ok(val),
+            Err(error) => Err(KeystoreFailure::new(
+                KeystoreError::InvalidFormat,
+                error.to_string().as_str(),
+            )),
+        }
+    }
+}
+
+impl KdfParams {
+    fn check_costs(&self) -> Result<(), KeystoreFailure> {
+        let costs = [
+            ("memory", self.memory_cost, Params::DEFAULT_M_COST),
+            ("time", self.time_cost, Params::DEFAULT_T_COST),
+            ("parallelism", self.parallelism, Params::DEFAULT_P_COST),
+        ];
+
+        for (name, cost, default) in costs {
+            let max = default.saturating_mul(MAX_COST_FACTOR);
+            if cost > max {
+                return Err(KeystoreFailure::new(
+                    KeystoreError::InvalidFormat,
+                    format!(
+                        "Argon2id {} cost {} exceeds the maximum of {}",
+                        name, cost, max
+                    )
+                    .as_str(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn write_private_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(KEYSTORE_FILE_MODE)
+        .open(path)?;
+    // the mode only applies to new files
+    file.set_permissions(fs::Permissions::from_mode(KEYSTORE_FILE_MODE))?;
+
+    file.write_all(contents)
+}
+
+#[cfg(not(unix))]
+fn write_private_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    fs::File::create(path)?.write_all(contents)
+}
+
+fn decode_member(member: &str, value: &str, length: usize) -> Result<Vec<u8>, KeystoreFailure> {
+    match URL_SAFE_NO_PAD.decode(value) {
+        Ok(val) if val.len() == length => Ok(val),
+        _ => Err(KeystoreFailure::new(
+            KeystoreError::InvalidFormat,
+            format!(
+                "Keystore '{}' must be {} base64url encoded bytes",
+                member, length
+            )
+            .as_str(),
+        )),
+    }
+}
@@ -1,69 +1,106 @@
-use did::DidDoc;
-use fi_common::did::{DidDocument, KeyPairToDidDocument};
+use dereferencing::{ContentStream, DidDereferencingResult};
+use fi_common::did::DidDocument;
 use fi_common::error::Error;
-use fi_common::keys::{KeyPair, VerificationKey};
+use fi_common::keys::KeyPair;
+use registry::global_resolver;
+use resolution::{DidResolutionResult, ResolutionFailure, ResolutionOptions};
 
+pub mod bls12381_g1_key2020;
+pub mod bls12381_g2_key2020;
+pub mod bls12381_key2020;
+pub mod data_integrity;
+pub mod dereferencing;
+pub mod derivation;
 pub mod did;
+pub mod did_key;
+pub mod did_url;
+pub mod ecdsa_secp256k1_verification_key2019;
 pub mod ed25519_verification_key2018;
 pub mod ed25519_verification_key2020;
+pub mod failure;
+pub mod fingerprint;
+pub mod json_ld;
+pub mod json_web_key2020;
+mod jwk;
+pub mod jws;
+pub mod jwt;
+pub mod keystore;
+mod multicodec;
+pub mod multikey;
+pub mod registry;
+pub mod resolution;
+pub mod signature;
 mod util;
 pub mod x25519_key_agreement_key2019;
 pub mod x25519_key_agreement_key2020;
 
+/// Resolves a `did:key` DID, or dereferences a DID URL pointing at one of its
+/// keys, representing the key with the verification key suite `suit_id`.
+///
+/// The key type is read from the multicodec header of the fingerprint, so
+/// `suit_id` only selects the representation, see [`resolve_did_with_format`].
 pub fn resolve_did(
     did: &str,
     suit_id: &str,
 ) -> Result<(Option<DidDocument>, Option<KeyPair>), Error> {
-    let splitted_did: Vec<&str> = did.split('#').collect();
-    let did_authority = splitted_did[0];
-
-    let fingerprint = &did_authority["did:key:".len()..];
-    let verification_key_pair: Box<dyn VerificationKey> = match suit_id {
-        ed25519_verification_key2018::SUITE_ID => {
-            match ed25519_verification_key2018::Ed25519VerificationKey2018::from_fingerprint(
-                fingerprint,
-            ) {
-                Ok(val) => Box::new(val),
-                Err(error) => {
-                    return Err(error);
-                }
-            }
-        }
-        ed25519_verification_key2020::SUITE_ID => {
-            match ed25519_verification_key2020::Ed25519VerificationKey2020::from_fingerprint(
-                fingerprint,
-            ) {
-                Ok(val) => Box::new(val),
-                Err(error) => {
-                    return Err(error);
-                }
-            }
-        }
-        _ => {
-            return Err(Error::new(
-                format!(
-                    "Cannot derive key verification key from suite id {}",
-                    suit_id
-                )
-                .as_str(),
-            ))
-        }
-    };
+    resolve_did_with_format(did, Some(suit_id))
+}
 
-    let did_doc = match DidDoc::key_pair_to_did_doc(&verification_key_pair, fingerprint) {
-        Ok(val) => val,
-        Err(error) => return Err(error),
+/// Resolves a DID, or dereferences a DID URL pointing at one of its keys, with
+/// the resolver registered for its method (`did:key` is built in).
+///
+/// For `did:key` the key type is read from the multicodec header of the
+/// fingerprint. `suit_id` only selects the verification key suite used to
+/// represent the key; when it is `None` the default suite for the detected key
+/// type is used.
+pub fn resolve_did_with_format(
+    did: &str,
+    suit_id: Option<&str>,
+) -> Result<(Option<DidDocument>, Option<KeyPair>), Error> {
+    let options = ResolutionOptions {
+        public_key_format: suit_id.map(String::from),
+        enable_experimental_public_key_types: true,
+        ..ResolutionOptions::default()
     };
 
-    if splitted_did.len() > 1 {
-        let key_id_fragment = splitted_did[1];
-        let key = match did_doc.get_key_pair(key_id_fragment) {
-            Ok(val) => val,
-            Err(error) => return Err(error),
-        };
+    let result = dereference(did, &options);
+    if let Some(error) = result.dereferencing_metadata.error {
+        let failure = ResolutionFailure::new(
+            error,
+            result
+                .dereferencing_metadata
+                .error_message
+                .unwrap_or_default()
+                .as_str(),
+        );
+        return Err(Error::from(failure));
+    }
 
-        return Ok((None, Some(key)));
+    match result.content_stream {
+        Some(ContentStream::DidDocument(did_doc)) => Ok((Some(did_doc), None)),
+        Some(ContentStream::VerificationMethod(key)) => Ok((None, Some(*key))),
+        _ => Err(Error::new(
+            format!(
+                "'{}' does not dereference to a DID document or key pair",
+                did
+            )
+            .as_str(),
+        )),
     }
+}
+
+/// Resolves a DID following the DID Resolution `resolve` function, using the
+/// resolver registered for its method in the global registry.
+///
+/// Failures are reported through `didResolutionMetadata.error` instead of an
+/// `Err`, so the result can be returned to clients as is.
+pub fn resolve(did: &str, options: &ResolutionOptions) -> DidResolutionResult {
+    registry::resolve_with(did, options, global_resolver)
+}
 
-    return Ok((Some(did_doc), None));
+/// Dereferences a DID URL following the DID URL Dereferencing `dereference`
+/// function, using the resolver registered for its method in the global
+/// registry.
+pub fn dereference(did_url: &str, options: &ResolutionOptions) -> DidDereferencingResult {
+    registry::dereference_with(did_url, options, global_resolver)
 }
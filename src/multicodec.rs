@@ -0,0 +1,379 @@
+use bls12_381::{G1Affine, G2Affine, Scalar};
+use curve25519_dalek::{edwards::CompressedEdwardsY, MontgomeryPoint};
+use fi_common::error::Error;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use rsa::{
+    pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey, EncodeRsaPublicKey},
+    traits::PublicKeyParts,
+    RsaPrivateKey, RsaPublicKey,
+};
+
+use crate::{
+    bls12381_g1_key2020::G1,
+    bls12381_g2_key2020::G2,
+    bls12381_key2020::Bls12381Group,
+    resolution::{ResolutionError, ResolutionFailure},
+    util::{ed25519_private_key_to_public_key, ED25519_KEY_LENGTH, MULTIBASE_BASE58BTC_HEADER},
+};
+
+// multicodec ed25519-pub header as varint
+pub const MULTICODEC_ED25519_PUB_HEADER: [u8; 2] = [0xed, 0x01];
+// multicodec ed25519-priv header as varint
+pub const MULTICODEC_ED25519_PRIV_HEADER: [u8; 2] = [0x80, 0x26];
+// multicodec x25519-pub header as varint
+pub const MULTICODEC_X25519_PUB_HEADER: [u8; 2] = [0xec, 0x01];
+// multicodec x25519-priv header as varint
+pub const MULTICODEC_X25519_PRIV_HEADER: [u8; 2] = [0x82, 0x26];
+// multicodec secp256k1-pub header as varint
+pub const MULTICODEC_SECP256K1_PUB_HEADER: [u8; 2] = [0xe7, 0x01];
+// multicodec secp256k1-priv header as varint
+pub const MULTICODEC_SECP256K1_PRIV_HEADER: [u8; 2] = [0x81, 0x26];
+// multicodec p256-pub header as varint
+pub const MULTICODEC_P256_PUB_HEADER: [u8; 2] = [0x80, 0x24];
+// multicodec p256-priv header as varint
+pub const MULTICODEC_P256_PRIV_HEADER: [u8; 2] = [0x86, 0x26];
+// multicodec p384-pub header as varint
+pub const MULTICODEC_P384_PUB_HEADER: [u8; 2] = [0x81, 0x24];
+// multicodec p384-priv header as varint
+pub const MULTICODEC_P384_PRIV_HEADER: [u8; 2] = [0x87, 0x26];
+// multicodec p521-pub header as varint
+pub const MULTICODEC_P521_PUB_HEADER: [u8; 2] = [0x82, 0x24];
+// multicodec p521-priv header as varint
+pub const MULTICODEC_P521_PRIV_HEADER: [u8; 2] = [0x88, 0x26];
+// multicodec bls12_381-g1-pub header as varint
+pub const MULTICODEC_BLS12381_G1_PUB_HEADER: [u8; 2] = [0xea, 0x01];
+// multicodec bls12_381-g1-priv header as varint
+pub const MULTICODEC_BLS12381_G1_PRIV_HEADER: [u8; 2] = [0x89, 0x26];
+// multicodec bls12_381-g2-pub header as varint
+pub const MULTICODEC_BLS12381_G2_PUB_HEADER: [u8; 2] = [0xeb, 0x01];
+// multicodec bls12_381-g2-priv header as varint
+pub const MULTICODEC_BLS12381_G2_PRIV_HEADER: [u8; 2] = [0x8a, 0x26];
+
+// multicodec rsa-pub header as varint
+pub const MULTICODEC_RSA_PUB_HEADER: [u8; 2] = [0x85, 0x24];
+// multicodec rsa-priv header as varint
+pub const MULTICODEC_RSA_PRIV_HEADER: [u8; 2] = [0x85, 0x26];
+
+const X25519_PUBLIC_KEY_LENGTH: usize = 32;
+// length of a compressed BLS12-381 G1 point
+const BLS12381_G1_PUBLIC_KEY_LENGTH: usize = 48;
+// length of a compressed BLS12-381 G2 point
+const BLS12381_G2_PUBLIC_KEY_LENGTH: usize = 96;
+
+// supported RSA modulus sizes in bits
+const RSA_KEY_SIZES: [usize; 2] = [2048, 4096];
+
+// multicodec header, curve name and length of the compressed SEC1 point of
+// the elliptic curve public keys
+const SEC1_PUBLIC_KEYS: [([u8; 2], &str, usize); 4] = [
+    (MULTICODEC_SECP256K1_PUB_HEADER, "secp256k1", 33),
+    (MULTICODEC_P256_PUB_HEADER, "P-256", 33),
+    (MULTICODEC_P384_PUB_HEADER, "P-384", 49),
+    (MULTICODEC_P521_PUB_HEADER, "P-521", 67),
+];
+
+/// Decodes a base58-btc multibase `fingerprint`, e.g. the method specific id of
+/// a `did:key`, into its multicodec header and raw public key bytes.
+pub fn decode_fingerprint(fingerprint: &str) -> Result<([u8; 2], Vec<u8>), ResolutionFailure> {
+    if !fingerprint.starts_with(MULTIBASE_BASE58BTC_HEADER) {
+        return Err(ResolutionFailure::new(
+            ResolutionError::InvalidDid,
+            "`fingerprint` must be a multibase encoded string.",
+        ));
+    }
+
+    let decoded = match bs58::decode(&fingerprint[1..]).into_vec() {
+        Ok(val) => val,
+        Err(_error) => {
+            return Err(ResolutionFailure::new(
+                ResolutionError::InvalidDid,
+                "Couldn't convert decoded bs58 values into a vec",
+            ));
+        }
+    };
+
+    if decoded.len() < 2 {
+        return Err(ResolutionFailure::new(
+            ResolutionError::InvalidDid,
+            format!("Unsupported fingerprint {}", fingerprint).as_str(),
+        ));
+    }
+
+    Ok(([decoded[0], decoded[1]], decoded[2..].to_vec()))
+}
+
+/// Checks that the raw public key bytes are a valid key of the type named by
+/// the multicodec `header`.
+pub fn validate_public_key(header: &[u8; 2], key_bytes: &[u8]) -> Result<(), ResolutionFailure> {
+    if let Some((_, curve, length)) = SEC1_PUBLIC_KEYS.iter().find(|(val, _, _)| val.eq(header)) {
+        return validate_sec1_public_key(header, curve, *length, key_bytes);
+    }
+
+    match *header {
+        MULTICODEC_ED25519_PUB_HEADER => {
+            let key_bytes: [u8; ED25519_KEY_LENGTH] = match key_bytes.try_into() {
+                Ok(val) => val,
+                Err(_error) => {
+                    return Err(ResolutionFailure::new(
+                        ResolutionError::InvalidPublicKeyLength,
+                        format!(
+                            "Ed25519 public key must be {} bytes, found {}",
+                            ED25519_KEY_LENGTH,
+                            key_bytes.len()
+                        )
+                        .as_str(),
+                    ))
+                }
+            };
+
+            if CompressedEdwardsY(key_bytes).decompress().is_none() {
+                return Err(ResolutionFailure::new(
+                    ResolutionError::InvalidPublicKey,
+                    "Ed25519 public key is not a valid curve point",
+                ));
+            }
+
+            Ok(())
+        }
+        MULTICODEC_X25519_PUB_HEADER => {
+            // every 32 bytes string is a valid Montgomery u-coordinate
+            if key_bytes.len() != X25519_PUBLIC_KEY_LENGTH {
+                return Err(ResolutionFailure::new(
+                    ResolutionError::InvalidPublicKeyLength,
+                    format!(
+                        "X25519 public key must be {} bytes, found {}",
+                        X25519_PUBLIC_KEY_LENGTH,
+                        key_bytes.len()
+                    )
+                    .as_str(),
+                ));
+            }
+
+            Ok(())
+        }
+        MULTICODEC_RSA_PUB_HEADER => {
+            // the key is a DER encoded PKCS#1 RSAPublicKey
+            let public_key = match RsaPublicKey::from_pkcs1_der(key_bytes) {
+                Ok(val) => val,
+                Err(_error) => {
+                    return Err(ResolutionFailure::new(
+                        ResolutionError::InvalidPublicKey,
+                        "RSA public key is not a DER encoded PKCS#1 public key",
+                    ))
+                }
+            };
+
+            let key_size = public_key.n().bits();
+            if !RSA_KEY_SIZES.contains(&key_size) {
+                return Err(ResolutionFailure::new(
+                    ResolutionError::InvalidPublicKeyLength,
+                    format!(
+                        "RSA public key must be 2048 or 4096 bits, found {} bits",
+                        key_size
+                    )
+                    .as_str(),
+                ));
+            }
+
+            Ok(())
+        }
+        MULTICODEC_BLS12381_G1_PUB_HEADER => {
+            let key_bytes: [u8; BLS12381_G1_PUBLIC_KEY_LENGTH] = match key_bytes.try_into() {
+                Ok(val) => val,
+                Err(_error) => {
+                    return Err(ResolutionFailure::new(
+                        ResolutionError::InvalidPublicKeyLength,
+                        format!(
+                            "BLS12-381 G1 public key must be {} bytes, found {}",
+                            BLS12381_G1_PUBLIC_KEY_LENGTH,
+                            key_bytes.len()
+                        )
+                        .as_str(),
+                    ))
+                }
+            };
+
+            // also checks that the point is in the prime order subgroup
+            if G1Affine::from_compressed(&key_bytes).is_none().into() {
+                return Err(ResolutionFailure::new(
+                    ResolutionError::InvalidPublicKey,
+                    "BLS12-381 G1 public key is not a valid group element",
+                ));
+            }
+
+            Ok(())
+        }
+        MULTICODEC_BLS12381_G2_PUB_HEADER => {
+            let key_bytes: [u8; BLS12381_G2_PUBLIC_KEY_LENGTH] = match key_bytes.try_into() {
+                Ok(val) => val,
+                Err(_error) => {
+                    return Err(ResolutionFailure::new(
+                        ResolutionError::InvalidPublicKeyLength,
+                        format!(
+                            "BLS12-381 G2 public key must be {} bytes, found {}",
+                            BLS12381_G2_PUBLIC_KEY_LENGTH,
+                            key_bytes.len()
+                        )
+                        .as_str(),
+                    ))
+                }
+            };
+
+            // also checks that the point is in the prime order subgroup
+            if G2Affine::from_compressed(&key_bytes).is_none().into() {
+                return Err(ResolutionFailure::new(
+                    ResolutionError::InvalidPublicKey,
+                    "BLS12-381 G2 public key is not a valid group element",
+                ));
+            }
+
+            Ok(())
+        }
+        _ => Err(ResolutionFailure::new(
+            ResolutionError::UnsupportedPublicKeyType,
+            format!(
+                "Unsupported multicodec header 0x{:02x}{:02x}",
+                header[0], header[1]
+            )
+            .as_str(),
+        )),
+    }
+}
+
+fn validate_sec1_public_key(
+    header: &[u8; 2],
+    curve: &str,
+    length: usize,
+    key_bytes: &[u8],
+) -> Result<(), ResolutionFailure> {
+    if key_bytes.len() != length {
+        return Err(ResolutionFailure::new(
+            ResolutionError::InvalidPublicKeyLength,
+            format!(
+                "{} public key must be a {} bytes compressed point, found {} bytes",
+                curve,
+                length,
+                key_bytes.len()
+            )
+            .as_str(),
+        ));
+    }
+
+    if encode_sec1_point(header, key_bytes, true).is_none() {
+        return Err(ResolutionFailure::new(
+            ResolutionError::InvalidPublicKey,
+            format!("{} public key is not a valid curve point", curve).as_str(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Re-encodes the SEC1 point `key_bytes` of the elliptic curve named by the
+/// multicodec `header` as a compressed or uncompressed SEC1 point. Returns
+/// `None` if `key_bytes` is not a point on the curve.
+pub fn encode_sec1_point(header: &[u8; 2], key_bytes: &[u8], compress: bool) -> Option<Vec<u8>> {
+    match *header {
+        MULTICODEC_SECP256K1_PUB_HEADER => k256::PublicKey::from_sec1_bytes(key_bytes)
+            .ok()
+            .map(|val| val.to_encoded_point(compress).as_bytes().to_vec()),
+        MULTICODEC_P256_PUB_HEADER => p256::PublicKey::from_sec1_bytes(key_bytes)
+            .ok()
+            .map(|val| val.to_encoded_point(compress).as_bytes().to_vec()),
+        MULTICODEC_P384_PUB_HEADER => p384::PublicKey::from_sec1_bytes(key_bytes)
+            .ok()
+            .map(|val| val.to_encoded_point(compress).as_bytes().to_vec()),
+        MULTICODEC_P521_PUB_HEADER => p521::PublicKey::from_sec1_bytes(key_bytes)
+            .ok()
+            .map(|val| val.to_encoded_point(compress).as_bytes().to_vec()),
+        _ => None,
+    }
+}
+
+/// Derives the raw public key bytes of the key type named by the multicodec
+/// public key `header` from the raw private key bytes, as they are encoded
+/// after the matching private key header.
+pub fn derive_public_key(header: &[u8; 2], private_key: &[u8]) -> Result<Vec<u8>, Error> {
+    let invalid_private_key = || Error::new("Private key is not a valid key of its key type");
+
+    match *header {
+        MULTICODEC_ED25519_PUB_HEADER => {
+            Ok(ed25519_private_key_to_public_key(private_key)?.to_vec())
+        }
+        MULTICODEC_X25519_PUB_HEADER => match <[u8; 32]>::try_from(private_key) {
+            Ok(val) => Ok(MontgomeryPoint::mul_base_clamped(val).to_bytes().to_vec()),
+            Err(_error) => Err(invalid_private_key()),
+        },
+        MULTICODEC_SECP256K1_PUB_HEADER => match k256::SecretKey::from_slice(private_key) {
+            Ok(val) => Ok(val.public_key().to_encoded_point(true).as_bytes().to_vec()),
+            Err(_error) => Err(invalid_private_key()),
+        },
+        MULTICODEC_P256_PUB_HEADER => match p256::SecretKey::from_slice(private_key) {
+            Ok(val) => Ok(val.public_key().to_encoded_point(true).as_bytes().to_vec()),
+            Err(_error) => Err(invalid_private_key()),
+        },
+        MULTICODEC_P384_PUB_HEADER => match p384::SecretKey::from_slice(private_key) {
+            Ok(val) => Ok(val.public_key().to_encoded_point(true).as_bytes().to_vec()),
+            Err(_error) => Err(invalid_private_key()),
+        },
+        MULTICODEC_P521_PUB_HEADER => match p521::SecretKey::from_slice(private_key) {
+            Ok(val) => Ok(val.public_key().to_encoded_point(true).as_bytes().to_vec()),
+            Err(_error) => Err(invalid_private_key()),
+        },
+        MULTICODEC_BLS12381_G1_PUB_HEADER => {
+            let secret_key = bls12381_secret_key(private_key).ok_or_else(invalid_private_key)?;
+
+            Ok(G1::public_key(&secret_key))
+        }
+        MULTICODEC_BLS12381_G2_PUB_HEADER => {
+            let secret_key = bls12381_secret_key(private_key).ok_or_else(invalid_private_key)?;
+
+            Ok(G2::public_key(&secret_key))
+        }
+        MULTICODEC_RSA_PUB_HEADER => {
+            let private_key = match RsaPrivateKey::from_pkcs1_der(private_key) {
+                Ok(val) => val,
+                Err(_error) => return Err(invalid_private_key()),
+            };
+
+            match private_key.to_public_key().to_pkcs1_der() {
+                Ok(val) => Ok(val.as_bytes().to_vec()),
+                Err(error) => Err(Error::new(error.to_string().as_str())),
+            }
+        }
+        _ => Err(Error::new(
+            format!(
+                "Unsupported multicodec header 0x{:02x}{:02x}",
+                header[0], header[1]
+            )
+            .as_str(),
+        )),
+    }
+}
+
+/// Checks that the raw `public_key` is a valid key of the type named by the
+/// multicodec public key `header` and that the raw `private_key`, if any,
+/// belongs to it.
+pub(crate) fn check_key_pair(
+    header: &[u8; 2],
+    public_key: &[u8],
+    private_key: Option<&[u8]>,
+) -> Result<(), Error> {
+    validate_public_key(header, public_key)?;
+
+    if let Some(private_key) = private_key {
+        if derive_public_key(header, private_key)? != public_key {
+            return Err(Error::new("Private key does not belong to the public key"));
+        }
+    }
+
+    Ok(())
+}
+
+// the scalar of a big endian BLS12-381 secret key
+fn bls12381_secret_key(private_key: &[u8]) -> Option<Scalar> {
+    let mut bytes: [u8; 32] = private_key.try_into().ok()?;
+    bytes.reverse();
+
+    Option::from(Scalar::from_bytes(&bytes))
+}
@@ -0,0 +1,400 @@
+use fi_common::{
+    error::Error,
+    keys::{AgreementKey, KeyPair, VerificationKey},
+};
+
+use crate::{
+    ed25519_verification_key2018, ed25519_verification_key2020,
+    fingerprint::KeyFingerprint,
+    json_web_key2020,
+    multicodec::{
+        self, MULTICODEC_BLS12381_G1_PRIV_HEADER, MULTICODEC_BLS12381_G1_PUB_HEADER,
+        MULTICODEC_BLS12381_G2_PRIV_HEADER, MULTICODEC_BLS12381_G2_PUB_HEADER,
+        MULTICODEC_ED25519_PRIV_HEADER, MULTICODEC_ED25519_PUB_HEADER, MULTICODEC_P256_PRIV_HEADER,
+        MULTICODEC_P256_PUB_HEADER, MULTICODEC_P384_PRIV_HEADER, MULTICODEC_P384_PUB_HEADER,
+        MULTICODEC_P521_PRIV_HEADER, MULTICODEC_P521_PUB_HEADER, MULTICODEC_RSA_PRIV_HEADER,
+        MULTICODEC_RSA_PUB_HEADER, MULTICODEC_SECP256K1_PRIV_HEADER,
+        MULTICODEC_SECP256K1_PUB_HEADER, MULTICODEC_X25519_PRIV_HEADER,
+        MULTICODEC_X25519_PUB_HEADER,
+    },
+    util::{base58_to_multibase, multibase_decode, MULTIBASE_BASE58BTC_HEADER},
+    x25519_key_agreement_key2019,
+    x25519_key_agreement_key2020::{self, X25519KeyAgreementKey2020},
+};
+
+pub(crate) const SUITE_ID: &str = "Multikey";
+pub(crate) const SUITE_CONTEXT: &str = "https://w3id.org/security/multikey/v1";
+
+// public key multicodec header and the matching private key header
+const SUPPORTED_HEADERS: [([u8; 2], [u8; 2]); 9] = [
+    (
+        MULTICODEC_ED25519_PUB_HEADER,
+        MULTICODEC_ED25519_PRIV_HEADER,
+    ),
+    (MULTICODEC_X25519_PUB_HEADER, MULTICODEC_X25519_PRIV_HEADER),
+    (
+        MULTICODEC_SECP256K1_PUB_HEADER,
+        MULTICODEC_SECP256K1_PRIV_HEADER,
+    ),
+    (MULTICODEC_P256_PUB_HEADER, MULTICODEC_P256_PRIV_HEADER),
+    (MULTICODEC_P384_PUB_HEADER, MULTICODEC_P384_PRIV_HEADER),
+    (MULTICODEC_P521_PUB_HEADER, MULTICODEC_P521_PRIV_HEADER),
+    (
+        MULTICODEC_BLS12381_G1_PUB_HEADER,
+        MULTICODEC_BLS12381_G1_PRIV_HEADER,
+    ),
+    (
+        MULTICODEC_BLS12381_G2_PUB_HEADER,
+        MULTICODEC_BLS12381_G2_PRIV_HEADER,
+    ),
+    (MULTICODEC_RSA_PUB_HEADER, MULTICODEC_RSA_PRIV_HEADER),
+];
+
+/// A `Multikey` verification method. The key type is given by the multicodec
+/// header of `publicKeyMultibase`, so the same type expresses Ed25519,
+/// secp256k1, NIST P-256/P-384/P-521, BLS12-381 G1/G2 and RSA verification
+/// keys as well as X25519 key agreement keys.
+///
+/// The private key is exported as `privateKeyMultibase`.
+pub struct Multikey {
+    _type: String,
+    id: Option<String>,
+    controller: Option<String>,
+    public_key_multibase: String,
+    private_key_multibase: Option<String>,
+    revoked: bool,
+}
+
+impl Multikey {
+    pub fn new(
+        controller: Option<String>,
+        public_key_multibase: String,
+        private_key_multibase: Option<String>,
+        fingerprint: Option<String>,
+    ) -> Result<Self, Error> {
+        let public_key_header = match decode_header(&public_key_multibase) {
+            Some(val) => val,
+            None => {
+                return Err(Error::new(
+                    format!(
+                        "'publicKeyMultibase' has invalid header bytes: '{}'.",
+                        public_key_multibase
+                    )
+                    .as_str(),
+                ))
+            }
+        };
+
+        let private_key_header = match SUPPORTED_HEADERS
+            .iter()
+            .find(|(public_header, _)| public_header.eq(&public_key_header))
+        {
+            Some((_, private_header)) => private_header,
+            None => {
+                return Err(Error::new(
+                    format!(
+                        "'publicKeyMultibase' has an unsupported key type: '{}'.",
+                        public_key_multibase
+                    )
+                    .as_str(),
+                ))
+            }
+        };
+
+        if let Some(private_key) = &private_key_multibase {
+            if decode_header(private_key).as_ref() != Some(private_key_header) {
+                return Err(Error::new(
+                    format!(
+                        "'privateKeyMultibase' has invalid header bytes: '{}'.",
+                        private_key
+                    )
+                    .as_str(),
+                ));
+            }
+
+            Multikey::check_private_key(
+                &public_key_header,
+                &public_key_multibase,
+                private_key_header,
+                private_key,
+            )?;
+        }
+
+        let mut id: Option<String> = None;
+        if controller.is_some() && fingerprint.is_some() {
+            let ctrler = controller.clone().unwrap();
+            let fprint = fingerprint.clone().unwrap();
+            id = Some(format!("{}#{}", ctrler, fprint));
+        }
+
+        Ok(Multikey {
+            _type: String::from(SUITE_ID),
+            id,
+            controller,
+            public_key_multibase,
+            private_key_multibase,
+            revoked: false,
+        })
+    }
+
+    /// Expresses an `Ed25519VerificationKey2018`, `Ed25519VerificationKey2020`
+    /// or `JsonWebKey2020` key as a `Multikey`.
+    pub fn from_ed25519_verification_key(key_pair: &dyn VerificationKey) -> Result<Self, Error> {
+        let (public_key_multibase, private_key_multibase) = match key_pair.get_current_suite_id() {
+            ed25519_verification_key2018::SUITE_ID => (
+                base58_to_multibase(
+                    &MULTICODEC_ED25519_PUB_HEADER,
+                    key_pair.get_public_key_content(),
+                )?,
+                match key_pair.get_private_key_content() {
+                    Some(val) => Some(base58_to_multibase(&MULTICODEC_ED25519_PRIV_HEADER, val)?),
+                    None => None,
+                },
+            ),
+            ed25519_verification_key2020::SUITE_ID | json_web_key2020::SUITE_ID | SUITE_ID => (
+                key_pair.get_public_key_content().clone(),
+                key_pair.get_private_key_content().clone(),
+            ),
+            suite_id => {
+                return Err(Error::new(
+                    format!("Cannot express a {} key as a Multikey", suite_id).as_str(),
+                ))
+            }
+        };
+
+        let controller = key_pair
+            .get_controller()
+            .clone()
+            .unwrap_or_else(|| format!("did:key:{}", public_key_multibase));
+
+        Multikey::new(
+            Some(controller),
+            public_key_multibase.clone(),
+            private_key_multibase,
+            Some(public_key_multibase),
+        )
+    }
+
+    /// Expresses an `X25519KeyAgreementKey2019`, `X25519KeyAgreementKey2020`
+    /// or `JsonWebKey2020` key agreement key as a `Multikey`.
+    pub fn from_x25519_key_agreement_key(key_pair: &dyn AgreementKey) -> Result<Self, Error> {
+        let (public_key_multibase, private_key_multibase) = match key_pair
+            .get_current_suite_context()
+        {
+            x25519_key_agreement_key2019::SUITE_CONTEXT => (
+                base58_to_multibase(
+                    &MULTICODEC_X25519_PUB_HEADER,
+                    key_pair.get_public_key_content(),
+                )?,
+                match key_pair.get_private_key_content() {
+                    Some(val) => Some(base58_to_multibase(&MULTICODEC_X25519_PRIV_HEADER, val)?),
+                    None => None,
+                },
+            ),
+            x25519_key_agreement_key2020::SUITE_CONTEXT
+            | json_web_key2020::SUITE_CONTEXT
+            | SUITE_CONTEXT => (
+                key_pair.get_public_key_content().clone(),
+                key_pair.get_private_key_content().clone(),
+            ),
+            suite_context => {
+                return Err(Error::new(
+                    format!("Cannot express a {} key as a Multikey", suite_context).as_str(),
+                ))
+            }
+        };
+
+        let controller = key_pair
+            .get_controller()
+            .clone()
+            .unwrap_or_else(|| format!("did:key:{}", public_key_multibase));
+
+        Multikey::new(
+            Some(controller),
+            public_key_multibase.clone(),
+            private_key_multibase,
+            Some(public_key_multibase),
+        )
+    }
+
+    /// Derives the X25519 key agreement `Multikey` of an Ed25519 `Multikey`.
+    pub fn to_x25519_key_agreement_key(&self) -> Result<Self, Error> {
+        if decode_header(&self.public_key_multibase) != Some(MULTICODEC_ED25519_PUB_HEADER) {
+            return Err(Error::new(
+                "Key agreement keys can only be derived from Ed25519 keys",
+            ));
+        }
+
+        let agreement_key = X25519KeyAgreementKey2020::from_ed25519_verification_key2020(self)?;
+
+        Multikey::new(
+            AgreementKey::get_controller(&agreement_key).clone(),
+            AgreementKey::get_public_key_content(&agreement_key).clone(),
+            AgreementKey::get_private_key_content(&agreement_key).clone(),
+            Some(AgreementKey::get_public_key_content(&agreement_key).clone()),
+        )
+    }
+
+    /// The multicodec header of the public key, naming the key type.
+    pub fn get_public_key_header(&self) -> [u8; 2] {
+        decode_header(&self.public_key_multibase).unwrap_or_default()
+    }
+
+    // checks that the private key belongs to the public key
+    fn check_private_key(
+        public_key_header: &[u8; 2],
+        public_key_multibase: &String,
+        private_key_header: &[u8; 2],
+        private_key_multibase: &String,
+    ) -> Result<(), Error> {
+        let public_key = multibase_decode(public_key_header, public_key_multibase)?;
+        let private_key = multibase_decode(private_key_header, private_key_multibase)?;
+
+        if multicodec::derive_public_key(public_key_header, &private_key)? != public_key {
+            return Err(Error::new(
+                "'privateKeyMultibase' does not belong to 'publicKeyMultibase'",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn to_key_pair(&self, public_key: bool, private_key: bool, include_context: bool) -> KeyPair {
+        KeyPair {
+            id: self.id.clone(),
+            _type: self._type.clone(),
+            context: match include_context {
+                true => Some(Vec::from([String::from(SUITE_CONTEXT)])),
+                false => None,
+            },
+            public_key_base58: None,
+            private_key_base58: None,
+            private_key_multibase: match private_key {
+                true => self.private_key_multibase.clone(),
+                false => None,
+            },
+            public_key_multibase: match public_key {
+                true => Some(self.public_key_multibase.clone()),
+                false => None,
+            },
+            revoked: Some(self.revoked),
+            controller: self.controller.clone(),
+            blockchain_account_id: None,
+            public_key_hex: None,
+            public_key_base64: None,
+            public_key_pem: None,
+            private_key_hex: None,
+            private_key_base64: None,
+            private_key_pem: None,
+            value: None,
+            ethereum_address: None,
+            public_key_jwk: None,
+            private_key_jwk: None,
+        }
+    }
+}
+
+fn decode_header(multibase_key: &str) -> Option<[u8; 2]> {
+    if !multibase_key.starts_with(MULTIBASE_BASE58BTC_HEADER) {
+        return None;
+    }
+
+    let decoded = match bs58::decode(&multibase_key[1..]).into_vec() {
+        Ok(val) => val,
+        Err(_error) => return None,
+    };
+
+    if decoded.len() < 2 {
+        return None;
+    }
+
+    Some([decoded[0], decoded[1]])
+}
+
+impl VerificationKey for Multikey {
+    fn from_fingerprint(fingerprint: &str) -> Result<Self, Error> {
+        Multikey::new(
+            Some(format!("did:key:{}", fingerprint)),
+            String::from(fingerprint),
+            None,
+            Some(String::from(fingerprint)),
+        )
+    }
+
+    fn get_suite_id() -> &'static str
+    where
+        Self: Sized,
+    {
+        SUITE_ID
+    }
+
+    fn get_current_suite_id(&self) -> &'static str {
+        SUITE_ID
+    }
+
+    fn get_suite_context() -> &'static str
+    where
+        Self: Sized,
+    {
+        SUITE_CONTEXT
+    }
+
+    fn get_current_suite_context(&self) -> &'static str {
+        SUITE_CONTEXT
+    }
+
+    fn get_controller(&self) -> &Option<String> {
+        &self.controller
+    }
+
+    fn get_type(&self) -> String {
+        self._type.clone()
+    }
+
+    fn get_private_key_content(&self) -> &Option<String> {
+        &self.private_key_multibase
+    }
+
+    fn get_public_key_content(&self) -> &String {
+        &self.public_key_multibase
+    }
+
+    fn export(&self, public_key: bool, private_key: bool, include_context: bool) -> KeyPair {
+        self.to_key_pair(public_key, private_key, include_context)
+    }
+}
+
+impl AgreementKey for Multikey {
+    fn get_suite_context() -> &'static str
+    where
+        Self: Sized,
+    {
+        SUITE_CONTEXT
+    }
+
+    fn get_current_suite_context(&self) -> &'static str {
+        SUITE_CONTEXT
+    }
+
+    fn get_controller(&self) -> &Option<String> {
+        &self.controller
+    }
+
+    fn get_private_key_content(&self) -> &Option<String> {
+        &self.private_key_multibase
+    }
+
+    fn get_public_key_content(&self) -> &String {
+        &self.public_key_multibase
+    }
+
+    fn export(&self, public_key: bool, private_key: bool, include_context: bool) -> KeyPair {
+        self.to_key_pair(public_key, private_key, include_context)
+    }
+}
+
+impl KeyFingerprint for Multikey {
+    fn get_fingerprint_key_bytes(&self) -> Result<([u8; 2], Vec<u8>), Error> {
+        Ok(multicodec::decode_fingerprint(&self.public_key_multibase)?)
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock, RwLock},
+};
+
+use crate::{
+    dereferencing::{dereference_did_document, DidDereferencingResult},
+    did_key::DidKeyResolver,
+    did_url::DidUrl,
+    resolution::{DidResolutionResult, ResolutionError, ResolutionOptions},
+};
+
+/// Resolver of a single DID method, e.g. `key` for `did:key` DIDs.
+pub trait DidMethodResolver: Send + Sync {
+    /// The method name this resolver handles, without the `did:` prefix.
+    fn method(&self) -> &str;
+
+    /// Resolves `did_url`, which is a plain DID of this resolver's method.
+    fn resolve(&self, did_url: &DidUrl, options: &ResolutionOptions) -> DidResolutionResult;
+
+    /// Dereferences `did_url` against the DID document of its DID.
+    fn dereference(&self, did_url: &DidUrl, options: &ResolutionOptions) -> DidDereferencingResult {
+        let did = match DidUrl::parse(did_url.did()) {
+            Ok(val) => val,
+            Err(failure) => return DidDereferencingResult::from(failure),
+        };
+
+        dereference_did_document(did_url, self.resolve(&did, options))
+    }
+}
+
+/// Dispatches resolution to the `DidMethodResolver` registered for the method
+/// name of the DID.
+pub struct DidResolverRegistry {
+    resolvers: HashMap<String, Arc<dyn DidMethodResolver>>,
+}
+
+impl DidResolverRegistry {
+    /// A registry with the built-in `did:key` resolver.
+    pub fn new() -> Self {
+        let mut registry = DidResolverRegistry::empty();
+        registry.register(Box::new(DidKeyResolver));
+
+        registry
+    }
+
+    /// A registry without any resolver.
+    pub fn empty() -> Self {
+        DidResolverRegistry {
+            resolvers: HashMap::new(),
+        }
+    }
+
+    /// Registers `resolver` for its method, replacing any resolver previously
+    /// registered for the same method.
+    pub fn register(&mut self, resolver: Box<dyn DidMethodResolver>) {
+        self.resolvers
+            .insert(String::from(resolver.method()), Arc::from(resolver));
+    }
+
+    pub fn is_supported(&self, method: &str) -> bool {
+        self.resolvers.contains_key(method)
+    }
+
+    /// The resolver registered for `method`, shared so it can be called after
+    /// a lock around the registry is released.
+    pub fn resolver(&self, method: &str) -> Option<Arc<dyn DidMethodResolver>> {
+        self.resolvers.get(method).cloned()
+    }
+
+    pub fn resolve(&self, did: &str, options: &ResolutionOptions) -> DidResolutionResult {
+        resolve_with(did, options, |method| self.resolver(method))
+    }
+
+    pub fn dereference(
+        &self,
+        did_url: &str,
+        options: &ResolutionOptions,
+    ) -> DidDereferencingResult {
+        dereference_with(did_url, options, |method| self.resolver(method))
+    }
+}
+
+// resolves `did` with the resolver `find_resolver` returns for its method
+pub(crate) fn resolve_with<F>(
+    did: &str,
+    options: &ResolutionOptions,
+    find_resolver: F,
+) -> DidResolutionResult
+where
+    F: FnOnce(&str) -> Option<Arc<dyn DidMethodResolver>>,
+{
+    let did_url = match DidUrl::parse(did) {
+        Ok(val) => val,
+        Err(failure) => return DidResolutionResult::from(failure),
+    };
+
+    if !did_url.is_did() {
+        return DidResolutionResult::from_error(
+            ResolutionError::InvalidDid,
+            format!("'{}' is a DID URL, not a DID", did).as_str(),
+        );
+    }
+
+    match find_resolver(did_url.method()) {
+        Some(resolver) => resolver.resolve(&did_url, options),
+        None => DidResolutionResult::from_error(
+            ResolutionError::MethodNotSupported,
+            format!("DID method '{}' is not supported", did_url.method()).as_str(),
+        ),
+    }
+}
+
+// dereferences `did_url` with the resolver `find_resolver` returns for its method
+pub(crate) fn dereference_with<F>(
+    did_url: &str,
+    options: &ResolutionOptions,
+    find_resolver: F,
+) -> DidDereferencingResult
+where
+    F: FnOnce(&str) -> Option<Arc<dyn DidMethodResolver>>,
+{
+    let did_url = match DidUrl::parse(did_url) {
+        Ok(val) => val,
+        Err(failure) => {
+            let error = match failure.error {
+                ResolutionError::InvalidDid => ResolutionError::InvalidDidUrl,
+                error => error,
+            };
+            return DidDereferencingResult::from_error(error, failure.message.as_str());
+        }
+    };
+
+    match find_resolver(did_url.method()) {
+        Some(resolver) => resolver.dereference(&did_url, options),
+        None => DidDereferencingResult::from_error(
+            ResolutionError::MethodNotSupported,
+            format!("DID method '{}' is not supported", did_url.method()).as_str(),
+        ),
+    }
+}
+
+impl Default for DidResolverRegistry {
+    fn default() -> Self {
+        DidResolverRegistry::new()
+    }
+}
+
+static GLOBAL_REGISTRY: OnceLock<RwLock<DidResolverRegistry>> = OnceLock::new();
+
+/// The process wide registry used by `resolve_did`, `resolve` and `dereference`.
+pub fn global_registry() -> &'static RwLock<DidResolverRegistry> {
+    GLOBAL_REGISTRY.get_or_init(|| RwLock::new(DidResolverRegistry::new()))
+}
+
+// the resolver registered for `method` in the global registry, cloned out so
+// the read lock is released before the resolver runs
+pub(crate) fn global_resolver(method: &str) -> Option<Arc<dyn DidMethodResolver>> {
+    match global_registry().read() {
+        Ok(registry) => registry.resolver(method),
+        Err(poisoned) => poisoned.into_inner().resolver(method),
+    }
+}
+
+/// Registers `resolver` in the global registry so `resolve_did` can resolve
+/// DIDs of its method.
+pub fn register_did_method(resolver: Box<dyn DidMethodResolver>) {
+    let mut registry = match global_registry().write() {
+        Ok(val) => val,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    registry.register(resolver);
+}
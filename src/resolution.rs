@@ -0,0 +1,215 @@
+use fi_common::did::DidDocument;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::failure::{error_codes, Failure};
+
+pub const DID_RESOLUTION_CONTEXT_URL: &str = "https://w3id.org/did-resolution/v1";
+pub const DID_LD_JSON_CONTENT_TYPE: &str = "application/did+ld+json";
+pub const DID_JSON_CONTENT_TYPE: &str = "application/did+json";
+
+pub const MULTIKEY_FORMAT: &str = crate::multikey::SUITE_ID;
+pub const JSON_WEB_KEY_2020_FORMAT: &str = crate::json_web_key2020::SUITE_ID;
+
+error_codes! {
+    /// Error codes of the DID Resolution specification, serialized as their
+    /// camelCase names (e.g. `invalidDid`).
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub enum ResolutionError {
+        InvalidDid => "invalidDid",
+        InvalidDidUrl => "invalidDidUrl",
+        MethodNotSupported => "methodNotSupported",
+        InvalidPublicKey => "invalidPublicKey",
+        InvalidPublicKeyLength => "invalidPublicKeyLength",
+        InvalidPublicKeyType => "invalidPublicKeyType",
+        UnsupportedPublicKeyType => "unsupportedPublicKeyType",
+        RepresentationNotSupported => "representationNotSupported",
+        NotFound => "notFound",
+        InternalError => "internalError",
+    }
+}
+
+pub type ResolutionFailure = Failure<ResolutionError>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DidResolutionMetadata {
+    #[serde(rename = "contentType", skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ResolutionError>,
+    #[serde(rename = "errorMessage", skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DidDocumentMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deactivated: Option<bool>,
+    #[serde(rename = "nextUpdate", skip_serializing_if = "Option::is_none")]
+    pub next_update: Option<String>,
+    #[serde(rename = "versionId", skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<String>,
+    #[serde(rename = "nextVersionId", skip_serializing_if = "Option::is_none")]
+    pub next_version_id: Option<String>,
+    #[serde(rename = "equivalentId", skip_serializing_if = "Option::is_none")]
+    pub equivalent_id: Option<Vec<String>>,
+    #[serde(rename = "canonicalId", skip_serializing_if = "Option::is_none")]
+    pub canonical_id: Option<String>,
+}
+
+/// Resolution options of the DID Resolution specification, including the
+/// `did:key` specific ones.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ResolutionOptions {
+    /// Media type of the requested representation, `application/did+ld+json`
+    /// when not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept: Option<String>,
+    /// Verification method type used to express the key, e.g. `Multikey`,
+    /// `JsonWebKey2020` or `Ed25519VerificationKey2020`. The default format of
+    /// the key type is used when not set.
+    #[serde(rename = "publicKeyFormat", skip_serializing_if = "Option::is_none")]
+    pub public_key_format: Option<String>,
+    /// Whether a key agreement key is derived from the verification key.
+    #[serde(rename = "enableEncryptionKeyDerivation")]
+    pub enable_encryption_key_derivation: bool,
+    /// Whether public key formats outside of `Multikey`, `JsonWebKey2020` and
+    /// `Ed25519VerificationKey2020` may be used.
+    #[serde(rename = "enableExperimentalPublicKeyTypes")]
+    pub enable_experimental_public_key_types: bool,
+    /// Contexts placed in front of the verification method contexts, the DID
+    /// v1 context when not set.
+    #[serde(rename = "defaultContext", skip_serializing_if = "Option::is_none")]
+    pub default_context: Option<Vec<String>>,
+}
+
+impl Default for ResolutionOptions {
+    fn default() -> Self {
+        ResolutionOptions {
+            accept: None,
+            public_key_format: None,
+            enable_encryption_key_derivation: true,
+            enable_experimental_public_key_types: false,
+            default_context: None,
+        }
+    }
+}
+
+impl ResolutionOptions {
+    pub(crate) fn content_type(&self) -> Result<&'static str, ResolutionFailure> {
+        match self.accept.as_deref() {
+            None | Some(DID_LD_JSON_CONTENT_TYPE) => Ok(DID_LD_JSON_CONTENT_TYPE),
+            Some(DID_JSON_CONTENT_TYPE) => Ok(DID_JSON_CONTENT_TYPE),
+            Some(accept) => Err(ResolutionFailure::new(
+                ResolutionError::RepresentationNotSupported,
+                format!("Representation '{}' is not supported", accept).as_str(),
+            )),
+        }
+    }
+
+    pub(crate) fn check_public_key_format(
+        &self,
+        public_key_format: &str,
+    ) -> Result<(), ResolutionFailure> {
+        if self.enable_experimental_public_key_types {
+            return Ok(());
+        }
+
+        match public_key_format {
+            MULTIKEY_FORMAT
+            | JSON_WEB_KEY_2020_FORMAT
+            | crate::ed25519_verification_key2020::SUITE_ID => Ok(()),
+            _ => Err(ResolutionFailure::new(
+                ResolutionError::InvalidPublicKeyType,
+                format!(
+                    "Public key format '{}' requires 'enableExperimentalPublicKeyTypes'",
+                    public_key_format
+                )
+                .as_str(),
+            )),
+        }
+    }
+}
+
+/// Result of the DID Resolution `resolve` function.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DidResolutionResult {
+    #[serde(rename = "@context")]
+    pub context: String,
+    #[serde(rename = "didResolutionMetadata")]
+    pub did_resolution_metadata: DidResolutionMetadata,
+    #[serde(rename = "didDocument", serialize_with = "serialize_without_nulls")]
+    pub did_document: Option<DidDocument>,
+    #[serde(rename = "didDocumentMetadata")]
+    pub did_document_metadata: DidDocumentMetadata,
+}
+
+impl DidResolutionResult {
+    pub fn from_did_document(did_document: DidDocument) -> Self {
+        DidResolutionResult {
+            context: String::from(DID_RESOLUTION_CONTEXT_URL),
+            did_resolution_metadata: DidResolutionMetadata {
+                content_type: Some(String::from(DID_LD_JSON_CONTENT_TYPE)),
+                error: None,
+                error_message: None,
+            },
+            did_document: Some(did_document),
+            did_document_metadata: DidDocumentMetadata::default(),
+        }
+    }
+
+    pub fn from_error(error: ResolutionError, error_message: &str) -> Self {
+        DidResolutionResult {
+            context: String::from(DID_RESOLUTION_CONTEXT_URL),
+            did_resolution_metadata: DidResolutionMetadata {
+                content_type: None,
+                error: Some(error),
+                error_message: Some(String::from(error_message)),
+            },
+            did_document: None,
+            did_document_metadata: DidDocumentMetadata::default(),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.did_resolution_metadata.error.is_some()
+    }
+}
+
+impl From<ResolutionFailure> for DidResolutionResult {
+    fn from(failure: ResolutionFailure) -> Self {
+        DidResolutionResult::from_error(failure.error, failure.message.as_str())
+    }
+}
+
+// `DidDocument` and `KeyPair` serialize every unset optional property as
+// `null`, which is not part of the DID document representation.
+pub(crate) fn serialize_without_nulls<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    let mut json = match serde_json::to_value(value) {
+        Ok(val) => val,
+        Err(error) => return Err(serde::ser::Error::custom(error)),
+    };
+    remove_nulls(&mut json);
+
+    json.serialize(serializer)
+}
+
+pub(crate) fn remove_nulls(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, val| !val.is_null());
+            map.values_mut().for_each(remove_nulls);
+        }
+        Value::Array(values) => values.iter_mut().for_each(remove_nulls),
+        _ => {}
+    }
+}
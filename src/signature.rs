@@ -0,0 +1,242 @@
+use ed25519_dalek::{
+    hazmat::{self, ExpandedSecretKey},
+    Signature, Signer, SigningKey, VerifyingKey,
+};
+use fi_common::{error::Error, keys::KeyPair};
+use sha2::Sha512;
+
+use crate::{
+    ed25519_verification_key2018, ed25519_verification_key2020,
+    failure::{error_codes, Failure},
+    json_web_key2020, jwk,
+    multicodec::{self, MULTICODEC_ED25519_PUB_HEADER},
+    multikey,
+    util::ED25519_KEY_LENGTH,
+};
+
+error_codes! {
+    /// Error codes of signing and verifying with an Ed25519 key.
+    pub enum SignatureError {
+        /// The key has no private key to sign with.
+        MissingPrivateKey => "missingPrivateKey",
+        /// The private key is malformed or does not belong to the public key.
+        InvalidPrivateKey => "invalidPrivateKey",
+        /// The public key is not an encoded curve point.
+        InvalidPublicKey => "invalidPublicKey",
+        /// The public key is a small order point, for which signatures are
+        /// meaningless.
+        WeakPublicKey => "weakPublicKey",
+        /// The signature is not a 64 byte Ed25519 signature.
+        InvalidSignature => "invalidSignature",
+        /// The signature does not verify under the strict rules of RFC 8032,
+        /// e.g. it is not canonical.
+        VerificationFailed => "verificationFailed",
+    }
+}
+
+pub type SignatureFailure = Failure<SignatureError>;
+
+/// Signing and verifying with an Ed25519 key.
+pub trait SignatureKey {
+    /// The raw public key and, if present, the raw private key of the key.
+    fn get_signature_key_bytes(&self) -> Result<(Vec<u8>, Option<Vec<u8>>), SignatureFailure>;
+
+    /// The expanded secret key of a key pair imported from one. It is kept
+    /// apart from the private key, which is always a seed.
+    fn get_expanded_secret_key(&self) -> Option<&ExpandedSecretKey> {
+        None
+    }
+
+    /// Signs `message` with the expanded secret key if there is one, and
+    /// otherwise with the private key, which is a 32 byte seed or a 64 byte
+    /// seed followed by the public key.
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, SignatureFailure> {
+        let (public_key, private_key) = self.get_signature_key_bytes()?;
+
+        if let Some(expanded_secret_key) = self.get_expanded_secret_key() {
+            let verifying_key = verifying_key(&public_key)?;
+            return sign_expanded(expanded_secret_key, &verifying_key, message);
+        }
+
+        let private_key = match private_key {
+            Some(val) => val,
+            None => {
+                return Err(SignatureFailure::new(
+                    SignatureError::MissingPrivateKey,
+                    "Key pair has no private key to sign with",
+                ))
+            }
+        };
+
+        let verifying_key = verifying_key(&public_key)?;
+        sign(&private_key, &verifying_key, message)
+    }
+
+    /// Verifies an Ed25519 `signature` of `message` with strict verification,
+    /// rejecting non-canonical signatures and small order public keys.
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), SignatureFailure> {
+        let (public_key, _) = self.get_signature_key_bytes()?;
+
+        verify(&public_key, message, signature)
+    }
+}
+
+/// Verifies an Ed25519 `signature` of `message` with the raw `public_key`, see
+/// [`SignatureKey::verify`].
+pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<(), SignatureFailure> {
+    let verifying_key = verifying_key(public_key)?;
+    if verifying_key.is_weak() {
+        return Err(SignatureFailure::new(
+            SignatureError::WeakPublicKey,
+            "Public key is a small order point",
+        ));
+    }
+
+    let signature = match Signature::from_slice(signature) {
+        Ok(val) => val,
+        Err(_error) => {
+            return Err(SignatureFailure::new(
+                SignatureError::InvalidSignature,
+                format!(
+                    "Signature must be {} bytes, found {}",
+                    Signature::BYTE_SIZE,
+                    signature.len()
+                )
+                .as_str(),
+            ))
+        }
+    };
+
+    match verifying_key.verify_strict(message, &signature) {
+        Ok(_) => Ok(()),
+        Err(_error) => Err(SignatureFailure::new(
+            SignatureError::VerificationFailed,
+            "Signature verification failed",
+        )),
+    }
+}
+
+fn sign(
+    private_key: &[u8],
+    verifying_key: &VerifyingKey,
+    message: &[u8],
+) -> Result<Vec<u8>, SignatureFailure> {
+    if private_key.len() != ED25519_KEY_LENGTH && private_key.len() != 2 * ED25519_KEY_LENGTH {
+        return Err(SignatureFailure::new(
+            SignatureError::InvalidPrivateKey,
+            format!(
+                "Private key must be {} or {} bytes, found {}",
+                ED25519_KEY_LENGTH,
+                2 * ED25519_KEY_LENGTH,
+                private_key.len()
+            )
+            .as_str(),
+        ));
+    }
+
+    let mut seed = [0u8; ED25519_KEY_LENGTH];
+    seed.copy_from_slice(&private_key[..ED25519_KEY_LENGTH]);
+    let signing_key = SigningKey::from_bytes(&seed);
+
+    // a 64 byte key must be the seed followed by the public key
+    if signing_key.verifying_key() != *verifying_key
+        || (private_key.len() == 2 * ED25519_KEY_LENGTH
+            && private_key[ED25519_KEY_LENGTH..] != verifying_key.to_bytes())
+    {
+        return Err(SignatureFailure::new(
+            SignatureError::InvalidPrivateKey,
+            "Private key does not belong to the public key",
+        ));
+    }
+
+    Ok(signing_key.sign(message).to_vec())
+}
+
+fn sign_expanded(
+    expanded_secret_key: &ExpandedSecretKey,
+    verifying_key: &VerifyingKey,
+    message: &[u8],
+) -> Result<Vec<u8>, SignatureFailure> {
+    if VerifyingKey::from(expanded_secret_key) != *verifying_key {
+        return Err(SignatureFailure::new(
+            SignatureError::InvalidPrivateKey,
+            "Expanded secret key does not belong to the public key",
+        ));
+    }
+
+    Ok(hazmat::raw_sign::<Sha512>(expanded_secret_key, message, verifying_key).to_vec())
+}
+
+// a copy of `expanded_secret_key` for the converted form of a key pair
+pub(crate) fn copy_expanded_secret_key(
+    expanded_secret_key: Option<&ExpandedSecretKey>,
+) -> Option<ExpandedSecretKey> {
+    expanded_secret_key.map(|val| ExpandedSecretKey {
+        scalar: val.scalar,
+        hash_prefix: val.hash_prefix,
+    })
+}
+
+fn verifying_key(public_key: &[u8]) -> Result<VerifyingKey, SignatureFailure> {
+    let public_key: [u8; ED25519_KEY_LENGTH] = match public_key.try_into() {
+        Ok(val) => val,
+        Err(_error) => {
+            return Err(SignatureFailure::new(
+                SignatureError::InvalidPublicKey,
+                format!(
+                    "Public key must be {} bytes, found {}",
+                    ED25519_KEY_LENGTH,
+                    public_key.len()
+                )
+                .as_str(),
+            ))
+        }
+    };
+
+    match VerifyingKey::from_bytes(&public_key) {
+        Ok(val) => Ok(val),
+        Err(_error) => Err(SignatureFailure::new(
+            SignatureError::InvalidPublicKey,
+            "Public key is not an Ed25519 curve point",
+        )),
+    }
+}
+
+/// The multicodec header and raw public key of an Ed25519VerificationKey2018,
+/// Ed25519VerificationKey2020, Multikey or JsonWebKey2020 verification method.
+pub(crate) fn verification_method_public_key(
+    verification_method: &KeyPair,
+) -> Result<([u8; 2], Vec<u8>), Error> {
+    match verification_method._type.as_str() {
+        ed25519_verification_key2018::SUITE_ID => match &verification_method.public_key_base58 {
+            Some(public_key_base58) => match bs58::decode(public_key_base58).into_vec() {
+                Ok(val) => Ok((MULTICODEC_ED25519_PUB_HEADER, val)),
+                Err(error) => Err(Error::new(error.to_string().as_str())),
+            },
+            None => Err(Error::new("Key pair is missing 'publicKeyBase58'")),
+        },
+        ed25519_verification_key2020::SUITE_ID | multikey::SUITE_ID => {
+            match &verification_method.public_key_multibase {
+                Some(public_key_multibase) => {
+                    Ok(multicodec::decode_fingerprint(public_key_multibase)?)
+                }
+                None => Err(Error::new("Key pair is missing 'publicKeyMultibase'")),
+            }
+        }
+        json_web_key2020::SUITE_ID => match &verification_method.public_key_jwk {
+            Some(public_key_jwk) => {
+                let decoded = jwk::decode_jwk(public_key_jwk)?;
+
+                Ok((decoded.public_key_header, decoded.public_key))
+            }
+            None => Err(Error::new("Key pair is missing 'publicKeyJwk'")),
+        },
+        _ => Err(Error::new(
+            format!(
+                "Cannot read the public key of key pair type '{}'",
+                verification_method._type
+            )
+            .as_str(),
+        )),
+    }
+}
@@ -1,15 +1,21 @@
+use bls12_381::Scalar;
 use curve25519_dalek::edwards::CompressedEdwardsY;
 use curve25519_dalek::traits::IsIdentity;
 use curve25519_dalek::MontgomeryPoint;
+use ed25519_dalek::SigningKey;
 use fi_common::error::Error;
+use rand_core::CryptoRngCore;
 use sha2::Digest;
 use sha2::Sha512;
 
 // multibase base58-btc header
 pub const MULTIBASE_BASE58BTC_HEADER: &str = "z";
 
+// length of an Ed25519 seed and public key
+pub(crate) const ED25519_KEY_LENGTH: usize = 32;
+
 pub fn multibase_decode(header: &[u8; 2], text: &String) -> Result<Vec<u8>, Error> {
-    let value_builder = multibase::decode(&text);
+    let value_builder = multibase::decode(text);
     let (_, mut value) = match value_builder {
         Ok(val) => val,
         Err(error) => {
@@ -17,9 +23,8 @@ pub fn multibase_decode(header: &[u8; 2], text: &String) -> Result<Vec<u8>, Erro
         }
     };
 
-    if value[0] == header[0] && value[1] == header[1] {
-        value.remove(0);
-        value.remove(0);
+    if value.starts_with(header) {
+        value.drain(..header.len());
 
         return Ok(value);
     }
@@ -31,9 +36,24 @@ pub fn multibase_encode(header: &[u8; 2], bytes: &mut Vec<u8>) -> String {
     let mut content_bytes: Vec<u8> = Vec::from(header);
     content_bytes.append(bytes);
 
-    let encoded_content_bytes = multibase::encode(multibase::Base::Base58Btc, content_bytes);
+    multibase::encode(multibase::Base::Base58Btc, content_bytes)
+}
+
+pub fn base58_decode(text: &str) -> Result<Vec<u8>, Error> {
+    match bs58::decode(text).into_vec() {
+        Ok(val) => Ok(val),
+        Err(error) => Err(Error::new(error.to_string().as_str())),
+    }
+}
+
+/// Re-encodes a base58 key as a multibase key with the multicodec `header`.
+pub fn base58_to_multibase(header: &[u8; 2], key_base58: &str) -> Result<String, Error> {
+    Ok(multibase_encode(header, &mut base58_decode(key_base58)?))
+}
 
-    return encoded_content_bytes;
+/// Re-encodes a multibase key with the multicodec `header` as a base58 key.
+pub fn multibase_to_base58(header: &[u8; 2], key_multibase: &String) -> Result<String, Error> {
+    Ok(bs58::encode(multibase_decode(header, key_multibase)?).into_string())
 }
 
 pub fn get_key_bytes_from_key_pair_bytes(
@@ -60,7 +80,7 @@ pub fn get_key_bytes_from_key_pair_bytes(
         }
     };
 
-    return Ok(bytes_32);
+    Ok(bytes_32)
 }
 
 pub fn ed25519_to_x25519_pubkey(ed25519_pubkey_bytes: &[u8; 32]) -> Result<[u8; 32], Error> {
@@ -68,10 +88,10 @@ pub fn ed25519_to_x25519_pubkey(ed25519_pubkey_bytes: &[u8; 32]) -> Result<[u8;
     let ed25519_point = CompressedEdwardsY(*ed25519_pubkey_bytes);
 
     // Decompress the point to get the Edwards form of the point
-    let edwards_point = ed25519_point
-        .decompress()
-        .ok_or("Invalid Ed25519 public key")
-        .unwrap();
+    let edwards_point = match ed25519_point.decompress() {
+        Some(val) => val,
+        None => return Err(Error::new("Invalid Ed25519 public key")),
+    };
 
     // Ensure the point is not the identity (neutral element)
     if edwards_point.is_identity() {
@@ -102,3 +122,94 @@ pub fn ed25519_to_x25519_privkey(ed25519_privkey_bytes: &[u8; 32]) -> [u8; 32] {
 
     x25519_privkey_bytes
 }
+
+/// Generates an Ed25519 key pair, returned as the 32 byte seed and the 32 byte
+/// public key.
+pub fn generate_ed25519_key_pair(rng: &mut impl CryptoRngCore) -> ([u8; 32], [u8; 32]) {
+    let mut seed = [0u8; 32];
+    rng.fill_bytes(&mut seed);
+
+    let public_key = SigningKey::from_bytes(&seed).verifying_key().to_bytes();
+
+    (seed, public_key)
+}
+
+/// The public key of raw Ed25519 private key bytes: a 32 byte seed or a 64
+/// byte libsodium `crypto_sign` secret key, i.e. the seed followed by the
+/// public key.
+pub fn ed25519_private_key_to_public_key(private_key: &[u8]) -> Result<[u8; 32], Error> {
+    let (_, public_key) = ed25519_private_key_seed(private_key)?;
+
+    Ok(public_key)
+}
+
+/// The X25519 private key of raw Ed25519 private key bytes, in any of the
+/// forms of [`ed25519_private_key_to_public_key`].
+pub fn ed25519_private_key_to_x25519(private_key: &[u8]) -> Result<[u8; 32], Error> {
+    let (seed, _) = ed25519_private_key_seed(private_key)?;
+
+    Ok(ed25519_to_x25519_privkey(&seed))
+}
+
+// returns the seed and the public key of a 32 byte seed or a 64 byte seed
+// followed by its public key. A 64 byte expanded secret key has no seed, so
+// it is rejected here and imported through `from_expanded_secret_key`.
+fn ed25519_private_key_seed(private_key: &[u8]) -> Result<([u8; 32], [u8; 32]), Error> {
+    if private_key.len() != ED25519_KEY_LENGTH && private_key.len() != 2 * ED25519_KEY_LENGTH {
+        return Err(Error::new(
+            format!(
+                "Ed25519 private key must be {} or {} bytes, found {}",
+                ED25519_KEY_LENGTH,
+                2 * ED25519_KEY_LENGTH,
+                private_key.len()
+            )
+            .as_str(),
+        ));
+    }
+
+    let mut seed = [0u8; ED25519_KEY_LENGTH];
+    seed.copy_from_slice(&private_key[..ED25519_KEY_LENGTH]);
+    let public_key = SigningKey::from_bytes(&seed).verifying_key().to_bytes();
+
+    if private_key.len() == 2 * ED25519_KEY_LENGTH
+        && private_key[ED25519_KEY_LENGTH..] != public_key
+    {
+        return Err(Error::new(
+            "64 byte Ed25519 private key must be the seed followed by its public key",
+        ));
+    }
+
+    Ok((seed, public_key))
+}
+
+/// Generates an X25519 key pair, returned as the clamped 32 byte private key
+/// and the 32 byte public key.
+pub fn generate_x25519_key_pair(rng: &mut impl CryptoRngCore) -> ([u8; 32], [u8; 32]) {
+    let mut private_key = [0u8; 32];
+    rng.fill_bytes(&mut private_key);
+
+    private_key[0] &= 248;
+    private_key[31] &= 127;
+    private_key[31] |= 64;
+
+    let public_key = MontgomeryPoint::mul_base_clamped(private_key).to_bytes();
+
+    (private_key, public_key)
+}
+
+/// Generates a BLS12-381 secret key, uniformly reduced from 64 random bytes.
+pub fn generate_bls12381_secret_key(rng: &mut impl CryptoRngCore) -> Scalar {
+    let mut bytes = [0u8; 64];
+    rng.fill_bytes(&mut bytes);
+
+    Scalar::from_bytes_wide(&bytes)
+}
+
+/// The big endian bytes of a BLS12-381 secret key, as used by
+/// `privateKeyBase58`.
+pub fn bls12381_secret_key_to_bytes(secret_key: &Scalar) -> Vec<u8> {
+    let mut bytes = secret_key.to_bytes().to_vec();
+    bytes.reverse();
+
+    bytes
+}
@@ -3,13 +3,21 @@ use fi_common::{
     error::Error,
     keys::{AgreementKey, KeyPair, VerificationKey},
 };
+use rand_core::{CryptoRngCore, OsRng};
 
 use crate::{
     ed25519_verification_key2018::Ed25519VerificationKey2018,
+    fingerprint::{self, KeyFingerprint},
+    multicodec::{
+        self, MULTICODEC_ED25519_PUB_HEADER, MULTICODEC_X25519_PRIV_HEADER,
+        MULTICODEC_X25519_PUB_HEADER,
+    },
     util::{
-        ed25519_to_x25519_privkey, ed25519_to_x25519_pubkey, get_key_bytes_from_key_pair_bytes,
-        MULTIBASE_BASE58BTC_HEADER,
+        base58_decode, base58_to_multibase, ed25519_private_key_to_x25519,
+        ed25519_to_x25519_pubkey, generate_x25519_key_pair, get_key_bytes_from_key_pair_bytes,
+        multibase_encode, multibase_to_base58,
     },
+    x25519_key_agreement_key2020::X25519KeyAgreementKey2020,
 };
 
 pub const SUITE_ID: &str = "X25519KeyAgreementKey2019";
@@ -49,8 +57,46 @@ impl X25519KeyAgreementKey2019 {
         }
     }
 
+    /// Generates a key pair with the OS random number generator, see
+    /// [`X25519KeyAgreementKey2019::generate_with_rng`].
+    pub fn generate() -> Self {
+        X25519KeyAgreementKey2019::generate_with_rng(&mut OsRng)
+    }
+
+    /// Generates a key pair whose controller is its encryption only `did:key`.
+    pub fn generate_with_rng(rng: &mut impl CryptoRngCore) -> Self {
+        let (private_key, public_key) = generate_x25519_key_pair(rng);
+
+        let fingerprint = multibase_encode(&MULTICODEC_X25519_PUB_HEADER, &mut public_key.to_vec());
+
+        X25519KeyAgreementKey2019::new(
+            Some(format!("did:key:{}", fingerprint)),
+            encode(public_key).into_string(),
+            Some(encode(private_key).into_string()),
+            Some(fingerprint),
+        )
+    }
+
+    /// The key agreement key of an X25519 `did:key` fingerprint.
+    pub fn from_fingerprint(fingerprint: &str) -> Result<Self, Error> {
+        let (header, public_key) = multicodec::decode_fingerprint(fingerprint)?;
+        if header != MULTICODEC_X25519_PUB_HEADER {
+            return Err(Error::new(
+                format!("Unsupported fingerprint {}", fingerprint).as_str(),
+            ));
+        }
+        multicodec::validate_public_key(&header, &public_key)?;
+
+        Ok(X25519KeyAgreementKey2019::new(
+            Some(format!("did:key:{}", fingerprint)),
+            encode(public_key).into_string(),
+            None,
+            Some(String::from(fingerprint)),
+        ))
+    }
+
     pub fn from_ed25519_verification_key2018(
-        key_pair: &Box<dyn VerificationKey>,
+        key_pair: &dyn VerificationKey,
     ) -> Result<X25519KeyAgreementKey2019, Error> {
         if !key_pair
             .get_current_suite_id()
@@ -63,10 +109,7 @@ impl X25519KeyAgreementKey2019 {
 
         let public_key_content = key_pair.get_public_key_content();
 
-        let public_key_base58 = match convert_from_ed_public_key(public_key_content) {
-            Ok(val) => val,
-            Err(error) => return Err(error),
-        };
+        let public_key_base58 = convert_from_ed_public_key(public_key_content)?;
 
         let private_key_content_option = key_pair.get_private_key_content();
 
@@ -74,25 +117,27 @@ impl X25519KeyAgreementKey2019 {
         if private_key_content_option.is_some() {
             let private_key_content = private_key_content_option.clone().unwrap();
 
-            if !public_key_content.starts_with(MULTIBASE_BASE58BTC_HEADER) {
-                return Err(Error::new(
-                format!("Expecting 'privateKeyMultibase' value to be multibase base58btc {} encoded (must start with 'z').", private_key_content).as_str(),
-            ));
-            }
-
-            let private_key = match convert_from_ed_private_key(&private_key_content) {
-                Ok(val) => val,
-                Err(error) => return Err(error),
-            };
+            let private_key = convert_from_ed_private_key(&private_key_content)?;
 
             private_key_base58 = Some(private_key);
         }
 
+        // the did:key of a derived key agreement key is the one of its Ed25519 key,
+        // and its fragment is the key agreement key's own fingerprint
+        let controller = match key_pair.get_controller() {
+            Some(val) => val.clone(),
+            None => format!(
+                "did:key:{}",
+                base58_to_multibase(&MULTICODEC_ED25519_PUB_HEADER, public_key_content)?
+            ),
+        };
+        let fingerprint = base58_to_multibase(&MULTICODEC_X25519_PUB_HEADER, &public_key_base58)?;
+
         Ok(X25519KeyAgreementKey2019::new(
-            key_pair.get_controller().clone(),
+            Some(controller),
             public_key_base58,
             private_key_base58,
-            Some(key_pair.get_public_key_content().clone()),
+            Some(fingerprint),
         ))
     }
 }
@@ -113,10 +158,7 @@ fn convert_from_ed_public_key(public_key_base58: &String) -> Result<String, Erro
             }
         };
 
-    let dh_pub_key_bytes = match ed25519_to_x25519_pubkey(&ed25519_pub_key) {
-        Ok(val) => val,
-        Err(error) => return Err(error),
-    };
+    let dh_pub_key_bytes = ed25519_to_x25519_pubkey(&ed25519_pub_key)?;
 
     let dh_pub_key_bytes_base58_builder = encode(dh_pub_key_bytes);
 
@@ -126,20 +168,12 @@ fn convert_from_ed_public_key(public_key_base58: &String) -> Result<String, Erro
 fn convert_from_ed_private_key(private_key_base58: &String) -> Result<String, Error> {
     let ed_pri_key_bytes_builder = bs58::decode(private_key_base58);
 
-    let mut ed_pri_key_bytes = match ed_pri_key_bytes_builder.into_vec() {
+    let ed_pri_key_bytes = match ed_pri_key_bytes_builder.into_vec() {
         Ok(val) => val,
         Err(error) => return Err(Error::new(error.to_string().as_str())),
     };
 
-    let ed25519_priv_key: [u8; 32] =
-        match get_key_bytes_from_key_pair_bytes(&mut ed_pri_key_bytes, false) {
-            Ok(val) => val,
-            Err(error) => {
-                return Err(error);
-            }
-        };
-
-    let dh_priv_key_bytes = ed25519_to_x25519_privkey(&ed25519_priv_key);
+    let dh_priv_key_bytes = ed25519_private_key_to_x25519(&ed_pri_key_bytes)?;
 
     let dh_priv_key_bytes_base58_builder = encode(dh_priv_key_bytes);
 
@@ -177,6 +211,7 @@ impl AgreementKey for X25519KeyAgreementKey2019 {
             value: None,
             ethereum_address: None,
             public_key_jwk: None,
+            private_key_jwk: None,
         }
     }
 
@@ -206,3 +241,74 @@ impl AgreementKey for X25519KeyAgreementKey2019 {
         SUITE_CONTEXT
     }
 }
+
+impl KeyFingerprint for X25519KeyAgreementKey2019 {
+    fn get_fingerprint_key_bytes(&self) -> Result<([u8; 2], Vec<u8>), Error> {
+        fingerprint::base58_key_bytes(&MULTICODEC_X25519_PUB_HEADER, &self.public_key_base58)
+    }
+}
+
+impl TryFrom<&X25519KeyAgreementKey2020> for X25519KeyAgreementKey2019 {
+    type Error = Error;
+
+    /// The same key pair with base58 keys, keeping the controller, id and
+    /// revocation status.
+    fn try_from(key_pair: &X25519KeyAgreementKey2020) -> Result<Self, Self::Error> {
+        let exported = key_pair.export(true, true, false);
+
+        let private_key_base58 = match &exported.private_key_multibase {
+            Some(val) => Some(multibase_to_base58(&MULTICODEC_X25519_PRIV_HEADER, val)?),
+            None => None,
+        };
+
+        Ok(X25519KeyAgreementKey2019 {
+            _type: String::from(SUITE_ID),
+            id: exported.id,
+            controller: exported.controller,
+            public_key_base58: multibase_to_base58(
+                &MULTICODEC_X25519_PUB_HEADER,
+                key_pair.get_public_key_content(),
+            )?,
+            private_key_base58,
+            revoked: exported.revoked.unwrap_or(false),
+        })
+    }
+}
+
+impl TryFrom<&KeyPair> for X25519KeyAgreementKey2019 {
+    type Error = Error;
+
+    /// The key pair of an exported `KeyPair`, e.g. one loaded from a keystore.
+    fn try_from(key_pair: &KeyPair) -> Result<Self, Self::Error> {
+        if key_pair._type != SUITE_ID {
+            return Err(Error::new(
+                format!("Key pair type '{}' is not '{}'", key_pair._type, SUITE_ID).as_str(),
+            ));
+        }
+
+        let public_key_base58 = match &key_pair.public_key_base58 {
+            Some(val) => val.clone(),
+            None => return Err(Error::new("Key pair is missing 'publicKeyBase58'")),
+        };
+        let private_key = match &key_pair.private_key_base58 {
+            Some(val) => Some(base58_decode(val)?),
+            None => None,
+        };
+        multicodec::check_key_pair(
+            &MULTICODEC_X25519_PUB_HEADER,
+            &base58_decode(&public_key_base58)?,
+            private_key.as_deref(),
+        )?;
+
+        let mut converted = X25519KeyAgreementKey2019::new(
+            key_pair.controller.clone(),
+            public_key_base58,
+            key_pair.private_key_base58.clone(),
+            None,
+        );
+        converted.id = key_pair.id.clone();
+        converted.revoked = key_pair.revoked.unwrap_or(false);
+
+        Ok(converted)
+    }
+}
@@ -1,26 +1,26 @@
 use fi_common::error::Error;
+use rand_core::{CryptoRngCore, OsRng};
 
 use crate::{
     ed25519_verification_key2020::Ed25519VerificationKey2020,
+    fingerprint::KeyFingerprint,
+    multicodec::{
+        self, MULTICODEC_ED25519_PRIV_HEADER, MULTICODEC_ED25519_PUB_HEADER,
+        MULTICODEC_X25519_PRIV_HEADER, MULTICODEC_X25519_PUB_HEADER,
+    },
+    multikey,
     util::{
-        ed25519_to_x25519_privkey, ed25519_to_x25519_pubkey, get_key_bytes_from_key_pair_bytes,
-        multibase_decode, multibase_encode, MULTIBASE_BASE58BTC_HEADER,
+        base58_to_multibase, ed25519_private_key_to_x25519, ed25519_to_x25519_pubkey,
+        generate_x25519_key_pair, get_key_bytes_from_key_pair_bytes, multibase_decode,
+        multibase_encode, MULTIBASE_BASE58BTC_HEADER,
     },
+    x25519_key_agreement_key2019::X25519KeyAgreementKey2019,
 };
 use fi_common::keys::{AgreementKey, KeyPair, VerificationKey};
 pub const SUITE_ID: &str = "X25519KeyAgreementKey2020";
 
 pub const SUITE_CONTEXT: &str = "https://w3id.org/security/suites/x25519-2020/v1";
 
-// multicodec ed25519-pub header as varint
-const MULTICODEC_ED25519_PUB_HEADER: [u8; 2] = [0xed, 0x01];
-// multicodec ed25519-priv header as varint
-const MULTICODEC_ED25519_PRIV_HEADER: [u8; 2] = [0x80, 0x26];
-// multicodec x25519-pub header as varint
-const MULTICODEC_X25519_PUB_HEADER: [u8; 2] = [0xec, 0x01];
-// multicodec x25519-priv header as varint
-const MULTICODEC_X25519_PRIV_HEADER: [u8; 2] = [0x82, 0x26];
-
 pub struct X25519KeyAgreementKey2020 {
     _type: String,
     id: Option<String>,
@@ -53,15 +53,57 @@ impl X25519KeyAgreementKey2020 {
         }
     }
 
+    /// Generates a key pair with the OS random number generator, see
+    /// [`X25519KeyAgreementKey2020::generate_with_rng`].
+    pub fn generate() -> Self {
+        X25519KeyAgreementKey2020::generate_with_rng(&mut OsRng)
+    }
+
+    /// Generates a key pair whose controller is its encryption only `did:key`.
+    pub fn generate_with_rng(rng: &mut impl CryptoRngCore) -> Self {
+        let (private_key, public_key) = generate_x25519_key_pair(rng);
+
+        let fingerprint = multibase_encode(&MULTICODEC_X25519_PUB_HEADER, &mut public_key.to_vec());
+
+        X25519KeyAgreementKey2020::new(
+            Some(format!("did:key:{}", fingerprint)),
+            fingerprint.clone(),
+            Some(multibase_encode(
+                &MULTICODEC_X25519_PRIV_HEADER,
+                &mut private_key.to_vec(),
+            )),
+            Some(fingerprint),
+        )
+    }
+
+    /// The key agreement key of an X25519 `did:key` fingerprint.
+    pub fn from_fingerprint(fingerprint: &str) -> Result<Self, Error> {
+        let (header, public_key) = multicodec::decode_fingerprint(fingerprint)?;
+        if header != MULTICODEC_X25519_PUB_HEADER {
+            return Err(Error::new(
+                format!("Unsupported fingerprint {}", fingerprint).as_str(),
+            ));
+        }
+        multicodec::validate_public_key(&header, &public_key)?;
+
+        Ok(X25519KeyAgreementKey2020::new(
+            Some(format!("did:key:{}", fingerprint)),
+            String::from(fingerprint),
+            None,
+            Some(String::from(fingerprint)),
+        ))
+    }
+
     pub fn from_ed25519_verification_key2020(
-        key_pair: &Box<dyn VerificationKey>,
+        key_pair: &dyn VerificationKey,
     ) -> Result<X25519KeyAgreementKey2020, Error> {
         if !key_pair
             .get_current_suite_id()
             .eq(Ed25519VerificationKey2020::get_suite_id())
+            && !key_pair.get_current_suite_id().eq(multikey::SUITE_ID)
         {
             return Err(Error::new(
-                "'key_pair' is not a Ed25519VerificationKey2020 struct instance",
+                "'key_pair' is not a Ed25519VerificationKey2020 or Multikey struct instance",
             ));
         }
 
@@ -73,46 +115,42 @@ impl X25519KeyAgreementKey2020 {
             ));
         }
 
-        let public_key_multibase = match convert_from_ed_public_key(public_key_content) {
-            Ok(val) => val,
-            Err(error) => return Err(error),
-        };
+        let public_key_multibase = convert_from_ed_public_key(public_key_content)?;
 
         let private_key_content_option = key_pair.get_private_key_content().clone();
 
         let mut private_key_multibase: Option<String> = None;
-        if private_key_content_option.is_some() {
-            let private_key_content = private_key_content_option.unwrap();
-
-            if !public_key_content.starts_with(MULTIBASE_BASE58BTC_HEADER) {
+        if let Some(private_key_content) = private_key_content_option {
+            if !private_key_content.starts_with(MULTIBASE_BASE58BTC_HEADER) {
                 return Err(Error::new(
                 format!("Expecting 'privateKeyMultibase' value to be multibase base58btc {} encoded (must start with 'z').", private_key_content).as_str(),
             ));
             }
 
-            let private_key = match convert_from_ed_private_key(&private_key_content) {
-                Ok(val) => val,
-                Err(error) => return Err(error),
-            };
+            let private_key = convert_from_ed_private_key(&private_key_content)?;
 
             private_key_multibase = Some(private_key);
         }
 
+        // the did:key of a derived key agreement key is the one of its Ed25519 key,
+        // and its fragment is the key agreement key's own fingerprint
+        let controller = key_pair
+            .get_controller()
+            .clone()
+            .unwrap_or_else(|| format!("did:key:{}", public_key_content));
+
         Ok(X25519KeyAgreementKey2020::new(
-            key_pair.get_controller().clone(),
-            public_key_multibase,
+            Some(controller),
+            public_key_multibase.clone(),
             private_key_multibase,
-            Some(key_pair.get_public_key_content().clone()),
+            Some(public_key_multibase),
         ))
     }
 }
 
 fn convert_from_ed_public_key(public_key_multibase: &String) -> Result<String, Error> {
     let mut ed_pub_key_bytes =
-        match multibase_decode(&MULTICODEC_ED25519_PUB_HEADER, public_key_multibase) {
-            Ok(val) => val,
-            Err(error) => return Err(error),
-        };
+        multibase_decode(&MULTICODEC_ED25519_PUB_HEADER, public_key_multibase)?;
 
     let ed25519_pub_key: [u8; 32] =
         match get_key_bytes_from_key_pair_bytes(&mut ed_pub_key_bytes, true) {
@@ -122,10 +160,7 @@ fn convert_from_ed_public_key(public_key_multibase: &String) -> Result<String, E
             }
         };
 
-    let dh_pub_key_bytes = match ed25519_to_x25519_pubkey(&ed25519_pub_key) {
-        Ok(val) => val,
-        Err(error) => return Err(error),
-    };
+    let dh_pub_key_bytes = ed25519_to_x25519_pubkey(&ed25519_pub_key)?;
 
     Ok(multibase_encode(
         &MULTICODEC_X25519_PUB_HEADER,
@@ -134,21 +169,10 @@ fn convert_from_ed_public_key(public_key_multibase: &String) -> Result<String, E
 }
 
 fn convert_from_ed_private_key(private_key_multibase: &String) -> Result<String, Error> {
-    let mut ed_pri_key_bytes =
-        match multibase_decode(&MULTICODEC_ED25519_PRIV_HEADER, private_key_multibase) {
-            Ok(val) => val,
-            Err(error) => return Err(error),
-        };
-
-    let ed25519_priv_key: [u8; 32] =
-        match get_key_bytes_from_key_pair_bytes(&mut ed_pri_key_bytes, false) {
-            Ok(val) => val,
-            Err(error) => {
-                return Err(error);
-            }
-        };
+    let ed_pri_key_bytes =
+        multibase_decode(&MULTICODEC_ED25519_PRIV_HEADER, private_key_multibase)?;
 
-    let dh_priv_key_bytes = ed25519_to_x25519_privkey(&ed25519_priv_key);
+    let dh_priv_key_bytes = ed25519_private_key_to_x25519(&ed_pri_key_bytes)?;
 
     Ok(multibase_encode(
         &MULTICODEC_X25519_PRIV_HEADER,
@@ -187,6 +211,7 @@ impl AgreementKey for X25519KeyAgreementKey2020 {
             value: None,
             ethereum_address: None,
             public_key_jwk: None,
+            private_key_jwk: None,
         }
     }
 
@@ -216,3 +241,74 @@ impl AgreementKey for X25519KeyAgreementKey2020 {
         SUITE_CONTEXT
     }
 }
+
+impl KeyFingerprint for X25519KeyAgreementKey2020 {
+    fn get_fingerprint_key_bytes(&self) -> Result<([u8; 2], Vec<u8>), Error> {
+        Ok(multicodec::decode_fingerprint(&self.public_key_multibase)?)
+    }
+}
+
+impl TryFrom<&X25519KeyAgreementKey2019> for X25519KeyAgreementKey2020 {
+    type Error = Error;
+
+    /// The same key pair with multibase keys, keeping the controller, id and
+    /// revocation status.
+    fn try_from(key_pair: &X25519KeyAgreementKey2019) -> Result<Self, Self::Error> {
+        let exported = key_pair.export(true, true, false);
+
+        let private_key_multibase = match &exported.private_key_base58 {
+            Some(val) => Some(base58_to_multibase(&MULTICODEC_X25519_PRIV_HEADER, val)?),
+            None => None,
+        };
+
+        Ok(X25519KeyAgreementKey2020 {
+            _type: String::from(SUITE_ID),
+            id: exported.id,
+            controller: exported.controller,
+            public_key_multibase: base58_to_multibase(
+                &MULTICODEC_X25519_PUB_HEADER,
+                key_pair.get_public_key_content(),
+            )?,
+            private_key_multibase,
+            revoked: exported.revoked.unwrap_or(false),
+        })
+    }
+}
+
+impl TryFrom<&KeyPair> for X25519KeyAgreementKey2020 {
+    type Error = Error;
+
+    /// The key pair of an exported `KeyPair`, e.g. one loaded from a keystore.
+    fn try_from(key_pair: &KeyPair) -> Result<Self, Self::Error> {
+        if key_pair._type != SUITE_ID {
+            return Err(Error::new(
+                format!("Key pair type '{}' is not '{}'", key_pair._type, SUITE_ID).as_str(),
+            ));
+        }
+
+        let public_key_multibase = match &key_pair.public_key_multibase {
+            Some(val) => val.clone(),
+            None => return Err(Error::new("Key pair is missing 'publicKeyMultibase'")),
+        };
+        let private_key = match &key_pair.private_key_multibase {
+            Some(val) => Some(multibase_decode(&MULTICODEC_X25519_PRIV_HEADER, val)?),
+            None => None,
+        };
+        multicodec::check_key_pair(
+            &MULTICODEC_X25519_PUB_HEADER,
+            &multibase_decode(&MULTICODEC_X25519_PUB_HEADER, &public_key_multibase)?,
+            private_key.as_deref(),
+        )?;
+
+        let mut converted = X25519KeyAgreementKey2020::new(
+            key_pair.controller.clone(),
+            public_key_multibase,
+            key_pair.private_key_multibase.clone(),
+            None,
+        );
+        converted.id = key_pair.id.clone();
+        converted.revoked = key_pair.revoked.unwrap_or(false);
+
+        Ok(converted)
+    }
+}
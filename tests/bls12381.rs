@@ -0,0 +1,89 @@
+use fi_key_resolver::resolution::{ResolutionError, ResolutionOptions};
+use fi_key_resolver::{resolve, resolve_did, resolve_did_with_format};
+use serde_json::Value;
+
+#[test]
+pub fn test_resolve_bls12381_g2() {
+    let did = "did:key:zUC7EK3ZakmukHhuncwkbySmomv3FmrkmS36E4Ks5rsb6VQSRpoCrx6Hb8e2Nk6UvJFSdyw9NK1scFXJp21gNNYFjVWNgaqyGnkyhtagagCpQb5B7tagJu3HDbjQ8h5ypoHjwBb";
+
+    let did_doc = match resolve_did_with_format(did, None) {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+
+    assert_eq!(
+        did_doc.context,
+        Vec::from([
+            Value::from("https://www.w3.org/ns/did/v1"),
+            Value::from("https://w3id.org/security/suites/bls12381-2020/v1"),
+        ])
+    );
+
+    let verification_method = &did_doc.verification_method.unwrap()[0];
+    assert_eq!(verification_method._type, "Bls12381G2Key2020");
+    assert_eq!(
+        verification_method.id.as_deref(),
+        Some(format!("{}#{}", did, &did[8..]).as_str())
+    );
+    assert_eq!(
+        verification_method.public_key_base58.as_deref(),
+        Some("zUVgX57gPfqhNgGuS2kZWUZ81VYYjTVAMfk3c1QnMc3e2DqaAHh8nggMDkkEPzhoVmXGKuA4EMyLs9HZD7CXuXyetEwsK9dVSEtHgTZwvdwJkzF5DxsB3oKRfWM6FF8sSaH")
+    );
+    assert!(did_doc.key_agreement.is_none());
+
+    let did_doc = match resolve_did(did, "Multikey") {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+    let verification_method = &did_doc.verification_method.unwrap()[0];
+    assert_eq!(verification_method._type, "Multikey");
+    assert_eq!(
+        verification_method.public_key_multibase.as_deref(),
+        Some(&did[8..])
+    );
+}
+
+#[test]
+pub fn test_resolve_bls12381_g1() {
+    // the G1 generator point
+    let did = "did:key:z3tEFUdV4D3tCMG6Fr1deVvt32DCS1Y4SxDGoELedXaMUdTdr5FfZvBnbK9bWMhAGj3RHk";
+
+    let did_doc = match resolve_did_with_format(did, None) {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+
+    let verification_method = &did_doc.verification_method.unwrap()[0];
+    assert_eq!(verification_method._type, "Bls12381G1Key2020");
+    assert_eq!(
+        verification_method.public_key_base58.as_deref(),
+        Some("6aGxBiDyBZxEL9RKJz8W2h7VQUUKv9BPqHbC2DNWKExVRM7GWtLk6mTQBYXbpgRHwp")
+    );
+
+    let result = resolve(
+        did,
+        &ResolutionOptions {
+            public_key_format: Some(String::from("JsonWebKey2020")),
+            ..ResolutionOptions::default()
+        },
+    );
+    assert_eq!(
+        result.did_resolution_metadata.error,
+        Some(ResolutionError::UnsupportedPublicKeyType)
+    );
+}
+
+#[test]
+pub fn test_resolve_invalid_bls12381_key() {
+    let result = resolve(
+        "did:key:z3tEFUdV4D3tCMG6Fr1deVvt32DCS1Y4SxDGoELedXaMUdTdr5FfZvBnbK9bWMhAGj3RHj",
+        &ResolutionOptions::default(),
+    );
+    assert_eq!(
+        result.did_resolution_metadata.error,
+        Some(ResolutionError::InvalidPublicKey)
+    );
+}
@@ -0,0 +1,590 @@
+use fi_key_resolver::data_integrity::{
+    create_proof, verify_proof, verify_proof_with_options, Cryptosuite, ProofError, ProofOptions,
+    ProofVerificationOptions,
+};
+use fi_key_resolver::ed25519_verification_key2020::Ed25519VerificationKey2020;
+use fi_key_resolver::json_ld::canonicalize;
+use fi_key_resolver::signature;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+mod common;
+use common::{decode_hex, FINGERPRINT, SEED};
+
+// the key pair and credential of the W3C vc-di-eddsa test vectors
+const W3C_PUBLIC_KEY_MULTIBASE: &str = "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2";
+const W3C_PRIVATE_KEY_MULTIBASE: &str = "z3u2en7t5LR2WtQH5PfFqMqwVHBeXouLzo6haApm8XHqvjxq";
+
+fn w3c_credential() -> Value {
+    json!({
+        "@context": [
+            "https://www.w3.org/ns/credentials/v2",
+            "https://www.w3.org/ns/credentials/examples/v2"
+        ],
+        "id": "urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33",
+        "type": ["VerifiableCredential", "AlumniCredential"],
+        "name": "Alumni Credential",
+        "description": "A minimum viable example of an Alumni Credential.",
+        "issuer": "https://vc.example/issuers/5678",
+        "validFrom": "2023-01-01T00:00:00Z",
+        "credentialSubject": {
+            "id": "did:example:abcdefgh",
+            "alumniOf": "The School of Examples"
+        }
+    })
+}
+
+fn w3c_key_pair() -> Ed25519VerificationKey2020 {
+    match Ed25519VerificationKey2020::new(
+        None,
+        String::from(W3C_PUBLIC_KEY_MULTIBASE),
+        Some(String::from(W3C_PRIVATE_KEY_MULTIBASE)),
+        None,
+    ) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+fn credential() -> Value {
+    json!({
+        "@context": ["https://www.w3.org/ns/credentials/v2"],
+        "type": ["VerifiableCredential"],
+        "issuer": format!("did:key:{}", FINGERPRINT),
+        "credentialSubject": {"id": "did:example:alice", "name": "Alice"}
+    })
+}
+
+fn secure(proof_purpose: &str) -> Value {
+    let key_pair = match Ed25519VerificationKey2020::from_private_key_bytes(&decode_hex(SEED)) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let mut options = ProofOptions::new(
+        format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT).as_str(),
+        proof_purpose,
+    );
+    options.created = Some(String::from("2024-01-01T00:00:00Z"));
+
+    match create_proof(&credential(), &options, &key_pair) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+#[test]
+pub fn test_create_eddsa_jcs_2022_proof() {
+    let secured = secure("assertionMethod");
+
+    assert_eq!(
+        secured["proof"],
+        json!({
+            "@context": ["https://www.w3.org/ns/credentials/v2"],
+            "type": "DataIntegrityProof",
+            "cryptosuite": "eddsa-jcs-2022",
+            "created": "2024-01-01T00:00:00Z",
+            "verificationMethod": format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT),
+            "proofPurpose": "assertionMethod",
+            "proofValue": "z4ft3oqjWKab39iZxergz2yKyBWkde9gtzBYN1NU2mJJdvmcJsoUEjzi5Vd9DHEyAjHRCQju3SfjvUXQZ4nN7EGjf"
+        })
+    );
+
+    let verification_method = match verify_proof(&secured) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        verification_method.id,
+        Some(format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT))
+    );
+}
+
+#[test]
+pub fn test_verify_eddsa_jcs_2022_proof_rejects_changes() {
+    let mut secured = secure("assertionMethod");
+    secured["credentialSubject"]["name"] = Value::from("Mallory");
+    match verify_proof(&secured) {
+        Ok(_) => panic!("Changed document must not verify"),
+        Err(failure) => assert_eq!(failure.error, ProofError::InvalidSignature),
+    };
+
+    let mut secured = secure("assertionMethod");
+    secured["proof"]["created"] = Value::from("2025-01-01T00:00:00Z");
+    match verify_proof(&secured) {
+        Ok(_) => panic!("Changed proof options must not verify"),
+        Err(failure) => assert_eq!(failure.error, ProofError::InvalidSignature),
+    };
+
+    let mut secured = secure("assertionMethod");
+    secured["proof"]["cryptosuite"] = Value::from("eddsa-rdfc-2022");
+    match verify_proof(&secured) {
+        Ok(_) => panic!("Another cryptosuite must not verify"),
+        Err(failure) => assert_eq!(failure.error, ProofError::InvalidSignature),
+    };
+
+    // an Ed25519Signature2020 has no cryptosuite
+    let mut secured = secure("assertionMethod");
+    secured["proof"]["type"] = Value::from("Ed25519Signature2020");
+    match verify_proof(&secured) {
+        Ok(_) => panic!("Ed25519Signature2020 with a cryptosuite must be rejected"),
+        Err(failure) => assert_eq!(failure.error, ProofError::UnsupportedCryptosuite),
+    };
+
+    let mut secured = secure("assertionMethod");
+    secured["proof"]["cryptosuite"] = Value::from("ecdsa-rdfc-2019");
+    match verify_proof(&secured) {
+        Ok(_) => panic!("Unknown cryptosuite must be rejected"),
+        Err(failure) => assert_eq!(failure.error, ProofError::UnsupportedCryptosuite),
+    };
+}
+
+#[test]
+pub fn test_verify_eddsa_jcs_2022_proof_checks_proof_purpose() {
+    // the key of an Ed25519 did:key is listed under every verification
+    // relationship but key agreement
+    let secured = secure("capabilityInvocation");
+    match verify_proof(&secured) {
+        Ok(_) => {}
+        Err(error) => panic!("{}", error),
+    };
+
+    let mut secured = secure("assertionMethod");
+    secured["proof"]["proofPurpose"] = Value::from("keyAgreement");
+    match verify_proof(&secured) {
+        Ok(_) => panic!("Key agreement is not a proof purpose"),
+        Err(failure) => assert_eq!(failure.error, ProofError::InvalidProofPurpose),
+    };
+
+    // a method of another DID document is not listed under its relationships
+    let mut secured = secure("assertionMethod");
+    secured["proof"]["verificationMethod"] = Value::from(format!(
+        "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH#{}",
+        FINGERPRINT
+    ));
+    match verify_proof(&secured) {
+        Ok(_) => panic!("A method missing from the relationship must be rejected"),
+        Err(failure) => assert_eq!(failure.error, ProofError::InvalidProofPurpose),
+    };
+}
+
+#[test]
+pub fn test_eddsa_jcs_2022_w3c_vector() {
+    let mut options = ProofOptions::new(
+        format!(
+            "did:key:{}#{}",
+            W3C_PUBLIC_KEY_MULTIBASE, W3C_PUBLIC_KEY_MULTIBASE
+        )
+        .as_str(),
+        "assertionMethod",
+    );
+    options.created = Some(String::from("2023-02-24T23:36:38Z"));
+
+    let secured = match create_proof(&w3c_credential(), &options, &w3c_key_pair()) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        secured["proof"]["proofValue"],
+        "z2HnFSSPPBzR36zdDgK8PbEHeXbR56YF24jwMpt3R1eHXQzJDMWS93FCzpvJpwTWd3GAVFuUfjoJdcnTMuVor51aX"
+    );
+
+    match verify_proof(&secured) {
+        Ok(_) => {}
+        Err(error) => panic!("{}", error),
+    };
+}
+
+#[test]
+pub fn test_create_eddsa_jcs_2022_proof_checks_verification_method() {
+    let key_pair = match Ed25519VerificationKey2020::from_private_key_bytes(&decode_hex(SEED)) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    for verification_method in [
+        format!(
+            "did:key:{}#{}",
+            W3C_PUBLIC_KEY_MULTIBASE, W3C_PUBLIC_KEY_MULTIBASE
+        ),
+        format!("did:key:{}#{}", W3C_PUBLIC_KEY_MULTIBASE, FINGERPRINT),
+        format!(
+            "https://vc.example/issuers/5678#{}",
+            W3C_PUBLIC_KEY_MULTIBASE
+        ),
+        String::from("https://vc.example/issuers/5678"),
+        String::from("did:key:"),
+    ] {
+        let options = ProofOptions::new(verification_method.as_str(), "assertionMethod");
+        match create_proof(&credential(), &options, &key_pair) {
+            Ok(_) => panic!("'{}' is not the signing key", verification_method),
+            Err(failure) => assert_eq!(failure.error, ProofError::InvalidVerificationMethod),
+        };
+    }
+
+    // the fragment of a URL that is not a DID URL is the fingerprint
+    let options = ProofOptions::new(
+        format!("https://vc.example/issuers/5678#{}", FINGERPRINT).as_str(),
+        "assertionMethod",
+    );
+    match create_proof(&credential(), &options, &key_pair) {
+        Ok(_) => {}
+        Err(error) => panic!("{}", error),
+    };
+}
+
+#[test]
+pub fn test_verify_eddsa_jcs_2022_proof_checks_options() {
+    let key_pair = match Ed25519VerificationKey2020::from_private_key_bytes(&decode_hex(SEED)) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    let mut options = ProofOptions::new(
+        format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT).as_str(),
+        "authentication",
+    );
+    options.created = Some(String::from("2024-01-01T00:00:00Z"));
+    // 2024-01-01T01:00:00Z
+    options.expires = Some(String::from("2024-01-01T02:00:00+01:00"));
+    options.challenge = Some(String::from("1f44d55f-f161-4938-a659-f8026467f126"));
+    options.domain = Some(String::from("https://example.com"));
+
+    let secured = match create_proof(&credential(), &options, &key_pair) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let expected = ProofVerificationOptions {
+        challenge: options.challenge.clone(),
+        domain: options.domain.clone(),
+        current_time: Some(1704067200),
+    };
+    match verify_proof_with_options(&secured, &expected) {
+        Ok(_) => {}
+        Err(error) => panic!("{}", error),
+    };
+
+    for (verification_options, error) in [
+        (
+            ProofVerificationOptions {
+                current_time: Some(1704070800),
+                ..expected.clone()
+            },
+            ProofError::ProofExpired,
+        ),
+        (
+            ProofVerificationOptions {
+                challenge: Some(String::from("another challenge")),
+                ..expected.clone()
+            },
+            ProofError::InvalidChallenge,
+        ),
+        (
+            ProofVerificationOptions {
+                domain: Some(String::from("https://example.org")),
+                ..expected.clone()
+            },
+            ProofError::InvalidDomain,
+        ),
+    ] {
+        match verify_proof_with_options(&secured, &verification_options) {
+            Ok(_) => panic!("{:?} must be rejected", verification_options),
+            Err(failure) => assert_eq!(failure.error, error),
+        };
+    }
+
+    // an expired proof is rejected at the system time
+    match verify_proof(&secured) {
+        Ok(_) => panic!("Expired proof must be rejected"),
+        Err(failure) => assert_eq!(failure.error, ProofError::ProofExpired),
+    };
+
+    // a proof without a challenge or domain does not have the expected ones
+    let secured = secure("authentication");
+    for verification_options in [
+        ProofVerificationOptions {
+            challenge: Some(String::from("1f44d55f-f161-4938-a659-f8026467f126")),
+            ..ProofVerificationOptions::default()
+        },
+        ProofVerificationOptions {
+            domain: Some(String::from("https://example.com")),
+            ..ProofVerificationOptions::default()
+        },
+    ] {
+        assert!(verify_proof_with_options(&secured, &verification_options).is_err());
+    }
+
+    let mut options = ProofOptions::new(
+        format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT).as_str(),
+        "authentication",
+    );
+    options.expires = Some(String::from("tomorrow"));
+    match create_proof(&credential(), &options, &key_pair) {
+        Ok(_) => panic!("'expires' must be a date time stamp"),
+        Err(failure) => assert_eq!(failure.error, ProofError::InvalidProof),
+    };
+}
+
+#[test]
+pub fn test_eddsa_rdfc_2022_w3c_vector() {
+    let mut options = ProofOptions::new(
+        format!(
+            "did:key:{}#{}",
+            W3C_PUBLIC_KEY_MULTIBASE, W3C_PUBLIC_KEY_MULTIBASE
+        )
+        .as_str(),
+        "assertionMethod",
+    );
+    options.created = Some(String::from("2023-02-24T23:36:38Z"));
+    options.cryptosuite = Cryptosuite::EddsaRdfc2022;
+
+    let secured = match create_proof(&w3c_credential(), &options, &w3c_key_pair()) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        secured["proof"],
+        json!({
+            "type": "DataIntegrityProof",
+            "cryptosuite": "eddsa-rdfc-2022",
+            "created": "2023-02-24T23:36:38Z",
+            "verificationMethod": format!("did:key:{}#{}", W3C_PUBLIC_KEY_MULTIBASE, W3C_PUBLIC_KEY_MULTIBASE),
+            "proofPurpose": "assertionMethod",
+            "proofValue": "z2YwC8z3ap7yx1nZYCg4L3j3ApHsF8kgPdSb5xoS1VR7vPG3F561B52hYnQF9iseabecm3ijx4K1FBTQsCZahKZme"
+        })
+    );
+
+    match verify_proof(&secured) {
+        Ok(_) => {}
+        Err(error) => panic!("{}", error),
+    };
+
+    // the canonical form does not depend on the order of the members
+    let mut reordered = secured.clone();
+    reordered["type"] = json!(["VerifiableCredential", "AlumniCredential"]);
+    reordered["credentialSubject"] = json!({
+        "alumniOf": "The School of Examples",
+        "id": "did:example:abcdefgh"
+    });
+    match verify_proof(&reordered) {
+        Ok(_) => {}
+        Err(error) => panic!("{}", error),
+    };
+
+    let mut changed = secured.clone();
+    changed["credentialSubject"]["alumniOf"] = Value::from("The School of Mallory");
+    match verify_proof(&changed) {
+        Ok(_) => panic!("Changed document must not verify"),
+        Err(failure) => assert_eq!(failure.error, ProofError::InvalidSignature),
+    };
+}
+
+#[test]
+pub fn test_ed25519_signature_2020_vector() {
+    // the credential and key published with the test vectors of the ssi
+    // crate, lds-ed25519-2020-vc0.jsonld and lds-ed25519-2020-issuer0.jsonld
+    let secured = json!({
+        "@context": [
+            "https://www.w3.org/2018/credentials/v1",
+            "https://www.w3.org/2018/credentials/examples/v1",
+            "https://w3id.org/security/suites/ed25519-2020/v1"
+        ],
+        "id": "http://example.gov/credentials/3732",
+        "type": ["VerifiableCredential", "UniversityDegreeCredential"],
+        "issuer": "https://example.com/issuer/123",
+        "issuanceDate": "2020-03-10T04:24:12.164Z",
+        "credentialSubject": {
+            "id": "did:example:456",
+            "degree": {
+                "type": "BachelorDegree",
+                "name": "Bachelor of Science and Arts"
+            }
+        },
+        "proof": {
+            "type": "Ed25519Signature2020",
+            "created": "2019-12-11T03:50:55Z",
+            "proofValue": "z5SpZtDGGz5a89PJbQT2sgbRUiyyAGhhgjcf86aJHfYcfvPjxn6vej5na6kUzmw1jMAR9PJU9mowshQFFdGmDN14D",
+            "proofPurpose": "assertionMethod",
+            "verificationMethod": "https://example.com/issuer/123#key-0"
+        }
+    });
+    let public_key =
+        match bs58::decode("6Mkf5rGMoatrSj1f4CyvuHBeXJELe9RPdzo2PKGNCKVtZxP").into_vec() {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+
+    // only DID verification methods are resolved, so the signature is checked
+    // against the hash data of the suite
+    match verify_proof(&secured) {
+        Ok(_) => panic!("An https verification method cannot be resolved"),
+        Err(failure) => assert_eq!(failure.error, ProofError::InvalidVerificationMethod),
+    };
+
+    let mut unsecured = secured.clone();
+    let mut proof_config = match unsecured
+        .as_object_mut()
+        .and_then(|val| val.remove("proof"))
+    {
+        Some(val) => val,
+        None => panic!("The vector has a proof"),
+    };
+    let proof_value = match proof_config
+        .as_object_mut()
+        .and_then(|val| val.remove("proofValue"))
+    {
+        Some(Value::String(val)) => val,
+        _ => panic!("The vector has a proof value"),
+    };
+    proof_config["@context"] = secured["@context"].clone();
+
+    let hash_data = |unsecured: &Value| {
+        let mut hash_data = Vec::new();
+        for document in [&proof_config, unsecured] {
+            match canonicalize(document) {
+                Ok(val) => hash_data.extend_from_slice(&Sha256::digest(val)),
+                Err(error) => panic!("{}", error),
+            };
+        }
+        hash_data
+    };
+    let signature = match bs58::decode(&proof_value[1..]).into_vec() {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    match signature::verify(&public_key[2..], &hash_data(&unsecured), &signature) {
+        Ok(_) => {}
+        Err(error) => panic!("{}", error),
+    };
+
+    let mut changed = unsecured.clone();
+    changed["credentialSubject"]["degree"]["name"] = Value::from("Doctor of Philosophy");
+    assert!(signature::verify(&public_key[2..], &hash_data(&changed), &signature).is_err());
+}
+
+#[test]
+pub fn test_ed25519_signature_2020_proof_needs_suite_context() {
+    let mut options = ProofOptions::new(
+        format!(
+            "did:key:{}#{}",
+            W3C_PUBLIC_KEY_MULTIBASE, W3C_PUBLIC_KEY_MULTIBASE
+        )
+        .as_str(),
+        "assertionMethod",
+    );
+    options.created = Some(String::from("2023-02-24T23:36:38Z"));
+    options.cryptosuite = Cryptosuite::Ed25519Signature2020;
+
+    // the credentials v2 vocabulary would otherwise define the proof type
+    match create_proof(&w3c_credential(), &options, &w3c_key_pair()) {
+        Ok(_) => panic!("The Ed25519 2020 suite context is required"),
+        Err(failure) => assert_eq!(failure.error, ProofError::InvalidDocument),
+    };
+
+    let mut document = w3c_credential();
+    document["@context"] = json!([
+        "https://www.w3.org/ns/credentials/v2",
+        "https://www.w3.org/ns/credentials/examples/v2",
+        "https://w3id.org/security/suites/ed25519-2020/v1"
+    ]);
+    let secured = match create_proof(&document, &options, &w3c_key_pair()) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    match verify_proof(&secured) {
+        Ok(_) => {}
+        Err(error) => panic!("{}", error),
+    };
+
+    let mut without_context = secured.clone();
+    without_context["@context"] = w3c_credential()["@context"].clone();
+    match verify_proof(&without_context) {
+        Ok(_) => panic!("The Ed25519 2020 suite context is required"),
+        Err(failure) => assert_eq!(failure.error, ProofError::InvalidDocument),
+    };
+}
+
+#[test]
+pub fn test_create_eddsa_rdfc_2022_proof_needs_bundled_contexts() {
+    let mut options = ProofOptions::new(
+        format!(
+            "did:key:{}#{}",
+            W3C_PUBLIC_KEY_MULTIBASE, W3C_PUBLIC_KEY_MULTIBASE
+        )
+        .as_str(),
+        "assertionMethod",
+    );
+    options.cryptosuite = Cryptosuite::EddsaRdfc2022;
+
+    let mut without_context = w3c_credential();
+    if let Some(document) = without_context.as_object_mut() {
+        document.remove("@context");
+    }
+    let mut unknown_context = w3c_credential();
+    unknown_context["@context"] = json!([
+        "https://www.w3.org/ns/credentials/v2",
+        "https://vc.example/contexts/v1"
+    ]);
+
+    for document in [without_context, unknown_context] {
+        match create_proof(&document, &options, &w3c_key_pair()) {
+            Ok(_) => panic!("{} cannot be canonicalized", document["@context"]),
+            Err(failure) => assert_eq!(failure.error, ProofError::InvalidDocument),
+        };
+    }
+}
+
+#[test]
+pub fn test_verify_eddsa_rdfc_2022_proof_rejects_undefined_terms() {
+    let mut options = ProofOptions::new(
+        format!(
+            "did:key:{}#{}",
+            W3C_PUBLIC_KEY_MULTIBASE, W3C_PUBLIC_KEY_MULTIBASE
+        )
+        .as_str(),
+        "assertionMethod",
+    );
+    options.created = Some(String::from("2024-01-01T00:00:00Z"));
+    options.cryptosuite = Cryptosuite::EddsaRdfc2022;
+
+    // the credentials v1 context has no vocabulary for undefined terms, and
+    // does not define the terms of the proof either
+    let document = json!({
+        "@context": [
+            "https://www.w3.org/2018/credentials/v1",
+            "https://w3id.org/security/data-integrity/v2"
+        ],
+        "id": "urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33",
+        "type": ["VerifiableCredential"],
+        "issuer": format!("did:key:{}", W3C_PUBLIC_KEY_MULTIBASE),
+        "issuanceDate": "2024-01-01T00:00:00Z",
+        "credentialSubject": {"id": "did:example:alice"}
+    });
+    let secured = match create_proof(&document, &options, &w3c_key_pair()) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    match verify_proof(&secured) {
+        Ok(_) => {}
+        Err(error) => panic!("{}", error),
+    };
+
+    let mut tampered = secured.clone();
+    tampered["credentialSubject"]["isAdmin"] = Value::from(true);
+    tampered["credentialSubject"]["role"] = Value::from("root");
+    match verify_proof(&tampered) {
+        Ok(_) => panic!("Undefined terms must not be dropped from the signed data"),
+        Err(failure) => assert_eq!(failure.error, ProofError::InvalidDocument),
+    };
+
+    let mut undefined = document.clone();
+    undefined["credentialSubject"]["isAdmin"] = Value::from(true);
+    let mut without_proof_context = document.clone();
+    without_proof_context["@context"] = json!(["https://www.w3.org/2018/credentials/v1"]);
+    for document in [undefined, without_proof_context] {
+        match create_proof(&document, &options, &w3c_key_pair()) {
+            Ok(_) => panic!("Undefined terms must not be dropped from the signed data"),
+            Err(failure) => assert_eq!(failure.error, ProofError::InvalidDocument),
+        };
+    }
+}
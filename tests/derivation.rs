@@ -0,0 +1,146 @@
+use fi_common::keys::{AgreementKey, VerificationKey};
+use fi_key_resolver::derivation::{
+    derive_from_mnemonic, derive_from_seed, derive_from_slip10_seed, derive_slip10_private_key,
+};
+
+mod common;
+use common::decode_hex;
+
+// SLIP-0010 Ed25519 test vector 1
+const SLIP10_SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+#[test]
+pub fn test_slip10_test_vector() {
+    let seed = decode_hex(SLIP10_SEED);
+    let vector = [
+        (
+            "m",
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+        ),
+        (
+            "m/0'",
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+        ),
+        (
+            "m/0'/1'",
+            "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+        ),
+        (
+            "m/0H/1H/2H",
+            "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+        ),
+        (
+            "m/0'/1'/2'/2'",
+            "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+        ),
+        (
+            "m/0'/1'/2'/2'/1000000000'",
+            "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+        ),
+    ];
+
+    for (path, private_key) in vector {
+        let derived = match derive_slip10_private_key(&seed, path) {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+        assert_eq!(derived.to_vec(), decode_hex(private_key), "{}", path);
+    }
+
+    // public keys a4b2856b...88ed and 3c24da04...4b7a of the test vector
+    let key_pair = match derive_from_slip10_seed(&seed, "m") {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        key_pair.get_verification_key().get_public_key_content(),
+        "z6MkqYAnwjMV8HXVoZs4RXrdQd1rgRPiKhTVtU89G4WZ8eKn"
+    );
+
+    let key_pair = match derive_from_slip10_seed(&seed, "m/0'/1'/2'/2'/1000000000'") {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    let exported = key_pair.get_verification_key().export(true, true, false);
+    assert_eq!(
+        exported.id.as_deref(),
+        Some("did:key:z6MkiW36hgJrnhJU4aAZVr4kPmBmzK8JbC74prUE2CPgrDy3#z6MkiW36hgJrnhJU4aAZVr4kPmBmzK8JbC74prUE2CPgrDy3")
+    );
+    assert_eq!(
+        exported.private_key_multibase.as_deref(),
+        Some("zrv3WPtJ2vmVUUyzMeGfRbegmp2bZHKtfUGh4fmuK2cxsnkCt3e7gaCCF6sFEaQivXmzDzan6RCeA5K2bioWdbsgGLR")
+    );
+}
+
+#[test]
+pub fn test_derive_from_seed_and_mnemonic() {
+    let seed: [u8; 32] =
+        decode_hex("2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7")
+            .try_into()
+            .unwrap();
+
+    let key_pair = match derive_from_seed(&seed) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        key_pair.get_verification_key().get_controller().as_deref(),
+        Some("did:key:z6MkqYAnwjMV8HXVoZs4RXrdQd1rgRPiKhTVtU89G4WZ8eKn")
+    );
+
+    let agreement_key = key_pair.get_agreement_key().export(true, true, false);
+    assert_eq!(
+        agreement_key.id.as_deref(),
+        Some("did:key:z6MkqYAnwjMV8HXVoZs4RXrdQd1rgRPiKhTVtU89G4WZ8eKn#z6LSpZ4ts4X37tVcibkPuBsZrP7WLnPQ6uQwWXPk3ez7T4GB")
+    );
+    assert_eq!(
+        agreement_key.private_key_multibase.as_deref(),
+        Some("z3wei8aC3Bs4jZA5zi3dQr6QJxRuLH1iBJvxiMRK11Y3qtmk")
+    );
+
+    // BIP-39 test vector with the passphrase "TREZOR"
+    let key_pair = match derive_from_mnemonic(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        "TREZOR",
+        "m/0'",
+    ) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        key_pair.get_verification_key().get_public_key_content(),
+        "z6MkkcTTSPfLk5Ary3xcS3pNxX6roAZczJfUAYiBpk61TcN5"
+    );
+}
+
+#[test]
+pub fn test_invalid_derivation() {
+    let seed = decode_hex(SLIP10_SEED);
+
+    // Ed25519 has no normal derivation
+    assert!(derive_slip10_private_key(&seed, "m/0'/1").is_err());
+    assert!(derive_slip10_private_key(&seed, "0'").is_err());
+    assert!(derive_slip10_private_key(&seed, "m/2147483648'").is_err());
+    assert!(derive_slip10_private_key(&seed[..8], "m").is_err());
+    assert!(derive_slip10_private_key(&[0u8; 65], "m").is_err());
+
+    // malformed paths
+    for path in [
+        "", "m/", "m/'", "m//0'", "m/0'/", "m/-1'", "m/+1'", "m/0''", "m/é'", "M/0'",
+    ] {
+        assert!(
+            derive_slip10_private_key(&seed, path).is_err(),
+            "path '{}' must be rejected",
+            path
+        );
+    }
+
+    // bad checksum
+    assert!(derive_from_mnemonic(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon",
+        "",
+        "m/0'",
+    )
+    .is_err());
+    assert!(derive_from_mnemonic("", "", "m/0'").is_err());
+}
@@ -1,3 +1,9 @@
+#![allow(
+    clippy::assertions_on_constants,
+    clippy::to_string_in_format_args,
+    clippy::unnecessary_unwrap
+)]
+
 use fi_key_resolver::resolve_did;
 
 #[test]
@@ -77,3 +83,34 @@ pub fn test_did_key_2() {
         );
     }
 }
+
+#[test]
+pub fn test_key_agreement_id() {
+    let did = "did:key:z6MkpVCWpibzht7gFFkBsnNigRvXiQWQgV2vqq8eN8zGkGGN";
+    let key_agreement_id = format!("{}#z6LSr95e5osYAGxuc83dB82EJRVPLFFcoSqZzmVKiqiV8krL", did);
+    let suite = "Ed25519VerificationKey2018";
+
+    let (did_doc_option, _) = match resolve_did(did, suite) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let key_pair = &did_doc_option
+        .expect("DID document not found")
+        .key_agreement
+        .unwrap()[0];
+    assert_eq!(key_pair.id.as_ref(), Some(&key_agreement_id));
+    assert_eq!(key_pair.controller.as_deref(), Some(did));
+
+    let (_, key_pair_option) = match resolve_did(&key_agreement_id, suite) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let key_pair = key_pair_option.expect("Key pair not found");
+    assert_eq!(key_pair._type, "X25519KeyAgreementKey2019");
+    assert_eq!(
+        key_pair.public_key_base58.as_deref(),
+        Some("FTuUZW4g4pFAWjfreUWGyqGuV6iW6qfR7nmeEP4xRP5a")
+    );
+}
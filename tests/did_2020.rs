@@ -1,3 +1,9 @@
+#![allow(
+    clippy::assertions_on_constants,
+    clippy::to_string_in_format_args,
+    clippy::unnecessary_unwrap
+)]
+
 use fi_key_resolver::resolve_did;
 
 #[test]
@@ -77,3 +83,32 @@ pub fn test_did_key_2() {
         );
     }
 }
+
+#[test]
+pub fn test_key_agreement_id() {
+    let did = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+    let key_agreement_id = format!("{}#z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc", did);
+    let suite = "Ed25519VerificationKey2020";
+
+    let (did_doc_option, _) = match resolve_did(did, suite) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let key_pair = &did_doc_option
+        .expect("DID document not found")
+        .key_agreement
+        .unwrap()[0];
+    assert_eq!(key_pair.id.as_ref(), Some(&key_agreement_id));
+    assert_eq!(key_pair.controller.as_deref(), Some(did));
+
+    let (_, key_pair_option) = match resolve_did(&key_agreement_id, suite) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    assert_eq!(
+        key_pair_option.expect("Key pair not found")._type,
+        "X25519KeyAgreementKey2020"
+    );
+}
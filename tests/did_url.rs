@@ -0,0 +1,95 @@
+use fi_key_resolver::dereferencing::ContentStream;
+use fi_key_resolver::did_url::DidUrl;
+use fi_key_resolver::resolution::{ResolutionError, ResolutionOptions};
+use fi_key_resolver::{dereference, resolve_did_with_format};
+
+#[test]
+pub fn test_parse_did_url() {
+    let did_url = match DidUrl::parse(
+        "did:example:123:456/path/to%20file?service=files&relativeRef=%2Fdocs&versionTime=2021-01-01T00:00:00Z#key%2D1",
+    ) {
+        Ok(val) => val,
+        Err(failure) => panic!("{}", failure),
+    };
+
+    assert_eq!(did_url.did(), "did:example:123:456");
+    assert_eq!(did_url.method(), "example");
+    assert_eq!(did_url.method_specific_id(), "123:456");
+    assert_eq!(did_url.path(), Some("/path/to%20file"));
+    assert_eq!(did_url.service(), Some("files"));
+    assert_eq!(did_url.relative_ref(), Some("/docs"));
+    assert_eq!(did_url.version_time(), Some("2021-01-01T00:00:00Z"));
+    assert_eq!(did_url.version_id(), None);
+    assert_eq!(did_url.fragment(), Some("key%2D1"));
+    assert_eq!(did_url.decoded_fragment().as_deref(), Some("key-1"));
+    assert!(!did_url.is_did());
+}
+
+#[test]
+pub fn test_parse_invalid_did_url() {
+    let invalid = [
+        "did:key",
+        "did::z6Mk",
+        "did:KEY:z6Mk",
+        "did:key:z6Mk:",
+        "did:key:z6Mk%2",
+        "did:key:z6Mk#frag ment",
+        "https://example.com",
+    ];
+
+    for did_url in invalid {
+        assert!(DidUrl::parse(did_url).is_err(), "{}", did_url);
+    }
+}
+
+#[test]
+pub fn test_dereference_verification_method() {
+    let did = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+    let did_url = format!("{}#z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH", did);
+
+    let result = dereference(&did_url, &ResolutionOptions::default());
+
+    match result.content_stream {
+        Some(ContentStream::VerificationMethod(key_pair)) => {
+            assert_eq!(key_pair.id, Some(did_url.clone()));
+            assert_eq!(key_pair.controller.as_deref(), Some(did));
+        }
+        _ => panic!("Expected a verification method"),
+    }
+
+    let (did_doc, key_pair) = match resolve_did_with_format(&did_url, None) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    assert!(did_doc.is_none());
+    assert_eq!(key_pair.expect("Key pair not found").id, Some(did_url));
+}
+
+#[test]
+pub fn test_dereference_not_found() {
+    let did = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+
+    for did_url in [
+        format!("{}#unknown", did),
+        format!("{}?service=files", did),
+        format!("{}?versionId=1", did),
+        format!("{}/path", did),
+    ] {
+        let result = dereference(&did_url, &ResolutionOptions::default());
+
+        assert_eq!(
+            result.dereferencing_metadata.error,
+            Some(ResolutionError::NotFound),
+            "{}",
+            did_url
+        );
+        assert!(resolve_did_with_format(&did_url, None).is_err());
+    }
+
+    let result = dereference("did:key:z6Mk#frag ment", &ResolutionOptions::default());
+    assert_eq!(
+        result.dereferencing_metadata.error,
+        Some(ResolutionError::InvalidDidUrl)
+    );
+}
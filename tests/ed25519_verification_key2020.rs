@@ -0,0 +1,111 @@
+use fi_common::keys::{AgreementKey, VerificationKey};
+use fi_key_resolver::ed25519_verification_key2020::Ed25519VerificationKey2020;
+use fi_key_resolver::x25519_key_agreement_key2020::X25519KeyAgreementKey2020;
+
+// RFC 8032 test 1 key pair
+const PUBLIC_KEY_MULTIBASE: &str = "z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw";
+const SEED_MULTIBASE: &str = "z3u2bpACJXYj89Vh7HqHn8oVv2A2niEy9FcQUzzuQTYJ61AX";
+const SEED_AND_PUBLIC_KEY_MULTIBASE: &str =
+    "zrv3nQ3vxUrShebtbJeB42niZe1oGRnFzGPusycqLLtiJEeSFbDjwS6rvt6uMYYkjGuZMTsqb6mzCgG19WbjcNNsvxq";
+
+#[test]
+pub fn test_ed25519_2020_private_key_export() {
+    for private_key_multibase in [SEED_MULTIBASE, SEED_AND_PUBLIC_KEY_MULTIBASE] {
+        let key_pair = match Ed25519VerificationKey2020::new(
+            Some(format!("did:key:{}", PUBLIC_KEY_MULTIBASE)),
+            String::from(PUBLIC_KEY_MULTIBASE),
+            Some(String::from(private_key_multibase)),
+            Some(String::from(PUBLIC_KEY_MULTIBASE)),
+        ) {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+
+        let public_key = key_pair.export(true, false, false);
+        assert!(public_key.private_key_multibase.is_none());
+
+        let private_key = key_pair.export(true, true, false);
+        assert_eq!(
+            private_key.public_key_multibase.as_deref(),
+            Some(PUBLIC_KEY_MULTIBASE)
+        );
+        assert_eq!(
+            private_key.private_key_multibase.as_deref(),
+            Some(private_key_multibase)
+        );
+    }
+}
+
+#[test]
+pub fn test_ed25519_2020_private_key_to_x25519() {
+    let key_pair = match Ed25519VerificationKey2020::new(
+        Some(format!("did:key:{}", PUBLIC_KEY_MULTIBASE)),
+        String::from(PUBLIC_KEY_MULTIBASE),
+        Some(String::from(SEED_AND_PUBLIC_KEY_MULTIBASE)),
+        Some(String::from(PUBLIC_KEY_MULTIBASE)),
+    ) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let agreement_key =
+        match X25519KeyAgreementKey2020::from_ed25519_verification_key2020(&key_pair) {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+
+    let exported = agreement_key.export(true, true, false);
+    assert_eq!(
+        exported.public_key_multibase.as_deref(),
+        Some("z6LSrEnPXPcLyNLKJPhdJ1eWqyYKARWket5BbiN1rjdUsQ9b")
+    );
+    assert_eq!(
+        exported.private_key_multibase.as_deref(),
+        Some("z3wea3FEiVjYREYVX7t99g4uEm4r4jPsTuhm4GMv5cS838rS")
+    );
+}
+
+#[test]
+pub fn test_ed25519_2020_invalid_private_key() {
+    let invalid_private_keys = [
+        // the seed encoded with the ed25519-pub header
+        "z6Mkq3cTLeo1DNGE6PUc95tN5zA4avkH9ku6zEqpoJUuEsDy",
+        // a seed of another key pair
+        "z3u2RHj5hRUAqd2AGVStkvCty1KjGWyk5SeUkBjfVp51Wh3e",
+        // malformed multibase values
+        "",
+        "z",
+        "z1",
+        "z0OIl",
+    ];
+
+    for private_key_multibase in invalid_private_keys {
+        assert!(Ed25519VerificationKey2020::new(
+            None,
+            String::from(PUBLIC_KEY_MULTIBASE),
+            Some(String::from(private_key_multibase)),
+            None,
+        )
+        .is_err());
+    }
+}
+
+#[test]
+pub fn test_ed25519_2020_malformed_public_key() {
+    for public_key_multibase in [
+        "",
+        "z",
+        "z1",
+        "z0OIl",
+        "6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw",
+    ] {
+        assert!(Ed25519VerificationKey2020::new(
+            None,
+            String::from(public_key_multibase),
+            None,
+            None,
+        )
+        .is_err());
+        assert!(Ed25519VerificationKey2020::from_fingerprint(public_key_multibase).is_err());
+    }
+}
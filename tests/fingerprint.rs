@@ -0,0 +1,103 @@
+use fi_common::keys::VerificationKey;
+use fi_key_resolver::bls12381_g1_key2020::Bls12381G1Key2020;
+use fi_key_resolver::ed25519_verification_key2018::Ed25519VerificationKey2018;
+use fi_key_resolver::ed25519_verification_key2020::Ed25519VerificationKey2020;
+use fi_key_resolver::fingerprint::{FingerprintError, KeyFingerprint};
+use fi_key_resolver::json_web_key2020::JsonWebKey2020;
+use fi_key_resolver::multikey::Multikey;
+use fi_key_resolver::x25519_key_agreement_key2019::X25519KeyAgreementKey2019;
+
+#[test]
+pub fn test_key_fingerprint() {
+    // RFC 8032 test 1 public key
+    let key_pair = Ed25519VerificationKey2018::new(
+        None,
+        String::from("FVen3X669xLzsi6N2V91DoiyzHzg1uAgqiT8jZ9nS96Z"),
+        None,
+        None,
+    );
+    assert_eq!(
+        key_pair.fingerprint().ok().as_deref(),
+        Some("z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw")
+    );
+    assert_eq!(
+        key_pair.did_key().ok().as_deref(),
+        Some("did:key:z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw")
+    );
+
+    let key_pair = X25519KeyAgreementKey2019::new(
+        None,
+        String::from("JhNWeSVLMYccCk7iopQW4guaSJTojqpMEELgSLhKwRr"),
+        None,
+        None,
+    );
+    assert_eq!(
+        key_pair.fingerprint().ok().as_deref(),
+        Some("z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc")
+    );
+
+    let key_pair = Bls12381G1Key2020::new(
+        None,
+        String::from("6aGxBiDyBZxEL9RKJz8W2h7VQUUKv9BPqHbC2DNWKExVRM7GWtLk6mTQBYXbpgRHwp"),
+        None,
+        None,
+    );
+    assert_eq!(
+        key_pair.did_key().ok().as_deref(),
+        Some("did:key:z3tEFUdV4D3tCMG6Fr1deVvt32DCS1Y4SxDGoELedXaMUdTdr5FfZvBnbK9bWMhAGj3RHk")
+    );
+}
+
+#[test]
+pub fn test_multibase_key_fingerprint() {
+    let fingerprint = "zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169";
+
+    let multikey = match Multikey::from_fingerprint(fingerprint) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(multikey.fingerprint().ok().as_deref(), Some(fingerprint));
+
+    let key_pair = match JsonWebKey2020::from_multikey(&multikey) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        key_pair.did_key().ok(),
+        Some(format!("did:key:{}", fingerprint))
+    );
+    assert!(key_pair.verify_fingerprint(fingerprint).is_ok());
+}
+
+#[test]
+pub fn test_verify_fingerprint() {
+    let fingerprint = "z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw";
+    let key_pair = match Ed25519VerificationKey2020::from_fingerprint(fingerprint) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    assert!(key_pair.verify_fingerprint(fingerprint).is_ok());
+
+    let failures = [
+        (&fingerprint[1..], FingerprintError::InvalidMultibasePrefix),
+        ("z0OIl", FingerprintError::InvalidMultibasePrefix),
+        // the X25519 key of the x25519 tests
+        (
+            "z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc",
+            FingerprintError::InvalidMulticodecHeader,
+        ),
+        // another Ed25519 key
+        (
+            "z6MkqYAnwjMV8HXVoZs4RXrdQd1rgRPiKhTVtU89G4WZ8eKn",
+            FingerprintError::InvalidKeyBytes,
+        ),
+    ];
+
+    for (presented, error) in failures {
+        match key_pair.verify_fingerprint(presented) {
+            Ok(_) => panic!("Fingerprint {} must not match", presented),
+            Err(failure) => assert_eq!(failure.error, error),
+        }
+    }
+}
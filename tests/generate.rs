@@ -0,0 +1,139 @@
+use fi_common::keys::{AgreementKey, VerificationKey};
+use fi_key_resolver::bls12381_g2_key2020::Bls12381G2Key2020;
+use fi_key_resolver::did::DidDoc;
+use fi_key_resolver::ecdsa_secp256k1_verification_key2019::EcdsaSecp256k1VerificationKey2019;
+use fi_key_resolver::ed25519_verification_key2018::Ed25519VerificationKey2018;
+use fi_key_resolver::ed25519_verification_key2020::Ed25519VerificationKey2020;
+use fi_key_resolver::resolution::ResolutionOptions;
+use fi_key_resolver::x25519_key_agreement_key2020::X25519KeyAgreementKey2020;
+use fi_key_resolver::{resolve_did, resolve_did_with_format};
+use rand_core::OsRng;
+
+#[test]
+pub fn test_generate_ed25519() {
+    let key_pair = match Ed25519VerificationKey2020::generate() {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let exported = key_pair.export(true, true, false);
+    let did = key_pair.get_controller().clone().unwrap();
+    assert_eq!(
+        exported.id,
+        Some(format!(
+            "{}#{}",
+            did,
+            exported.public_key_multibase.unwrap()
+        ))
+    );
+
+    let did_doc =
+        match DidDoc::key_pair_to_did_doc_from_controller(&key_pair, &ResolutionOptions::default())
+        {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+    assert!(did_doc.key_agreement.is_some());
+
+    let resolved = match resolve_did_with_format(&did, None) {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        serde_json::to_value(&did_doc).unwrap(),
+        serde_json::to_value(&resolved).unwrap()
+    );
+
+    let key_pair = Ed25519VerificationKey2018::generate_with_rng(&mut OsRng);
+    let did = key_pair.get_controller().clone().unwrap();
+    assert!(key_pair
+        .export(true, true, false)
+        .private_key_base58
+        .is_some());
+
+    let did_doc =
+        match DidDoc::key_pair_to_did_doc_from_controller(&key_pair, &ResolutionOptions::default())
+        {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+    let resolved = match resolve_did(&did, "Ed25519VerificationKey2018") {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        serde_json::to_value(&did_doc).unwrap(),
+        serde_json::to_value(&resolved).unwrap()
+    );
+}
+
+#[test]
+pub fn test_generate_x25519() {
+    let key_pair = X25519KeyAgreementKey2020::generate();
+    let did = key_pair.get_controller().clone().unwrap();
+
+    let did_doc = match DidDoc::agreement_key_to_did_doc_from_controller(
+        &key_pair,
+        &ResolutionOptions::default(),
+    ) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert!(did_doc.verification_method.is_none());
+
+    let resolved = match resolve_did_with_format(&did, None) {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        serde_json::to_value(&did_doc).unwrap(),
+        serde_json::to_value(&resolved).unwrap()
+    );
+}
+
+#[test]
+pub fn test_generate_secp256k1_and_bls12381() {
+    let key_pair = match EcdsaSecp256k1VerificationKey2019::generate_with_rng(&mut OsRng) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert!(key_pair.get_private_key_jwk().is_some());
+
+    let did = key_pair.get_controller().clone().unwrap();
+    let did_doc =
+        match DidDoc::key_pair_to_did_doc_from_controller(&key_pair, &ResolutionOptions::default())
+        {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+    let resolved = match resolve_did_with_format(&did, None) {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        serde_json::to_value(&did_doc).unwrap(),
+        serde_json::to_value(&resolved).unwrap()
+    );
+
+    let key_pair = Bls12381G2Key2020::generate();
+    let did = key_pair.get_controller().clone().unwrap();
+    let did_doc =
+        match DidDoc::key_pair_to_did_doc_from_controller(&key_pair, &ResolutionOptions::default())
+        {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+    let resolved = match resolve_did_with_format(&did, None) {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        serde_json::to_value(&did_doc).unwrap(),
+        serde_json::to_value(&resolved).unwrap()
+    );
+}
@@ -0,0 +1,185 @@
+use fi_key_resolver::json_ld::{canonicalize, load_context};
+use fi_key_resolver::resolution::ResolutionOptions;
+use fi_key_resolver::resolve;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+mod common;
+use common::decode_hex;
+
+#[test]
+pub fn test_load_context() {
+    for url in [
+        "https://www.w3.org/ns/did/v1",
+        "https://www.w3.org/ns/credentials/v2",
+        "https://w3id.org/security/multikey/v1",
+        "https://w3id.org/security/suites/ed25519-2020/v1",
+        "https://w3id.org/security/suites/jws-2020/v1",
+        "https://w3id.org/security/suites/ed25519-2018/v1",
+        "https://w3id.org/security/suites/x25519-2019/v1",
+        "https://w3id.org/security/suites/x25519-2020/v1",
+        "https://w3id.org/security/suites/secp256k1-2019/v1",
+        "https://w3id.org/security/suites/bls12381-2020/v1",
+        "https://www.w3.org/2018/credentials/examples/v1",
+    ] {
+        match load_context(url) {
+            Ok(val) => assert!(serde_json::from_str::<serde_json::Value>(val).is_ok()),
+            Err(error) => panic!("{}", error),
+        };
+
+        // contexts referenced by a bundled context are bundled too
+        let document = json!({"@context": url, "@id": "did:example:alice"});
+        if let Err(error) = canonicalize(&document) {
+            panic!("{}: {}", url, error);
+        }
+    }
+
+    // contexts are never fetched
+    assert!(load_context("https://vc.example/contexts/v1").is_err());
+}
+
+#[test]
+pub fn test_canonicalize() {
+    let canonical = match canonicalize(&json!({
+        "@context": {"name": "http://schema.org/name"},
+        "@id": "did:example:alice",
+        "name": "Alice"
+    })) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        canonical,
+        "<did:example:alice> <http://schema.org/name> \"Alice\" .\n"
+    );
+
+    // terms the contexts do not define, relative IRIs and null contexts are
+    // not dropped silently
+    for document in [
+        json!({
+            "@context": {"name": "http://schema.org/name"},
+            "@id": "did:example:alice",
+            "name": "Alice",
+            "undefined": "dropped"
+        }),
+        json!({
+            "@context": {"name": "http://schema.org/name"},
+            "@id": "did:example:alice",
+            "@type": "Undefined"
+        }),
+        json!({
+            "@context": {"name": "http://schema.org/name"},
+            "@id": "alice",
+            "name": "Alice"
+        }),
+        json!({
+            "@id": "did:example:alice",
+            "http://schema.org/knows": {
+                "@context": null,
+                "http://schema.org/name": "Bob",
+                "undefined": "dropped"
+            }
+        }),
+        json!({
+            "@context": [{"name": "http://schema.org/name"}, {"@vocab": null}],
+            "@id": "did:example:alice",
+            "undefined": "dropped"
+        }),
+    ] {
+        assert!(canonicalize(&document).is_err(), "{}", document);
+    }
+
+    // the credential of the W3C vc-di-eddsa test vectors
+    let canonical = match canonicalize(&json!({
+        "@context": [
+            "https://www.w3.org/ns/credentials/v2",
+            "https://www.w3.org/ns/credentials/examples/v2"
+        ],
+        "id": "urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33",
+        "type": ["VerifiableCredential", "AlumniCredential"],
+        "name": "Alumni Credential",
+        "description": "A minimum viable example of an Alumni Credential.",
+        "issuer": "https://vc.example/issuers/5678",
+        "validFrom": "2023-01-01T00:00:00Z",
+        "credentialSubject": {
+            "id": "did:example:abcdefgh",
+            "alumniOf": "The School of Examples"
+        }
+    })) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        Sha256::digest(canonical).to_vec(),
+        decode_hex("517744132ae165a5349155bef0bb0cf2258fff99dfe1dbd914b938d775a36017")
+    );
+
+    for document in [
+        json!({"@context": "https://vc.example/contexts/v1", "name": "Alice"}),
+        json!({"@context": 5}),
+    ] {
+        assert!(canonicalize(&document).is_err());
+    }
+}
+
+#[test]
+pub fn test_canonicalize_resolved_did_documents() {
+    let did = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+
+    for (suite, agreement_suite, public_key_property, public_key) in [
+        (
+            "Ed25519VerificationKey2018",
+            "X25519KeyAgreementKey2019",
+            "publicKeyBase58",
+            "\"B12NYF8RrR3h41TDCTJojY59usg3mbtbjnFs7Eud1Y6u\"",
+        ),
+        (
+            "Ed25519VerificationKey2020",
+            "X25519KeyAgreementKey2020",
+            "publicKeyMultibase",
+            "\"z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH\"^^<https://w3id.org/security#multibase>",
+        ),
+    ] {
+        let options = ResolutionOptions {
+            public_key_format: Some(String::from(suite)),
+            enable_experimental_public_key_types: true,
+            ..ResolutionOptions::default()
+        };
+        let result = resolve(did, &options);
+        assert!(!result.is_error());
+        let document = match serde_json::to_value(&result) {
+            Ok(val) => val["didDocument"].clone(),
+            Err(error) => panic!("{}", error),
+        };
+
+        let canonical = match canonicalize(&document) {
+            Ok(val) => val,
+            Err(error) => panic!("{}: {}", suite, error),
+        };
+        assert!(
+            canonical.contains(
+                format!(
+                    "<{}#z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH> <https://w3id.org/security#{}> {} .\n",
+                    did, public_key_property, public_key
+                )
+                .as_str()
+            ),
+            "{}",
+            canonical
+        );
+        assert!(canonical.contains(
+            format!(
+                "<{}#z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://w3id.org/security#{}> .\n",
+                did, suite
+            )
+            .as_str()
+        ));
+        assert!(canonical.contains(
+            format!(
+                "<http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://w3id.org/security#{}> .\n",
+                agreement_suite
+            )
+            .as_str()
+        ));
+    }
+}
@@ -0,0 +1,175 @@
+use fi_common::keys::{AgreementKey, VerificationKey};
+use fi_key_resolver::json_web_key2020::JsonWebKey2020;
+use fi_key_resolver::resolve_did;
+use fi_key_resolver::x25519_key_agreement_key2019::X25519KeyAgreementKey2019;
+use serde_json::{json, Value};
+
+#[test]
+pub fn test_resolve_json_web_key2020() {
+    let did = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+
+    let did_doc = match resolve_did(did, "JsonWebKey2020") {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+
+    assert_eq!(
+        did_doc.context,
+        Vec::from([
+            Value::from("https://www.w3.org/ns/did/v1"),
+            Value::from("https://w3id.org/security/suites/jws-2020/v1"),
+        ])
+    );
+
+    let verification_method = &did_doc.verification_method.unwrap()[0];
+    assert_eq!(verification_method._type, "JsonWebKey2020");
+    assert_eq!(
+        verification_method.id.as_deref(),
+        Some("did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH#z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH")
+    );
+    assert_eq!(
+        verification_method.public_key_jwk,
+        Some(json!({
+            "kty": "OKP",
+            "crv": "Ed25519",
+            "x": "lJZrfAjkBXdfjebMHEUI9usidAPhAlssitLXR3OYxbI"
+        }))
+    );
+    assert!(verification_method.public_key_multibase.is_none());
+
+    let key_agreement = &did_doc.key_agreement.unwrap()[0];
+    assert_eq!(key_agreement._type, "JsonWebKey2020");
+    assert_eq!(
+        key_agreement.id.as_deref(),
+        Some("did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH#z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc")
+    );
+    assert_eq!(
+        key_agreement.public_key_jwk,
+        Some(json!({
+            "kty": "OKP",
+            "crv": "X25519",
+            "x": "BIiFcQEn3dfvB2pjlhOQQour6jXy9d5s2FKEJNTOJik"
+        }))
+    );
+}
+
+#[test]
+pub fn test_json_web_key2020_private_key_export() {
+    // RFC 7748 section 6.1 key pair of Alice
+    let agreement_key = X25519KeyAgreementKey2019::new(
+        None,
+        String::from("9xgMXw7nrN39BoN9rJuGV6B9LwBNYXAJAMfeACcdyLMP"),
+        Some(String::from("91e5r98drPSsxzLHWEa83gKyGgpSRcQezLWUNX656vaM")),
+        None,
+    );
+
+    let key_pair = match JsonWebKey2020::from_x25519_key_agreement_key(&agreement_key) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    assert_eq!(
+        AgreementKey::get_public_key_content(&key_pair),
+        "z6LSkdrX4EvewpktHBjvNxRDogPdC5iVF8LT3LPKefGAgi89"
+    );
+
+    let public_key = AgreementKey::export(&key_pair, true, false, false);
+    assert_eq!(
+        public_key.public_key_jwk,
+        Some(json!({
+            "kty": "OKP",
+            "crv": "X25519",
+            "x": "hSDwCYkwp1R0i33ctD73Wg2_Og0mOBr066SpjqqbTmo"
+        }))
+    );
+    assert!(public_key.private_key_jwk.is_none());
+
+    let private_key = AgreementKey::export(&key_pair, true, true, true);
+    assert_eq!(
+        private_key.private_key_jwk,
+        Some(json!({
+            "kty": "OKP",
+            "crv": "X25519",
+            "x": "hSDwCYkwp1R0i33ctD73Wg2_Og0mOBr066SpjqqbTmo",
+            "d": "dwdtCnMYpX08FsFyUbJmRd9ML4frwJkqsXf7pR25LCo"
+        }))
+    );
+    assert_eq!(
+        private_key.context,
+        Some(Vec::from([String::from(
+            "https://w3id.org/security/suites/jws-2020/v1"
+        )]))
+    );
+
+    let imported = match JsonWebKey2020::new(
+        None,
+        public_key.public_key_jwk.unwrap(),
+        private_key.private_key_jwk,
+        None,
+    ) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        VerificationKey::get_private_key_content(&imported),
+        VerificationKey::get_private_key_content(&key_pair)
+    );
+}
+
+#[test]
+pub fn test_json_web_key2020_rejects_invalid_jwk() {
+    let public_key_jwk = json!({
+        "kty": "OKP",
+        "crv": "Ed25519",
+        "x": "lJZrfAjkBXdfjebMHEUI9usidAPhAlssitLXR3OYxbI"
+    });
+
+    let other_private_key_jwk = json!({
+        "kty": "OKP",
+        "crv": "X25519",
+        "x": "hSDwCYkwp1R0i33ctD73Wg2_Og0mOBr066SpjqqbTmo",
+        "d": "dwdtCnMYpX08FsFyUbJmRd9ML4frwJkqsXf7pR25LCo"
+    });
+    assert!(JsonWebKey2020::new(
+        None,
+        public_key_jwk.clone(),
+        Some(other_private_key_jwk.clone()),
+        None
+    )
+    .is_err());
+    assert!(JsonWebKey2020::new(None, other_private_key_jwk, None, None).is_err());
+
+    let short_key_jwk = json!({ "kty": "OKP", "crv": "Ed25519", "x": "lJZrfAjkBXdf" });
+    assert!(JsonWebKey2020::new(None, short_key_jwk, None, None).is_err());
+
+    let unknown_curve_jwk = json!({
+        "kty": "OKP",
+        "crv": "Ed448",
+        "x": "lJZrfAjkBXdfjebMHEUI9usidAPhAlssitLXR3OYxbI"
+    });
+    assert!(JsonWebKey2020::new(None, unknown_curve_jwk, None, None).is_err());
+
+    assert!(JsonWebKey2020::new(None, public_key_jwk, None, None).is_ok());
+}
+
+#[test]
+pub fn test_json_web_key2020_rejects_private_key_of_another_key() {
+    // RFC 7748 section 6.1 public key of Alice with the private key of Bob
+    let public_key_jwk = json!({
+        "kty": "OKP",
+        "crv": "X25519",
+        "x": "hSDwCYkwp1R0i33ctD73Wg2_Og0mOBr066SpjqqbTmo"
+    });
+    let private_key_jwk = json!({
+        "kty": "OKP",
+        "crv": "X25519",
+        "x": "hSDwCYkwp1R0i33ctD73Wg2_Og0mOBr066SpjqqbTmo",
+        "d": "XasIfmJKikt54X-Lg4AO5m87sSkmGLb9HC-LJ_-I4Os"
+    });
+
+    match JsonWebKey2020::new(None, public_key_jwk, Some(private_key_jwk), None) {
+        Ok(_) => panic!("A private key of another key must be rejected"),
+        Err(error) => assert!(error.to_string().contains("does not belong")),
+    };
+}
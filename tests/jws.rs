@@ -0,0 +1,179 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use fi_key_resolver::ed25519_verification_key2020::Ed25519VerificationKey2020;
+use fi_key_resolver::jws::{verify_detached_jws, JwsError};
+use fi_key_resolver::signature::SignatureKey;
+use serde_json::{json, Value};
+
+mod common;
+use common::{decode_hex, FINGERPRINT, SEED};
+
+fn sign_detached(header: &Value, payload: &[u8], encode_payload: bool) -> String {
+    let key_pair = match Ed25519VerificationKey2020::from_private_key_bytes(&decode_hex(SEED)) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let encoded_header = URL_SAFE_NO_PAD.encode(header.to_string());
+    let mut signing_input = format!("{}.", encoded_header).into_bytes();
+    if encode_payload {
+        signing_input.extend_from_slice(URL_SAFE_NO_PAD.encode(payload).as_bytes());
+    } else {
+        signing_input.extend_from_slice(payload);
+    }
+
+    let signature = match key_pair.sign(&signing_input) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    format!("{}..{}", encoded_header, URL_SAFE_NO_PAD.encode(signature))
+}
+
+#[test]
+pub fn test_verify_unencoded_detached_jws() {
+    let kid = format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT);
+    let payload = br#"{"event":"invoice.paid"}"#;
+
+    let jws = sign_detached(
+        &json!({"alg": "EdDSA", "b64": false, "crit": ["b64"], "kid": kid}),
+        payload,
+        false,
+    );
+    let verification_method = match verify_detached_jws(&jws, payload) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(verification_method.id, Some(kid.clone()));
+    assert_eq!(verification_method._type, "Ed25519VerificationKey2020");
+
+    // an encoded payload is verified too
+    let jws = sign_detached(&json!({"alg": "EdDSA", "kid": kid}), payload, true);
+    match verify_detached_jws(&jws, payload) {
+        Ok(val) => assert_eq!(val.id, Some(kid.clone())),
+        Err(error) => panic!("{}", error),
+    };
+
+    match verify_detached_jws(&jws, br#"{"event":"invoice.void"}"#) {
+        Ok(_) => panic!("JWS over another payload must not verify"),
+        Err(failure) => assert_eq!(failure.error, JwsError::InvalidSignature),
+    };
+}
+
+#[test]
+pub fn test_verify_detached_jws_checks_header() {
+    let kid = format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT);
+    let payload = b"payload";
+
+    // 'b64' must be critical
+    let jws = sign_detached(
+        &json!({"alg": "EdDSA", "b64": false, "kid": kid}),
+        payload,
+        false,
+    );
+    match verify_detached_jws(&jws, payload) {
+        Ok(_) => panic!("'b64' without 'crit' must be rejected"),
+        Err(failure) => assert_eq!(failure.error, JwsError::InvalidHeader),
+    };
+
+    let jws = sign_detached(&json!({"alg": "HS256", "kid": kid}), payload, true);
+    match verify_detached_jws(&jws, payload) {
+        Ok(_) => panic!("HS256 must be rejected"),
+        Err(failure) => assert_eq!(failure.error, JwsError::UnsupportedAlgorithm),
+    };
+
+    let jws = sign_detached(
+        &json!({"alg": "EdDSA", "kid": "https://example.com/keys/1"}),
+        payload,
+        true,
+    );
+    match verify_detached_jws(&jws, payload) {
+        Ok(_) => panic!("A kid that is not a did:key URL must be rejected"),
+        Err(failure) => assert_eq!(failure.error, JwsError::InvalidKeyId),
+    };
+
+    // the payload must be detached
+    let attached = jws.replacen("..", ".cGF5bG9hZA.", 1);
+    match verify_detached_jws(&attached, payload) {
+        Ok(_) => panic!("An attached payload must be rejected"),
+        Err(failure) => assert_eq!(failure.error, JwsError::InvalidJws),
+    };
+}
+
+#[test]
+pub fn test_verify_detached_jws_rejects_incompatible_key() {
+    let kid = "did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme#zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme";
+    let payload = b"payload";
+
+    let jws = sign_detached(&json!({"alg": "EdDSA", "kid": kid}), payload, true);
+    match verify_detached_jws(&jws, payload) {
+        Ok(_) => panic!("A secp256k1 key must not verify an EdDSA JWS"),
+        Err(failure) => assert_eq!(failure.error, JwsError::IncompatibleAlgorithm),
+    };
+}
+
+#[test]
+pub fn test_verify_detached_jws_rejects_malformed_input() {
+    let kid = format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT);
+    let payload = b"payload";
+    let jws = sign_detached(&json!({"alg": "EdDSA", "kid": kid}), payload, true);
+    let (_, signature) = jws.split_once("..").unwrap();
+
+    for (malformed, error) in [
+        (String::new(), JwsError::InvalidJws),
+        (String::from(".."), JwsError::InvalidHeader),
+        (String::from("a.b"), JwsError::InvalidJws),
+        (String::from("a..b.c"), JwsError::InvalidJws),
+        (format!("!!..{}", signature), JwsError::InvalidHeader),
+        (
+            format!("{}..{}", URL_SAFE_NO_PAD.encode("[]"), signature),
+            JwsError::InvalidHeader,
+        ),
+        (
+            format!("{}..{}", URL_SAFE_NO_PAD.encode("{"), signature),
+            JwsError::InvalidHeader,
+        ),
+        (jws.replacen(signature, "!!", 1), JwsError::InvalidJws),
+        (jws.replacen(signature, "", 1), JwsError::InvalidSignature),
+    ] {
+        match verify_detached_jws(&malformed, payload) {
+            Ok(_) => panic!("'{}' must be rejected", malformed),
+            Err(failure) => assert_eq!(failure.error, error, "{}", malformed),
+        };
+    }
+
+    for (header, error) in [
+        (json!({"kid": kid}), JwsError::InvalidHeader),
+        (json!({"alg": "EdDSA"}), JwsError::InvalidKeyId),
+        (json!({"alg": "EdDSA", "kid": 1}), JwsError::InvalidKeyId),
+        (
+            json!({"alg": "EdDSA", "kid": kid, "crit": "b64"}),
+            JwsError::InvalidHeader,
+        ),
+        (
+            json!({"alg": "EdDSA", "kid": kid, "crit": ["exp"]}),
+            JwsError::InvalidHeader,
+        ),
+        (
+            json!({"alg": "EdDSA", "kid": kid, "b64": "false", "crit": ["b64"]}),
+            JwsError::InvalidHeader,
+        ),
+        (
+            json!({"alg": "EdDSA", "kid": format!("did:key:{}", FINGERPRINT)}),
+            JwsError::InvalidKeyId,
+        ),
+        (
+            json!({"alg": "EdDSA", "kid": "did:key:#"}),
+            JwsError::InvalidKeyId,
+        ),
+        (
+            json!({"alg": "EdDSA", "kid": "did:key:zé#zé"}),
+            JwsError::InvalidKeyId,
+        ),
+    ] {
+        let jws = sign_detached(&header, payload, true);
+        match verify_detached_jws(&jws, payload) {
+            Ok(_) => panic!("{} must be rejected", header),
+            Err(failure) => assert_eq!(failure.error, error, "{}", header),
+        };
+    }
+}
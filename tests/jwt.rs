@@ -0,0 +1,287 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use fi_key_resolver::ed25519_verification_key2020::Ed25519VerificationKey2020;
+use fi_key_resolver::jwt::{
+    issue_jwt, verify_jwt, JwtClaims, JwtError, JwtValidationOptions, VerifiedJwt,
+};
+use fi_key_resolver::signature::SignatureKey;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+mod common;
+use common::{decode_hex, FINGERPRINT, SEED};
+
+const ISSUED_AT: u64 = 1704067200;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Claims {
+    aud: String,
+    scope: String,
+}
+
+fn key_pair() -> Ed25519VerificationKey2020 {
+    match Ed25519VerificationKey2020::from_private_key_bytes(&decode_hex(SEED)) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+fn claims() -> JwtClaims<Claims> {
+    let mut claims = JwtClaims::new(Claims {
+        aud: String::from("https://api.example.com"),
+        scope: String::from("invoices:read"),
+    });
+    claims.iat = Some(ISSUED_AT as f64);
+    claims.nbf = Some(ISSUED_AT as f64);
+    claims.exp = Some((ISSUED_AT + 3600) as f64);
+
+    claims
+}
+
+fn verify_at(token: &str, current_time: u64) -> Result<VerifiedJwt<Claims>, JwtError> {
+    let options = JwtValidationOptions {
+        current_time: Some(current_time),
+        ..JwtValidationOptions::default()
+    };
+
+    verify_jwt(token, &options).map_err(|failure| failure.error)
+}
+
+#[test]
+pub fn test_issue_and_verify_jwt() {
+    let token = match issue_jwt(&claims(), &key_pair()) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let header = token.split('.').next().unwrap();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&URL_SAFE_NO_PAD.decode(header).unwrap())
+            .unwrap(),
+        json!({
+            "alg": "EdDSA",
+            "typ": "JWT",
+            "kid": format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT)
+        })
+    );
+
+    let verified = match verify_at(&token, ISSUED_AT + 60) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        verified.claims.iss,
+        Some(format!("did:key:{}", FINGERPRINT))
+    );
+    assert_eq!(verified.claims.private, claims().private);
+    assert_eq!(
+        verified.verification_method.id,
+        Some(format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT))
+    );
+
+    // the payload is covered by the signature
+    let mut parts: Vec<String> = token.split('.').map(String::from).collect();
+    let mut tampered = claims();
+    tampered.private.scope = String::from("invoices:write");
+    tampered.iss = Some(format!("did:key:{}", FINGERPRINT));
+    parts[1] = URL_SAFE_NO_PAD.encode(serde_json::to_string(&tampered).unwrap());
+    match verify_at(&parts.join("."), ISSUED_AT + 60) {
+        Ok(_) => panic!("Tampered JWT must not verify"),
+        Err(error) => assert_eq!(error, JwtError::InvalidSignature),
+    };
+}
+
+#[test]
+pub fn test_verify_jwt_validates_times_with_leeway() {
+    let token = match issue_jwt(&claims(), &key_pair()) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    // the default leeway is a minute, and the token is expired from `exp` on
+    assert!(verify_at(&token, ISSUED_AT + 3600 + 59).is_ok());
+    assert!(verify_at(&token, ISSUED_AT - 60).is_ok());
+
+    assert_eq!(
+        verify_at(&token, ISSUED_AT + 3600 + 60).err(),
+        Some(JwtError::Expired)
+    );
+    assert_eq!(
+        verify_at(&token, ISSUED_AT - 61).err(),
+        Some(JwtError::NotYetValid)
+    );
+
+    let mut issued_later = claims();
+    issued_later.nbf = None;
+    issued_later.iat = Some((ISSUED_AT + 600) as f64);
+    let token = match issue_jwt(&issued_later, &key_pair()) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        verify_at(&token, ISSUED_AT).err(),
+        Some(JwtError::IssuedInFuture)
+    );
+
+    let options = JwtValidationOptions {
+        leeway: 600,
+        current_time: Some(ISSUED_AT),
+    };
+    match verify_jwt::<Claims>(&token, &options) {
+        Ok(_) => {}
+        Err(error) => panic!("{}", error),
+    };
+
+    // without leeway the token is expired at `exp` exactly
+    let options = JwtValidationOptions {
+        leeway: 0,
+        current_time: Some(ISSUED_AT + 600),
+    };
+    let mut expiring = claims();
+    expiring.exp = Some((ISSUED_AT + 600) as f64);
+    let token = match issue_jwt(&expiring, &key_pair()) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    match verify_jwt::<Claims>(&token, &options) {
+        Ok(_) => panic!("JWT must be expired at 'exp'"),
+        Err(failure) => assert_eq!(failure.error, JwtError::Expired),
+    };
+}
+
+#[test]
+pub fn test_jwt_times_are_numeric_dates() {
+    // whole seconds are written as integers
+    let token = match issue_jwt(&claims(), &key_pair()) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    let payload: serde_json::Value = serde_json::from_slice(
+        &URL_SAFE_NO_PAD
+            .decode(token.split('.').nth(1).unwrap())
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(payload["exp"], json!(ISSUED_AT + 3600));
+
+    // a NumericDate may have a fraction
+    let mut fractional = claims();
+    fractional.exp = Some(ISSUED_AT as f64 + 3600.5);
+    let token = match issue_jwt(&fractional, &key_pair()) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    let verified = match verify_at(&token, ISSUED_AT + 3600 + 60) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(verified.claims.exp, Some(ISSUED_AT as f64 + 3600.5));
+    assert_eq!(
+        verify_at(&token, ISSUED_AT + 3600 + 61).err(),
+        Some(JwtError::Expired)
+    );
+
+    let mut not_finite = claims();
+    not_finite.exp = Some(f64::INFINITY);
+    match issue_jwt(&not_finite, &key_pair()) {
+        Ok(_) => panic!("A time must be a finite number"),
+        Err(failure) => assert_eq!(failure.error, JwtError::InvalidClaims),
+    };
+
+    // a time that is not a number is rejected once the signature verifies
+    let header = json!({
+        "alg": "EdDSA",
+        "kid": format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT)
+    });
+    let payload = json!({
+        "iss": format!("did:key:{}", FINGERPRINT),
+        "aud": "https://api.example.com",
+        "scope": "invoices:read",
+        "exp": "2024-01-01T01:00:00Z"
+    });
+    let signing_input = format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(header.to_string()),
+        URL_SAFE_NO_PAD.encode(payload.to_string())
+    );
+    let signature = match key_pair().sign(signing_input.as_bytes()) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    let token = format!("{}.{}", signing_input, URL_SAFE_NO_PAD.encode(signature));
+    assert_eq!(
+        verify_at(&token, ISSUED_AT).err(),
+        Some(JwtError::InvalidClaims)
+    );
+}
+
+#[test]
+pub fn test_verify_jwt_rejects_malformed_tokens() {
+    let token = match issue_jwt(&claims(), &key_pair()) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    let parts: Vec<&str> = token.split('.').collect();
+
+    for malformed in [
+        String::new(),
+        format!("{}.{}", parts[0], parts[1]),
+        format!("{}.{}.{}.{}", parts[0], parts[1], parts[2], parts[2]),
+        format!("{}.{}.!", parts[0], parts[1]),
+        format!("!.{}.{}", parts[1], parts[2]),
+        format!(
+            "{}.{}.{}",
+            URL_SAFE_NO_PAD.encode("not json"),
+            parts[1],
+            parts[2]
+        ),
+    ] {
+        assert_eq!(
+            verify_at(&malformed, ISSUED_AT).err(),
+            Some(JwtError::InvalidToken),
+            "{}",
+            malformed
+        );
+    }
+
+    let header = URL_SAFE_NO_PAD.encode(
+        json!({"alg": "none", "kid": format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT)})
+            .to_string(),
+    );
+    assert_eq!(
+        verify_at(&format!("{}.{}.", header, parts[1]), ISSUED_AT).err(),
+        Some(JwtError::UnsupportedAlgorithm)
+    );
+}
+
+#[test]
+pub fn test_jwt_issuer_must_match_key() {
+    let mut other_issuer = claims();
+    other_issuer.iss = Some(String::from(
+        "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH",
+    ));
+    match issue_jwt(&other_issuer, &key_pair()) {
+        Ok(_) => panic!("Another issuer must be rejected"),
+        Err(failure) => assert_eq!(failure.error, JwtError::IssuerMismatch),
+    };
+
+    // a token signed with the key of the kid for another issuer
+    let header = json!({
+        "alg": "EdDSA",
+        "kid": format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT)
+    });
+    let signing_input = format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(header.to_string()),
+        URL_SAFE_NO_PAD.encode(serde_json::to_string(&other_issuer).unwrap())
+    );
+    let signature = match key_pair().sign(signing_input.as_bytes()) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    let token = format!("{}.{}", signing_input, URL_SAFE_NO_PAD.encode(signature));
+
+    assert_eq!(
+        verify_at(&token, ISSUED_AT).err(),
+        Some(JwtError::IssuerMismatch)
+    );
+}
@@ -0,0 +1,188 @@
+use fi_common::keys::{AgreementKey, VerificationKey};
+use fi_key_resolver::ed25519_verification_key2018::Ed25519VerificationKey2018;
+use fi_key_resolver::ed25519_verification_key2020::Ed25519VerificationKey2020;
+use fi_key_resolver::keystore::{Keystore, KeystoreError, KeystoreOptions};
+use fi_key_resolver::x25519_key_agreement_key2019::X25519KeyAgreementKey2019;
+use serde_json::Value;
+
+// cheap Argon2id parameters, only for tests
+const OPTIONS: KeystoreOptions = KeystoreOptions {
+    memory_cost: 64,
+    time_cost: 1,
+    parallelism: 1,
+};
+
+#[test]
+pub fn test_keystore_save_and_load() {
+    let key_pair = match Ed25519VerificationKey2020::generate() {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let keystore = match Keystore::encrypt(&key_pair.export(true, true, true), b"secret", &OPTIONS)
+    {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(keystore.get_header().version, 1);
+    assert_eq!(
+        Some(&keystore.get_header().did),
+        key_pair.get_controller().as_ref()
+    );
+    assert_eq!(keystore.get_header().suite, "Ed25519VerificationKey2020");
+
+    let path = std::env::temp_dir().join(format!(
+        "fi-key-resolver-keystore-{}.json",
+        std::process::id()
+    ));
+    if let Err(error) = keystore.save(&path) {
+        panic!("{}", error);
+    }
+    let loaded = Keystore::load(&path);
+    let _ = std::fs::remove_file(&path);
+
+    let loaded = match loaded {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    let decrypted = match loaded.decrypt_key::<Ed25519VerificationKey2020>(b"secret") {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        serde_json::to_value(decrypted.export(true, true, true)).unwrap(),
+        serde_json::to_value(key_pair.export(true, true, true)).unwrap()
+    );
+
+    // the key pair is not a key of another suite
+    match loaded.decrypt_key::<Ed25519VerificationKey2018>(b"secret") {
+        Ok(_) => panic!("Key pair must not load as Ed25519VerificationKey2018"),
+        Err(failure) => assert_eq!(failure.error, KeystoreError::InvalidKeyPair),
+    }
+}
+
+#[test]
+pub fn test_keystore_wrong_password_and_tampering() {
+    let key_pair = Ed25519VerificationKey2018::generate();
+    let keystore = match Keystore::encrypt(&key_pair.export(true, true, true), b"secret", &OPTIONS)
+    {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    match keystore.decrypt(b"wrong") {
+        Ok(_) => panic!("Wrong password must not decrypt"),
+        Err(failure) => assert_eq!(failure.error, KeystoreError::DecryptionFailed),
+    }
+
+    let json: Value = serde_json::from_str(&keystore.to_json().unwrap()).unwrap();
+
+    let mut tampered = json.clone();
+    tampered["did"] = Value::from("did:key:z6MkqYAnwjMV8HXVoZs4RXrdQd1rgRPiKhTVtU89G4WZ8eKn");
+    let tampered = Keystore::from_json(&tampered.to_string()).unwrap();
+    match tampered.decrypt(b"secret") {
+        Ok(_) => panic!("Tampered header must not decrypt"),
+        Err(failure) => assert_eq!(failure.error, KeystoreError::DecryptionFailed),
+    }
+
+    let mut tampered = json.clone();
+    tampered["suite"] = Value::from("Ed25519VerificationKey2020");
+    let tampered = Keystore::from_json(&tampered.to_string()).unwrap();
+    assert!(tampered.decrypt(b"secret").is_err());
+
+    let mut unsupported = json;
+    unsupported["version"] = Value::from(2);
+    match Keystore::from_json(&unsupported.to_string()) {
+        Ok(_) => panic!("Version 2 must not be supported"),
+        Err(failure) => assert_eq!(failure.error, KeystoreError::UnsupportedVersion),
+    }
+}
+
+#[test]
+pub fn test_keystore_reencrypt() {
+    let key_pair = X25519KeyAgreementKey2019::generate();
+    let keystore = match Keystore::encrypt(&key_pair.export(true, true, true), b"old", &OPTIONS) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let reencrypted = match keystore.reencrypt(b"old", b"new", &OPTIONS) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(reencrypted.get_header().did, keystore.get_header().did);
+    assert!(reencrypted.decrypt(b"old").is_err());
+
+    let decrypted = match reencrypted.decrypt_key::<X25519KeyAgreementKey2019>(b"new") {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        decrypted.export(true, true, false).private_key_base58,
+        key_pair.export(true, true, false).private_key_base58
+    );
+}
+
+#[test]
+pub fn test_keystore_rejects_excessive_kdf_costs() {
+    let key_pair = Ed25519VerificationKey2018::generate();
+    let keystore = match Keystore::encrypt(&key_pair.export(true, true, true), b"secret", &OPTIONS)
+    {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    let json: Value = serde_json::from_str(&keystore.to_json().unwrap()).unwrap();
+
+    // e.g. 4 TiB of memory
+    for (member, cost) in [
+        ("memoryCost", u32::MAX),
+        ("timeCost", u32::MAX),
+        ("parallelism", 1024),
+    ] {
+        let mut crafted = json.clone();
+        crafted["kdf"][member] = Value::from(cost);
+        let crafted = Keystore::from_json(&crafted.to_string()).unwrap();
+
+        match crafted.decrypt(b"secret") {
+            Ok(_) => panic!("Excessive '{}' must be rejected", member),
+            Err(failure) => assert_eq!(failure.error, KeystoreError::InvalidFormat),
+        }
+    }
+
+    // the options of a new keystore are capped too
+    let options = KeystoreOptions {
+        time_cost: u32::MAX,
+        ..OPTIONS
+    };
+    assert!(Keystore::encrypt(&key_pair.export(true, true, true), b"secret", &options).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+pub fn test_keystore_file_is_private() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let key_pair = X25519KeyAgreementKey2019::generate();
+    let keystore = match Keystore::encrypt(&key_pair.export(true, true, true), b"secret", &OPTIONS)
+    {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let path = std::env::temp_dir().join(format!(
+        "fi-key-resolver-keystore-mode-{}.json",
+        std::process::id()
+    ));
+    // an existing file loses its broader permissions
+    std::fs::write(&path, "").unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+    let saved = keystore.save(&path);
+    let mode = std::fs::metadata(&path).map(|val| val.permissions().mode());
+    let _ = std::fs::remove_file(&path);
+
+    if let Err(error) = saved {
+        panic!("{}", error);
+    }
+    assert_eq!(mode.unwrap() & 0o777, 0o600);
+}
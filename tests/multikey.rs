@@ -0,0 +1,108 @@
+use fi_common::keys::VerificationKey;
+use fi_key_resolver::ed25519_verification_key2018::Ed25519VerificationKey2018;
+use fi_key_resolver::multikey::Multikey;
+use fi_key_resolver::resolution::ResolutionOptions;
+use fi_key_resolver::resolve;
+use serde_json::Value;
+
+#[test]
+pub fn test_resolve_multikey() {
+    let did = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+    let options = ResolutionOptions {
+        public_key_format: Some(String::from("Multikey")),
+        ..ResolutionOptions::default()
+    };
+
+    let did_doc = resolve(did, &options)
+        .did_document
+        .expect("DID document not found");
+
+    assert_eq!(
+        did_doc.context,
+        Vec::from([
+            Value::from("https://www.w3.org/ns/did/v1"),
+            Value::from("https://w3id.org/security/multikey/v1"),
+        ])
+    );
+
+    let verification_method = &did_doc.verification_method.unwrap()[0];
+    assert_eq!(verification_method._type, "Multikey");
+    assert_eq!(
+        verification_method.id.as_deref(),
+        Some("did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH#z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH")
+    );
+
+    let key_agreement = &did_doc.key_agreement.unwrap()[0];
+    assert_eq!(key_agreement._type, "Multikey");
+    assert_eq!(
+        key_agreement.public_key_multibase.as_deref(),
+        Some("z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc")
+    );
+    assert_eq!(
+        key_agreement.id.as_deref(),
+        Some("did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH#z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc")
+    );
+}
+
+#[test]
+pub fn test_multikey_from_ed25519_verification_key2018() {
+    let key_pair = match Ed25519VerificationKey2018::from_fingerprint(
+        "z6MkpVCWpibzht7gFFkBsnNigRvXiQWQgV2vqq8eN8zGkGGN",
+    ) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let multikey = match Multikey::from_ed25519_verification_key(&key_pair) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    assert_eq!(
+        VerificationKey::get_public_key_content(&multikey),
+        "z6MkpVCWpibzht7gFFkBsnNigRvXiQWQgV2vqq8eN8zGkGGN"
+    );
+    assert_eq!(multikey.get_public_key_header(), [0xed, 0x01]);
+}
+
+#[test]
+pub fn test_multikey_rejects_unknown_key_type() {
+    assert!(
+        Multikey::from_fingerprint("z4TcdE8GjDme6bJ4CnQG4bpoc5bv3T1oqJUr7vH4DWAEFxaB").is_err()
+    );
+}
+
+#[test]
+pub fn test_multikey_checks_private_key() {
+    // the W3C Data Integrity EdDSA test vector key pair
+    let public_key_multibase = "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2";
+    let private_key_multibase = "z3u2en7t5LR2WtQH5PfFqMqwVHBeXouLzo6haApm8XHqvjxq";
+
+    let multikey = match Multikey::new(
+        Some(format!("did:key:{}", public_key_multibase)),
+        String::from(public_key_multibase),
+        Some(String::from(private_key_multibase)),
+        Some(String::from(public_key_multibase)),
+    ) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    // the derived X25519 private key is checked against its public key too
+    let agreement_key = match multikey.to_x25519_key_agreement_key() {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(agreement_key.get_public_key_header(), [0xec, 0x01]);
+
+    let other_public_key_multibase = "z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+    match Multikey::new(
+        None,
+        String::from(other_public_key_multibase),
+        Some(String::from(private_key_multibase)),
+        None,
+    ) {
+        Ok(_) => panic!("A private key of another key must be rejected"),
+        Err(error) => assert!(error.to_string().contains("does not belong")),
+    };
+}
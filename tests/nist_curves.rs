@@ -0,0 +1,111 @@
+use fi_key_resolver::resolution::{ResolutionError, ResolutionOptions};
+use fi_key_resolver::{resolve, resolve_did};
+use serde_json::{json, Value};
+
+#[test]
+pub fn test_resolve_p256() {
+    let did = "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169";
+
+    let did_doc = match resolve_did(did, "JsonWebKey2020") {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+
+    assert_eq!(
+        did_doc.context,
+        Vec::from([
+            Value::from("https://www.w3.org/ns/did/v1"),
+            Value::from("https://w3id.org/security/suites/jws-2020/v1"),
+        ])
+    );
+
+    let verification_method = &did_doc.verification_method.unwrap()[0];
+    assert_eq!(verification_method._type, "JsonWebKey2020");
+    assert_eq!(
+        verification_method.id.as_deref(),
+        Some("did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169#zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169")
+    );
+    assert_eq!(
+        verification_method.public_key_jwk,
+        Some(json!({
+            "kty": "EC",
+            "crv": "P-256",
+            "x": "fyNYMN0976ci7xqiSdag3buk-ZCwgXU4kz9XNkBlNUI",
+            "y": "hW2ojTNfH7Jbi8--CJUo3OCbH3y5n91g-IMA9MLMbTU"
+        }))
+    );
+    assert!(did_doc.key_agreement.is_none());
+
+    let did_doc = resolve(did, &ResolutionOptions::default())
+        .did_document
+        .expect("DID document not found");
+    let verification_method = &did_doc.verification_method.unwrap()[0];
+    assert_eq!(verification_method._type, "Multikey");
+    assert_eq!(
+        verification_method.public_key_multibase.as_deref(),
+        Some("zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169")
+    );
+}
+
+#[test]
+pub fn test_resolve_p384_and_p521() {
+    let did_doc = match resolve_did(
+        "did:key:z82Lm1MpAkeJcix9K8TMiLd5NMAhnwkjjCBeWHXyu3U4oT2MVJJKXkcVBgjGhnLBn2Kaau9",
+        "JsonWebKey2020",
+    ) {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        did_doc.verification_method.unwrap()[0].public_key_jwk,
+        Some(json!({
+            "kty": "EC",
+            "crv": "P-384",
+            "x": "lInTxl8fjLKp_UCrxI0WDklahi-7-_6JbtiHjiRvMvhedhKVdHBfi2HCY8t_QJyc",
+            "y": "y6N1IC-2mXxHreETBW7K3mBcw0qGr3CWHCs-yl09yCQRLcyfGv7XhqAngHOu51Zv"
+        }))
+    );
+
+    let did_doc = match resolve_did(
+        "did:key:z2J9gaYxrKVpdoG9A4gRnmpnRCcxU6agDtFVVBVdn1JedouoZN7SzcyREXXzWgt3gGiwpoHq7K68X4m32D8HgzG8wv3sY5j7",
+        "JsonWebKey2020",
+    ) {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        did_doc.verification_method.unwrap()[0].public_key_jwk,
+        Some(json!({
+            "kty": "EC",
+            "crv": "P-521",
+            "x": "ASUHPMyichQ0QbHZ9ofNx_l4y7luncn5feKLo3OpJ2nSbZoC7mffolj5uy7s6KSKXFmnNWxGJ42IOrjZ47qqwqyS",
+            "y": "AW9ziIC4ZQQVSNmLlp59yYKrjRY0_VqO-GOIYQ9tYpPraBKUloEId6cI_vynCzlZWZtWpgOM3HPhYEgawQ703RjC"
+        }))
+    );
+}
+
+#[test]
+pub fn test_resolve_invalid_nist_keys() {
+    // x coordinate 0x01 has no point on P-256
+    let result = resolve(
+        "did:key:zDnaeQRy3dcKsKa1zmKtVKsTy3m2HYoQnFnfKuxD6HfSTQgYg",
+        &ResolutionOptions::default(),
+    );
+    assert_eq!(
+        result.did_resolution_metadata.error,
+        Some(ResolutionError::InvalidPublicKey)
+    );
+
+    // a 33 bytes point with the P-384 header
+    let result = resolve(
+        "did:key:zDtNJfRWT1uCbUdRTfrmWhmjPhFAabz3JSjDTXdrPkX2wh1He",
+        &ResolutionOptions::default(),
+    );
+    assert_eq!(
+        result.did_resolution_metadata.error,
+        Some(ResolutionError::InvalidPublicKeyLength)
+    );
+}
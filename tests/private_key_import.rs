@@ -0,0 +1,180 @@
+use fi_common::keys::{AgreementKey, VerificationKey};
+use fi_key_resolver::ed25519_verification_key2018::Ed25519VerificationKey2018;
+use fi_key_resolver::ed25519_verification_key2020::Ed25519VerificationKey2020;
+use fi_key_resolver::signature::SignatureKey;
+use fi_key_resolver::x25519_key_agreement_key2019::X25519KeyAgreementKey2019;
+use fi_key_resolver::x25519_key_agreement_key2020::X25519KeyAgreementKey2020;
+
+mod common;
+use common::{decode_hex, EXPANDED_SECRET_KEY, FINGERPRINT, PUBLIC_KEY, SEED};
+
+#[test]
+pub fn test_import_ed25519_2020_private_key() {
+    let mut libsodium_secret_key = decode_hex(SEED);
+    libsodium_secret_key.extend(decode_hex(PUBLIC_KEY));
+
+    let forms = [
+        (decode_hex(SEED), "zrv3nQ3vxUrShebtbJeB42niZe1oGRnFzGPusycqLLtiJEeSFbDjwS6rvt6uMYYkjGuZMTsqb6mzCgG19WbjcNNsvxq"),
+        (libsodium_secret_key, "zrv3nQ3vxUrShebtbJeB42niZe1oGRnFzGPusycqLLtiJEeSFbDjwS6rvt6uMYYkjGuZMTsqb6mzCgG19WbjcNNsvxq"),
+    ];
+
+    for (private_key, private_key_multibase) in forms {
+        let key_pair = match Ed25519VerificationKey2020::from_private_key_bytes(&private_key) {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+
+        let exported = key_pair.export(true, true, false);
+        assert_eq!(
+            exported.id,
+            Some(format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT))
+        );
+        assert_eq!(exported.public_key_multibase.as_deref(), Some(FINGERPRINT));
+        assert_eq!(
+            exported.private_key_multibase.as_deref(),
+            Some(private_key_multibase)
+        );
+
+        // the export can be imported again
+        let imported = match Ed25519VerificationKey2020::new(
+            exported.controller,
+            exported.public_key_multibase.unwrap(),
+            exported.private_key_multibase,
+            Some(String::from(FINGERPRINT)),
+        ) {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+
+        // both forms have the same X25519 private key
+        let agreement_key =
+            match X25519KeyAgreementKey2020::from_ed25519_verification_key2020(&imported) {
+                Ok(val) => val,
+                Err(error) => panic!("{}", error),
+            };
+        assert_eq!(
+            agreement_key
+                .export(false, true, false)
+                .private_key_multibase
+                .as_deref(),
+            Some("z3wea3FEiVjYREYVX7t99g4uEm4r4jPsTuhm4GMv5cS838rS")
+        );
+    }
+}
+
+#[test]
+pub fn test_import_ed25519_2018_private_key() {
+    let key_pair = match Ed25519VerificationKey2018::from_private_key_bytes(&decode_hex(SEED)) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let exported = key_pair.export(true, true, false);
+    assert_eq!(
+        exported.id,
+        Some(format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT))
+    );
+    assert_eq!(
+        exported.public_key_base58.as_deref(),
+        Some("FVen3X669xLzsi6N2V91DoiyzHzg1uAgqiT8jZ9nS96Z")
+    );
+    assert_eq!(
+        exported.private_key_base58.as_deref(),
+        Some("49W385L4rePHy6PAaQUovbD2aacgN4HsKXSMeUzRg4fmwXszN91JuMFrQRj3vMDpZuRF3ZknQBuRBoWQJEfXstMw")
+    );
+
+    let mut expanded_secret_key = [0u8; 64];
+    expanded_secret_key.copy_from_slice(&decode_hex(EXPANDED_SECRET_KEY));
+    let expanded_key_pair =
+        Ed25519VerificationKey2018::from_expanded_secret_key(&expanded_secret_key);
+    assert_eq!(
+        expanded_key_pair.get_public_key_content(),
+        "FVen3X669xLzsi6N2V91DoiyzHzg1uAgqiT8jZ9nS96Z"
+    );
+    assert!(expanded_key_pair.get_private_key_content().is_none());
+
+    let agreement_key =
+        match X25519KeyAgreementKey2019::from_ed25519_verification_key2018(&key_pair) {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+    assert_eq!(
+        agreement_key
+            .export(false, true, false)
+            .private_key_base58
+            .as_deref(),
+        Some("4GGh2VrEgkH7MVktXDJ9Wcqr6X9rdybhFQuQxYmjs67G")
+    );
+}
+
+#[test]
+pub fn test_import_ed25519_2020_expanded_secret_key() {
+    let mut expanded_secret_key = [0u8; 64];
+    expanded_secret_key.copy_from_slice(&decode_hex(EXPANDED_SECRET_KEY));
+
+    let key_pair = match Ed25519VerificationKey2020::from_expanded_secret_key(&expanded_secret_key)
+    {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    // the expanded secret key has no seed, so it is never exported as one
+    let exported = key_pair.export(true, true, false);
+    assert_eq!(
+        exported.id,
+        Some(format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT))
+    );
+    assert_eq!(exported.public_key_multibase.as_deref(), Some(FINGERPRINT));
+    assert_eq!(exported.private_key_multibase, None);
+
+    let agreement_key =
+        match X25519KeyAgreementKey2020::from_ed25519_verification_key2020(&key_pair) {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+    assert_eq!(
+        agreement_key
+            .export(false, true, false)
+            .private_key_multibase,
+        None
+    );
+
+    // it is kept when converting between the suites
+    let converted = match Ed25519VerificationKey2018::try_from(&key_pair) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert!(converted.get_expanded_secret_key().is_some());
+    assert_eq!(converted.export(true, true, false).private_key_base58, None);
+
+    let round_trip = match Ed25519VerificationKey2020::try_from(&converted) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert!(round_trip.get_expanded_secret_key().is_some());
+}
+
+#[test]
+pub fn test_import_invalid_private_key() {
+    assert!(Ed25519VerificationKey2020::from_private_key_bytes(&[1u8; 31]).is_err());
+    assert!(Ed25519VerificationKey2018::from_private_key_bytes(&[1u8; 48]).is_err());
+    assert!(Ed25519VerificationKey2020::from_private_key_bytes(&[]).is_err());
+}
+
+#[test]
+pub fn test_import_rejects_64_byte_key_without_public_key() {
+    // a seed followed by bytes that are not its public key
+    let mut corrupted_secret_key = decode_hex(SEED);
+    corrupted_secret_key.extend([7u8; 32]);
+
+    // an expanded secret key is only imported with `from_expanded_secret_key`
+    for private_key in [corrupted_secret_key, decode_hex(EXPANDED_SECRET_KEY)] {
+        match Ed25519VerificationKey2020::from_private_key_bytes(&private_key) {
+            Ok(_) => panic!("64 byte key must be the seed followed by its public key"),
+            Err(error) => assert!(error
+                .to_string()
+                .contains("seed followed by its public key")),
+        };
+        assert!(Ed25519VerificationKey2018::from_private_key_bytes(&private_key).is_err());
+    }
+}
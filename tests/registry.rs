@@ -0,0 +1,222 @@
+use fi_common::did::{DidDocument, Service};
+use fi_key_resolver::dereferencing::ContentStream;
+use fi_key_resolver::did_url::DidUrl;
+use fi_key_resolver::registry::{register_did_method, DidMethodResolver, DidResolverRegistry};
+use fi_key_resolver::resolution::{DidResolutionResult, ResolutionError, ResolutionOptions};
+use fi_key_resolver::{dereference, resolve, resolve_did_with_format};
+use serde_json::Value;
+
+struct ExampleResolver;
+
+impl DidMethodResolver for ExampleResolver {
+    fn method(&self) -> &str {
+        "example"
+    }
+
+    fn resolve(&self, did_url: &DidUrl, _options: &ResolutionOptions) -> DidResolutionResult {
+        DidResolutionResult::from_did_document(DidDocument {
+            context: Vec::from([Value::from("https://www.w3.org/ns/did/v1")]),
+            id: String::from(did_url.did()),
+            verification_method: None,
+            authentication: None,
+            assertion_method: None,
+            capability_delegation: None,
+            capability_invocation: None,
+            key_agreement: None,
+            services: Some(Vec::from([Service {
+                id: format!("{}#files", did_url.did()),
+                _type: String::from("LinkedDomains"),
+                service_endpoint: Value::from("https://example.com/files/"),
+            }])),
+        })
+    }
+}
+
+#[test]
+pub fn test_registry_dispatch() {
+    let mut registry = DidResolverRegistry::new();
+    let options = ResolutionOptions::default();
+
+    let result = registry.resolve("did:example:123", &options);
+    assert_eq!(
+        result.did_resolution_metadata.error,
+        Some(ResolutionError::MethodNotSupported)
+    );
+
+    registry.register(Box::new(ExampleResolver));
+    assert!(registry.is_supported("example"));
+    assert!(registry.is_supported("key"));
+
+    let did_doc = registry
+        .resolve("did:example:123", &options)
+        .did_document
+        .expect("DID document not found");
+    assert_eq!(did_doc.id, "did:example:123");
+
+    let result = registry.dereference(
+        "did:example:123?service=files&relativeRef=%2Fdocs%2Fa.pdf",
+        &options,
+    );
+    match result.content_stream {
+        Some(ContentStream::ServiceEndpoint(endpoint)) => {
+            assert_eq!(endpoint, "https://example.com/docs/a.pdf")
+        }
+        _ => panic!("Expected a service endpoint"),
+    }
+
+    let result = DidResolverRegistry::empty().resolve(
+        "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH",
+        &options,
+    );
+    assert_eq!(
+        result.did_resolution_metadata.error,
+        Some(ResolutionError::MethodNotSupported)
+    );
+}
+
+#[test]
+pub fn test_global_registry() {
+    struct OtherResolver;
+
+    impl DidMethodResolver for OtherResolver {
+        fn method(&self) -> &str {
+            "other"
+        }
+
+        fn resolve(&self, did_url: &DidUrl, options: &ResolutionOptions) -> DidResolutionResult {
+            ExampleResolver.resolve(did_url, options)
+        }
+    }
+
+    let options = ResolutionOptions::default();
+    assert!(resolve_did_with_format("did:other:123", None).is_err());
+
+    register_did_method(Box::new(OtherResolver));
+
+    let (did_doc, _) = match resolve_did_with_format("did:other:123", None) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(did_doc.expect("DID document not found").id, "did:other:123");
+    assert!(!resolve("did:other:123", &options).is_error());
+
+    let result = dereference("did:other:123#files", &options);
+    assert!(matches!(
+        result.content_stream,
+        Some(ContentStream::Service(_))
+    ));
+}
+
+#[test]
+pub fn test_dereference_resolves_did() {
+    struct PlainDidResolver;
+
+    impl DidMethodResolver for PlainDidResolver {
+        fn method(&self) -> &str {
+            "plain"
+        }
+
+        fn resolve(&self, did_url: &DidUrl, options: &ResolutionOptions) -> DidResolutionResult {
+            if !did_url.is_did() {
+                return DidResolutionResult::from_error(
+                    ResolutionError::InvalidDid,
+                    format!("'{}' is not a DID", did_url).as_str(),
+                );
+            }
+
+            ExampleResolver.resolve(did_url, options)
+        }
+    }
+
+    let mut registry = DidResolverRegistry::new();
+    registry.register(Box::new(PlainDidResolver));
+
+    let result = registry.dereference("did:plain:123#files", &ResolutionOptions::default());
+    assert!(matches!(
+        result.content_stream,
+        Some(ContentStream::Service(_))
+    ));
+}
+
+#[test]
+pub fn test_resolver_uses_global_registry() {
+    // resolves did:alias:<id> through the global registry as did:key:<id> and
+    // registers a resolver while it runs, which takes the registry write lock
+    struct AliasResolver;
+
+    impl DidMethodResolver for AliasResolver {
+        fn method(&self) -> &str {
+            "alias"
+        }
+
+        fn resolve(&self, did_url: &DidUrl, options: &ResolutionOptions) -> DidResolutionResult {
+            register_did_method(Box::new(ExampleResolver));
+
+            resolve(
+                format!("did:key:{}", did_url.method_specific_id()).as_str(),
+                options,
+            )
+        }
+    }
+
+    register_did_method(Box::new(AliasResolver));
+
+    let options = ResolutionOptions::default();
+    let result = resolve(
+        "did:alias:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH",
+        &options,
+    );
+    assert!(!result.is_error());
+
+    let result = dereference(
+        "did:alias:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH#z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH",
+        &options,
+    );
+    assert!(!result.is_error());
+}
+
+#[test]
+pub fn test_version_time() {
+    struct VersionedResolver;
+
+    impl DidMethodResolver for VersionedResolver {
+        fn method(&self) -> &str {
+            "versioned"
+        }
+
+        fn resolve(&self, did_url: &DidUrl, options: &ResolutionOptions) -> DidResolutionResult {
+            let mut result = ExampleResolver.resolve(did_url, options);
+            result.did_document_metadata.created = Some(String::from("2021-01-01T12:00:00+02:00"));
+            result
+        }
+    }
+
+    let mut registry = DidResolverRegistry::new();
+    registry.register(Box::new(VersionedResolver));
+    let options = ResolutionOptions::default();
+
+    for version_time in ["2021-01-01T10:00:00Z", "2021-01-01T11:00:00Z"] {
+        let did_url = format!("did:versioned:123?versionTime={}", version_time);
+        let result = registry.dereference(&did_url, &options);
+        assert!(
+            matches!(result.content_stream, Some(ContentStream::DidDocument(_))),
+            "{}",
+            did_url
+        );
+    }
+
+    let result = registry.dereference(
+        "did:versioned:123?versionTime=2021-01-01T09:59:59Z",
+        &options,
+    );
+    assert_eq!(
+        result.dereferencing_metadata.error,
+        Some(ResolutionError::NotFound)
+    );
+
+    let result = registry.dereference("did:versioned:123?versionTime=yesterday", &options);
+    assert_eq!(
+        result.dereferencing_metadata.error,
+        Some(ResolutionError::InvalidDidUrl)
+    );
+}
@@ -0,0 +1,148 @@
+use fi_key_resolver::resolution::{ResolutionError, ResolutionOptions};
+use fi_key_resolver::resolve;
+
+#[test]
+pub fn test_resolution_result() {
+    let did = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+
+    let result = resolve(did, &ResolutionOptions::default());
+
+    assert!(!result.is_error());
+    assert_eq!(
+        result.did_resolution_metadata.content_type.as_deref(),
+        Some("application/did+ld+json")
+    );
+    assert_eq!(result.did_document.expect("DID document not found").id, did);
+}
+
+#[test]
+pub fn test_resolution_result_json() {
+    let did = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+
+    let json = serde_json::to_value(resolve(did, &ResolutionOptions::default()))
+        .expect("Serialization failed");
+
+    assert_eq!(
+        json["didResolutionMetadata"]["contentType"],
+        "application/did+ld+json"
+    );
+    assert_eq!(json["didDocument"]["id"], did);
+    assert!(json["didDocument"].get("services").is_none());
+    assert!(json["didDocumentMetadata"].is_object());
+
+    let json = serde_json::to_value(resolve(
+        "did:web:example.com",
+        &ResolutionOptions::default(),
+    ))
+    .expect("Serialization failed");
+
+    assert_eq!(json["didResolutionMetadata"]["error"], "methodNotSupported");
+    assert!(json["didDocument"].is_null());
+}
+
+#[test]
+pub fn test_resolution_errors() {
+    let cases = [
+        ("did:web:example.com", ResolutionError::MethodNotSupported),
+        (
+            "key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH",
+            ResolutionError::InvalidDid,
+        ),
+        (
+            "did:key:6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH",
+            ResolutionError::InvalidDid,
+        ),
+        (
+            "did:key:z2DQUz8yxybcgY49o2TDENNPqPQBbVynuU6CcNCWtSMrwMx",
+            ResolutionError::InvalidPublicKeyLength,
+        ),
+        (
+            "did:key:z6Mkeb4rtEhc8DUtvt5ehaVjdx3TLbQPpnTArkXhqfb1Mq75",
+            ResolutionError::InvalidPublicKey,
+        ),
+        (
+            "did:key:z4TcdE8GjDme6bJ4CnQG4bpoc5bv3T1oqJUr7vH4DWAEFxaB",
+            ResolutionError::UnsupportedPublicKeyType,
+        ),
+    ];
+
+    for (did, error) in cases {
+        let result = resolve(did, &ResolutionOptions::default());
+
+        assert_eq!(result.did_resolution_metadata.error, Some(error), "{}", did);
+        assert!(result.did_document.is_none());
+    }
+}
+
+#[test]
+pub fn test_resolution_options() {
+    let did = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+    let options = ResolutionOptions {
+        enable_encryption_key_derivation: false,
+        default_context: Some(Vec::from([
+            String::from("https://www.w3.org/ns/did/v1"),
+            String::from("https://example.com/context/v1"),
+        ])),
+        ..ResolutionOptions::default()
+    };
+
+    let did_doc = resolve(did, &options)
+        .did_document
+        .expect("DID document not found");
+
+    assert!(did_doc.key_agreement.is_none());
+    assert_eq!(
+        did_doc.context,
+        Vec::from([
+            serde_json::Value::from("https://www.w3.org/ns/did/v1"),
+            serde_json::Value::from("https://example.com/context/v1"),
+            serde_json::Value::from("https://w3id.org/security/suites/ed25519-2020/v1"),
+        ])
+    );
+}
+
+#[test]
+pub fn test_experimental_public_key_format() {
+    let did = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+    let mut options = ResolutionOptions {
+        public_key_format: Some(String::from("Ed25519VerificationKey2018")),
+        ..ResolutionOptions::default()
+    };
+
+    let result = resolve(did, &options);
+    assert_eq!(
+        result.did_resolution_metadata.error,
+        Some(ResolutionError::InvalidPublicKeyType)
+    );
+
+    options.enable_experimental_public_key_types = true;
+    let did_doc = resolve(did, &options)
+        .did_document
+        .expect("DID document not found");
+    assert_eq!(
+        did_doc.verification_method.unwrap()[0]._type,
+        "Ed25519VerificationKey2018"
+    );
+}
+
+#[test]
+pub fn test_accept_option() {
+    let did = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+    let mut options = ResolutionOptions {
+        accept: Some(String::from("application/did+json")),
+        ..ResolutionOptions::default()
+    };
+
+    let result = resolve(did, &options);
+    assert_eq!(
+        result.did_resolution_metadata.content_type.as_deref(),
+        Some("application/did+json")
+    );
+
+    options.accept = Some(String::from("application/did+cbor"));
+    let result = resolve(did, &options);
+    assert_eq!(
+        result.did_resolution_metadata.error,
+        Some(ResolutionError::RepresentationNotSupported)
+    );
+}
@@ -0,0 +1,56 @@
+use fi_key_resolver::{resolve_did, resolve_did_with_format};
+
+#[test]
+pub fn test_detect_suite_from_multicodec() {
+    let did = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+
+    let (did_doc_option, _) = match resolve_did_with_format(did, None) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let did_doc = did_doc_option.expect("DID document not found");
+    let verification_method = &did_doc.verification_method.unwrap()[0];
+
+    assert_eq!(verification_method._type, "Ed25519VerificationKey2020");
+    assert_eq!(
+        verification_method.public_key_multibase.as_deref(),
+        Some("z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH")
+    );
+}
+
+#[test]
+pub fn test_suite_is_output_preference() {
+    let did = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+
+    let (did_doc_option, _) = match resolve_did(did, "Ed25519VerificationKey2018") {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let did_doc = did_doc_option.expect("DID document not found");
+    let verification_method = &did_doc.verification_method.unwrap()[0];
+
+    assert_eq!(verification_method._type, "Ed25519VerificationKey2018");
+    assert!(verification_method.public_key_base58.is_some());
+}
+
+#[test]
+pub fn test_unsupported_suite_for_key_type() {
+    let did = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+
+    assert!(resolve_did(did, "X25519KeyAgreementKey2020").is_err());
+}
+
+#[test]
+pub fn test_error_keeps_resolution_error_code() {
+    let error = match resolve_did_with_format(
+        "did:key:6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH",
+        None,
+    ) {
+        Ok(_) => panic!("Fingerprint without multibase prefix must not resolve"),
+        Err(error) => error,
+    };
+
+    assert!(error.to_string().starts_with("invalidDid: "), "{}", error);
+}
@@ -0,0 +1,94 @@
+use fi_key_resolver::resolution::{ResolutionError, ResolutionOptions};
+use fi_key_resolver::{resolve, resolve_did, resolve_did_with_format};
+use serde_json::{json, Value};
+
+#[test]
+pub fn test_resolve_rsa_2048() {
+    let did = "did:key:z4MXj1wBzi9jUstyPMS4jQqB6KdJaiatPkAtVtGc6bQEQEEsKTic4G7Rou3iBf9vPmT5dbkm9qsZsuVNjq8HCuW1w24nhBFGkRE4cd2Uf2tfrB3N7h4mnyPp1BF3ZttHTYv3DLUPi1zMdkULiow3M1GfXkoC6DoxDUm1jmN6GBj22SjVsr6dxezRVQc7aj9TxE7JLbMH1wh5X3kA58H3DFW8rnYMakFGbca5CB2Jf6CnGQZmL7o5uJAdTwXfy2iiiyPxXEGerMhHwhjTA1mKYobyk2CpeEcmvynADfNZ5MBvcCS7m3XkFCMNUYBS9NQ3fze6vMSUPsNa6GVYmKx2x6JrdEjCk3qRMMmyjnjCMfR4pXbRMZa3i";
+
+    let did_doc = match resolve_did_with_format(did, None) {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+
+    assert_eq!(
+        did_doc.context,
+        Vec::from([
+            Value::from("https://www.w3.org/ns/did/v1"),
+            Value::from("https://w3id.org/security/suites/jws-2020/v1"),
+        ])
+    );
+
+    let verification_method = &did_doc.verification_method.unwrap()[0];
+    assert_eq!(verification_method._type, "JsonWebKey2020");
+    assert_eq!(
+        verification_method.id.as_deref(),
+        Some(format!("{}#{}", did, &did[8..]).as_str())
+    );
+    assert_eq!(
+        verification_method.public_key_jwk,
+        Some(json!({
+            "kty": "RSA",
+            "n": "sbX82NTV6IylxCh7MfV4hlyvaniCajuP97GyOqSvTmoEdBOflFvZ06kR_9D6ctt45Fk6hskfnag2GG69NALVH2o4RCR6tQiLRpKcMRtDYE_thEmfBvDzm_VVkOIYfxu-Ipuo9J_S5XDNDjczx2v-3oDh5-CIHkU46hvFeCvpUS-L8TJSbgX0kjVk_m4eIb9wh63rtmD6Uz_KBtCo5mmR4TEtcLZKYdqMp3wCjN-TlgHiz_4oVXWbHUefCEe8rFnX1iQnpDHU49_SaXQoud1jCaexFn25n-Aa8f8bc5Vm-5SeRwidHa6ErvEhTvf1dz6GoNPp2iRvm-wJ1gxwWJEYPQ",
+            "e": "AQAB"
+        }))
+    );
+    assert!(did_doc.key_agreement.is_none());
+
+    let did_doc = match resolve_did(did, "Multikey") {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+    let verification_method = &did_doc.verification_method.unwrap()[0];
+    assert_eq!(verification_method._type, "Multikey");
+    assert_eq!(
+        verification_method.public_key_multibase.as_deref(),
+        Some(&did[8..])
+    );
+}
+
+#[test]
+pub fn test_resolve_rsa_4096() {
+    let did = "did:key:zgghBUVkqmWS8e1j8QiuFPb2zjGKYTsNxSRCVGD1ox8ALNnP3GVuvSX4axUxnYeAcnECCjx5Lnsg5m8gW7g5BmR64eSikCkfU4tHUNWE382N14WrEG3742LkG24GPKjugvcxkxKxwEA5PKAbyW2sXmSPg1x78MmBAD36ghv3PBaTtyegCVo5H6vVJ6o3HqHvV1QgxW28meUABLDudHUnvXfZghCAfKhF9bpGRYgVcbXZf6aJcFGWifEn7GxgULREFs3updwEazpD7datSSrv3eKMVcfBnBD8Fza2ubqdtjKyKEuV2LbrKZQT5ZE8NEnf5a8DNw1Jyu2RPW6yqWec5nu46tMhcd2pLk87SefdytDx1cBovNeuVfekYE37EjLgvANtN6F12N2zk55i7jLNAbPPuQ2Ppri7C1Q5m3TXUZqMp3stv3uxfcbaJP4g25SVVgE73h9TYbQi8sWef565sGfv55AKjidHUu4sCwgq9Ry4L8e9D2siYaMehJxmpQaDVbVPQF9TRrVxn7smfCSQ5DNK4kX8QiTekb1VfmEhpndCgvE6nA87cwDDWQv8tke3kCre6yzAuG8P7gNY7t9LBpBq9j9u6LhCkXV1LggwQ6pCX2WKQmdLccLUK1JjndGFR49d8xc5AnTiXFiDiZ9cVjxQDxissphbhPQ7JJDJL4k8bDEv3mKhBKtEtMnrSrQbuYgBJEEpYJDRchPqsW";
+
+    let did_doc = match resolve_did_with_format(did, None) {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+
+    let verification_method = &did_doc.verification_method.unwrap()[0];
+    assert_eq!(verification_method._type, "JsonWebKey2020");
+    let public_key_jwk = verification_method.public_key_jwk.as_ref().unwrap();
+    assert_eq!(public_key_jwk["kty"], "RSA");
+    assert_eq!(public_key_jwk["e"], "AQAB");
+    // 512 bytes modulus as unpadded base64url
+    assert_eq!(public_key_jwk["n"].as_str().unwrap().len(), 683);
+}
+
+#[test]
+pub fn test_resolve_invalid_rsa_key() {
+    // a 1024 bit key
+    let result = resolve(
+        "did:key:zP7FcPGbPmVLQADrUwuhHzuuWvc2g8dyA3JVgn9qe1wo2HzjK8inPuwB44h7aZrq6szpdniEjtBQyKkknnM4rngYJeuZsH3syb7oBikauEXGkhYWGrTjfyiUfogPBrrgMxgXmTT8siLExBtw4V7tx2R7sqFMCE1ozoGifPT7ziaoruvJJeseivNPy8WuYKmk3Uc",
+        &ResolutionOptions::default(),
+    );
+    assert_eq!(
+        result.did_resolution_metadata.error,
+        Some(ResolutionError::InvalidPublicKeyLength)
+    );
+
+    let result = resolve(
+        "did:key:z4MXj1wBzi9jUstyPMS4jQqB6KdJaiatPkAtVtGc6bQEQEEsKTic4G7Rou3iBf9vPmT5dbkm9qsZsuVNjq8HCuW1w24nhBFGkRE4cd2Uf2tfrB3N7h4mnyPp1BF3ZttHTYv3DLUPi1zMdkULiow3M1GfXkoC6DoxDUm1jmN6GBj22SjVsr6dxezRVQc7aj9TxE7JLbMH1wh5X3kA58H3DFW8rnYMakFGbca5CB2Jf6CnGQZmL7o5uJAdTwXfy2iiiyPxXEGerMhHwhjTA1mKYobyk2CpeEcmvynADfNZ5MBvcCS7m3XkFCMNUYBS9NQ3fze6vMSUPsNa6GVYmKx2x6JrdEjCk3qRMMmyjnjCMfR4pXbRMZa3i",
+        &ResolutionOptions {
+            public_key_format: Some(String::from("Ed25519VerificationKey2020")),
+            ..ResolutionOptions::default()
+        },
+    );
+    assert_eq!(
+        result.did_resolution_metadata.error,
+        Some(ResolutionError::UnsupportedPublicKeyType)
+    );
+}
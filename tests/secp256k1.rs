@@ -0,0 +1,148 @@
+use fi_common::keys::VerificationKey;
+use fi_key_resolver::ecdsa_secp256k1_verification_key2019::EcdsaSecp256k1VerificationKey2019;
+use fi_key_resolver::resolution::{ResolutionError, ResolutionOptions};
+use fi_key_resolver::{resolve, resolve_did, resolve_did_with_format};
+use serde_json::{json, Value};
+
+#[test]
+pub fn test_resolve_secp256k1() {
+    let did = "did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme";
+
+    let did_doc = match resolve_did_with_format(did, None) {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+
+    assert_eq!(
+        did_doc.context,
+        Vec::from([
+            Value::from("https://www.w3.org/ns/did/v1"),
+            Value::from("https://w3id.org/security/suites/secp256k1-2019/v1"),
+        ])
+    );
+
+    let verification_method = &did_doc.verification_method.unwrap()[0];
+    assert_eq!(
+        verification_method._type,
+        "EcdsaSecp256k1VerificationKey2019"
+    );
+    assert_eq!(
+        verification_method.id.as_deref(),
+        Some("did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme#zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme")
+    );
+    assert_eq!(
+        verification_method.public_key_jwk,
+        Some(json!({
+            "kty": "EC",
+            "crv": "secp256k1",
+            "x": "h0wVx_2iDlOcblulc8E5iEw1EYh5n1RYtLQfeSTyNc0",
+            "y": "O2EATIGbu6DezKFptj5scAIRntgfecanVNXxat1rnwE"
+        }))
+    );
+    assert_eq!(
+        did_doc.assertion_method,
+        Some(Vec::from([verification_method.id.clone().unwrap()]))
+    );
+    assert!(did_doc.key_agreement.is_none());
+}
+
+#[test]
+pub fn test_resolve_secp256k1_formats() {
+    let did = "did:key:zQ3shtxV1FrJfhqE1dvxYRcCknWNjHc3c5X1y3ZSoPDi2aur2";
+
+    let did_doc = resolve(did, &ResolutionOptions::default())
+        .did_document
+        .expect("DID document not found");
+    let verification_method = &did_doc.verification_method.unwrap()[0];
+    assert_eq!(verification_method._type, "Multikey");
+    assert_eq!(
+        verification_method.public_key_multibase.as_deref(),
+        Some("zQ3shtxV1FrJfhqE1dvxYRcCknWNjHc3c5X1y3ZSoPDi2aur2")
+    );
+
+    let did_doc = match resolve_did(did, "JsonWebKey2020") {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+    let verification_method = &did_doc.verification_method.unwrap()[0];
+    assert_eq!(verification_method._type, "JsonWebKey2020");
+    assert_eq!(
+        verification_method.public_key_jwk,
+        Some(json!({
+            "kty": "EC",
+            "crv": "secp256k1",
+            "x": "1LjPGVO9OOqfeaUcT9S-Ml_5wQOybbSQ0SGgMgG9U0M",
+            "y": "aq-OS5tX6WqaY6fDHtATYwbIUijr8PvcGWd-FnCNQBM"
+        }))
+    );
+
+    let result = resolve(
+        did,
+        &ResolutionOptions {
+            public_key_format: Some(String::from("Ed25519VerificationKey2020")),
+            ..ResolutionOptions::default()
+        },
+    );
+    assert_eq!(
+        result.did_resolution_metadata.error,
+        Some(ResolutionError::UnsupportedPublicKeyType)
+    );
+}
+
+#[test]
+pub fn test_resolve_invalid_secp256k1_key() {
+    // x coordinate 0x05 has no point on secp256k1
+    let result = resolve(
+        "did:key:zQ3shMQnkqiyfujhRPGFFqSEeD2yV9kUcmyBiu2fT2BXfFPMN",
+        &ResolutionOptions::default(),
+    );
+    assert_eq!(
+        result.did_resolution_metadata.error,
+        Some(ResolutionError::InvalidPublicKey)
+    );
+}
+
+#[test]
+pub fn test_secp256k1_private_key_export() {
+    // the private key 1, whose public key is the generator point
+    let public_key_multibase = "zQ3shVc2UkAfJCdc1TR8E66J85h48P43r93q8jGPkPpjF9Ef9";
+    let private_key_multibase = "z3vLTztfd4SjVQVf9WfKUYKsCdtUznWH7f2rzpGFnFLtyCja";
+
+    let key_pair = match EcdsaSecp256k1VerificationKey2019::new(
+        Some(format!("did:key:{}", public_key_multibase)),
+        String::from(public_key_multibase),
+        Some(String::from(private_key_multibase)),
+        Some(String::from(public_key_multibase)),
+    ) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let public_key = key_pair.export(true, false, false);
+    assert!(public_key.private_key_jwk.is_none());
+
+    let private_key = key_pair.export(true, true, false);
+    assert_eq!(
+        private_key.private_key_jwk,
+        Some(json!({
+            "kty": "EC",
+            "crv": "secp256k1",
+            "x": "eb5mfvncu6xVoGKVzocLBwKb_NstzijZWfKBWxb4F5g",
+            "y": "SDradyajxGVdpPv8DhEIqP0XtEimhVQZnEfQj_sQ1Lg",
+            "d": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAE"
+        }))
+    );
+
+    // the private key 2 does not belong to the generator point
+    assert!(EcdsaSecp256k1VerificationKey2019::new(
+        None,
+        String::from(public_key_multibase),
+        Some(String::from(
+            "z3vLTztfd4SjVQVf9WfKUYKsCdtUznWH7f2rzpGFnFLtyCjb"
+        )),
+        None,
+    )
+    .is_err());
+}
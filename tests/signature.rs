@@ -0,0 +1,155 @@
+use fi_key_resolver::ed25519_verification_key2018::Ed25519VerificationKey2018;
+use fi_key_resolver::ed25519_verification_key2020::Ed25519VerificationKey2020;
+use fi_key_resolver::resolve_did_with_format;
+use fi_key_resolver::signature::{self, SignatureError, SignatureKey};
+
+mod common;
+use common::{decode_hex, EXPANDED_SECRET_KEY, FINGERPRINT, PUBLIC_KEY, SEED};
+
+// RFC 8032 test 1 signature of the empty message
+const SIGNATURE: &str = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b";
+
+#[test]
+pub fn test_sign_rfc8032_vector() {
+    let mut libsodium_secret_key = decode_hex(SEED);
+    libsodium_secret_key.extend(decode_hex(PUBLIC_KEY));
+
+    let mut expanded_secret_key = [0u8; 64];
+    expanded_secret_key.copy_from_slice(&decode_hex(EXPANDED_SECRET_KEY));
+
+    let key_pairs = [
+        Ed25519VerificationKey2020::from_private_key_bytes(&decode_hex(SEED)),
+        Ed25519VerificationKey2020::from_private_key_bytes(&libsodium_secret_key),
+        Ed25519VerificationKey2020::from_expanded_secret_key(&expanded_secret_key),
+    ];
+
+    for key_pair in key_pairs {
+        let key_pair = match key_pair {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+
+        let signature = match key_pair.sign(b"") {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+        assert_eq!(signature, decode_hex(SIGNATURE));
+
+        let key_pair = match Ed25519VerificationKey2018::try_from(&key_pair) {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+        match key_pair.sign(b"") {
+            Ok(val) => assert_eq!(val, decode_hex(SIGNATURE)),
+            Err(error) => panic!("{}", error),
+        };
+    }
+}
+
+#[test]
+pub fn test_verify_with_resolved_did_key() {
+    let did_url = format!("did:key:{}#{}", FINGERPRINT, FINGERPRINT);
+    let verification_method = match resolve_did_with_format(did_url.as_str(), None) {
+        Ok((_, Some(val))) => val,
+        Ok(_) => panic!("Expected a verification method"),
+        Err(error) => panic!("{}", error),
+    };
+
+    let key_pair = match Ed25519VerificationKey2020::try_from(&verification_method) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    let signature = decode_hex(SIGNATURE);
+
+    match key_pair.verify(b"", &signature) {
+        Ok(_) => {}
+        Err(error) => panic!("{}", error),
+    };
+
+    let mut tampered = signature.clone();
+    tampered[0] ^= 1;
+    match key_pair.verify(b"", &tampered) {
+        Ok(_) => panic!("Tampered signature must not verify"),
+        Err(failure) => assert_eq!(failure.error, SignatureError::VerificationFailed),
+    };
+
+    match key_pair.verify(b"", &signature[..63]) {
+        Ok(_) => panic!("Truncated signature must not verify"),
+        Err(failure) => assert_eq!(failure.error, SignatureError::InvalidSignature),
+    };
+
+    // no private key to sign with
+    match key_pair.sign(b"") {
+        Ok(_) => panic!("Public key must not sign"),
+        Err(failure) => assert_eq!(failure.error, SignatureError::MissingPrivateKey),
+    };
+}
+
+#[test]
+pub fn test_verify_rejects_weak_public_key() {
+    // the identity point, which is of small order
+    let mut identity = [0u8; 32];
+    identity[0] = 1;
+    let key_pair =
+        Ed25519VerificationKey2018::new(None, bs58::encode(identity).into_string(), None, None);
+
+    // the identity point with the identity signature (R = identity, s = 0)
+    // verifies every message under the permissive rules
+    let mut signature = [0u8; 64];
+    signature[0] = 1;
+
+    match key_pair.verify(b"message", &signature) {
+        Ok(_) => panic!("Small order public key must be rejected"),
+        Err(failure) => assert_eq!(failure.error, SignatureError::WeakPublicKey),
+    };
+}
+
+#[test]
+pub fn test_verify_rejects_malformed_input() {
+    let public_key = decode_hex(PUBLIC_KEY);
+    let signature = decode_hex(SIGNATURE);
+
+    for signature in [Vec::new(), vec![0u8; 63], vec![0u8; 65]] {
+        match signature::verify(&public_key, b"", &signature) {
+            Ok(_) => panic!("Malformed signature must not verify"),
+            Err(failure) => assert_eq!(failure.error, SignatureError::InvalidSignature),
+        };
+    }
+
+    for public_key in [Vec::new(), vec![0u8; 31], vec![0u8; 33]] {
+        match signature::verify(&public_key, b"", &signature) {
+            Ok(_) => panic!("Malformed public key must not verify"),
+            Err(failure) => assert_eq!(failure.error, SignatureError::InvalidPublicKey),
+        };
+    }
+
+    // s + L, the group order, is a non-canonical encoding of s
+    let group_order =
+        decode_hex("edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010");
+    let mut non_canonical = signature.clone();
+    let mut carry = 0u16;
+    for index in 0..32 {
+        let sum = non_canonical[32 + index] as u16 + group_order[index] as u16 + carry;
+        non_canonical[32 + index] = sum as u8;
+        carry = sum >> 8;
+    }
+    match signature::verify(&public_key, b"", &non_canonical) {
+        Ok(_) => panic!("Non-canonical signature must not verify"),
+        Err(failure) => assert_eq!(failure.error, SignatureError::VerificationFailed),
+    };
+}
+
+#[test]
+pub fn test_sign_rejects_private_key_of_another_key() {
+    let key_pair = Ed25519VerificationKey2018::new(
+        None,
+        bs58::encode(decode_hex(PUBLIC_KEY)).into_string(),
+        Some(bs58::encode([7u8; 32]).into_string()),
+        None,
+    );
+
+    match key_pair.sign(b"") {
+        Ok(_) => panic!("Private key of another key must not sign"),
+        Err(failure) => assert_eq!(failure.error, SignatureError::InvalidPrivateKey),
+    };
+}
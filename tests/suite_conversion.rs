@@ -0,0 +1,259 @@
+use fi_common::error::Error;
+use fi_common::keys::{AgreementKey, KeyPair, VerificationKey};
+use fi_key_resolver::ed25519_verification_key2018::Ed25519VerificationKey2018;
+use fi_key_resolver::ed25519_verification_key2020::Ed25519VerificationKey2020;
+use fi_key_resolver::x25519_key_agreement_key2019::X25519KeyAgreementKey2019;
+use fi_key_resolver::x25519_key_agreement_key2020::X25519KeyAgreementKey2020;
+
+// RFC 8032 test 1 key pair
+const PUBLIC_KEY_MULTIBASE: &str = "z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw";
+const PRIVATE_KEY_MULTIBASE: &str =
+    "zrv3nQ3vxUrShebtbJeB42niZe1oGRnFzGPusycqLLtiJEeSFbDjwS6rvt6uMYYkjGuZMTsqb6mzCgG19WbjcNNsvxq";
+
+#[test]
+pub fn test_convert_ed25519_suites() {
+    let key_pair = match Ed25519VerificationKey2020::new(
+        Some(String::from("did:example:123")),
+        String::from(PUBLIC_KEY_MULTIBASE),
+        Some(String::from(PRIVATE_KEY_MULTIBASE)),
+        Some(String::from("key-1")),
+    ) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    let converted = match Ed25519VerificationKey2018::try_from(&key_pair) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    let exported = converted.export(true, true, false);
+    assert_eq!(exported._type, "Ed25519VerificationKey2018");
+    assert_eq!(exported.id.as_deref(), Some("did:example:123#key-1"));
+    assert_eq!(exported.controller.as_deref(), Some("did:example:123"));
+    assert_eq!(exported.revoked, Some(false));
+    assert_eq!(
+        exported.public_key_base58.as_deref(),
+        Some("FVen3X669xLzsi6N2V91DoiyzHzg1uAgqiT8jZ9nS96Z")
+    );
+    assert_eq!(
+        exported.private_key_base58.as_deref(),
+        Some("49W385L4rePHy6PAaQUovbD2aacgN4HsKXSMeUzRg4fmwXszN91JuMFrQRj3vMDpZuRF3ZknQBuRBoWQJEfXstMw")
+    );
+
+    let round_trip = match Ed25519VerificationKey2020::try_from(&converted) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        serde_json::to_value(round_trip.export(true, true, true)).unwrap(),
+        serde_json::to_value(key_pair.export(true, true, true)).unwrap()
+    );
+}
+
+#[test]
+pub fn test_convert_x25519_suites() {
+    let key_pair = X25519KeyAgreementKey2020::new(
+        Some(String::from("did:example:123")),
+        String::from("z6LSrEnPXPcLyNLKJPhdJ1eWqyYKARWket5BbiN1rjdUsQ9b"),
+        Some(String::from(
+            "z3wea3FEiVjYREYVX7t99g4uEm4r4jPsTuhm4GMv5cS838rS",
+        )),
+        Some(String::from("key-2")),
+    );
+
+    let converted = match X25519KeyAgreementKey2019::try_from(&key_pair) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    let exported = converted.export(true, true, false);
+    assert_eq!(exported._type, "X25519KeyAgreementKey2019");
+    assert_eq!(exported.id.as_deref(), Some("did:example:123#key-2"));
+    assert_eq!(
+        exported.private_key_base58.as_deref(),
+        Some("4GGh2VrEgkH7MVktXDJ9Wcqr6X9rdybhFQuQxYmjs67G")
+    );
+
+    let round_trip = match X25519KeyAgreementKey2020::try_from(&converted) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        serde_json::to_value(round_trip.export(true, true, true)).unwrap(),
+        serde_json::to_value(key_pair.export(true, true, true)).unwrap()
+    );
+}
+
+#[test]
+pub fn test_convert_invalid_ed25519_key() {
+    // the private key of another key pair
+    let key_pair = Ed25519VerificationKey2018::new(
+        None,
+        String::from("FVen3X669xLzsi6N2V91DoiyzHzg1uAgqiT8jZ9nS96Z"),
+        Some(String::from("4GGh2VrEgkH7MVktXDJ9Wcqr6X9rdybhFQuQxYmjs67G")),
+        None,
+    );
+
+    assert!(Ed25519VerificationKey2020::try_from(&key_pair).is_err());
+}
+
+#[test]
+pub fn test_convert_malformed_key_pair() {
+    let ed25519_2020 = match Ed25519VerificationKey2020::new(
+        None,
+        String::from(PUBLIC_KEY_MULTIBASE),
+        Some(String::from(PRIVATE_KEY_MULTIBASE)),
+        None,
+    ) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    let ed25519_2018 = match Ed25519VerificationKey2018::try_from(&ed25519_2020) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+    let x25519_2020 =
+        match X25519KeyAgreementKey2020::from_ed25519_verification_key2020(&ed25519_2020) {
+            Ok(val) => val,
+            Err(error) => panic!("{}", error),
+        };
+    let x25519_2019 = match X25519KeyAgreementKey2019::try_from(&x25519_2020) {
+        Ok(val) => val,
+        Err(error) => panic!("{}", error),
+    };
+
+    for value in ["", "z", "z1", "0OIl"] {
+        let mut key_pair = ed25519_2020.export(true, true, false);
+        key_pair.public_key_multibase = Some(String::from(value));
+        assert!(Ed25519VerificationKey2020::try_from(&key_pair).is_err());
+
+        let mut key_pair = x25519_2020.export(true, true, false);
+        key_pair.public_key_multibase = Some(String::from(value));
+        assert!(X25519KeyAgreementKey2020::try_from(&key_pair).is_err());
+        let mut key_pair = x25519_2020.export(true, true, false);
+        key_pair.private_key_multibase = Some(String::from(value));
+        assert!(X25519KeyAgreementKey2020::try_from(&key_pair).is_err());
+
+        let mut key_pair = ed25519_2018.export(true, true, false);
+        key_pair.public_key_base58 = Some(String::from(value));
+        assert!(Ed25519VerificationKey2018::try_from(&key_pair).is_err());
+
+        let mut key_pair = x25519_2019.export(true, true, false);
+        key_pair.public_key_base58 = Some(String::from(value));
+        assert!(X25519KeyAgreementKey2019::try_from(&key_pair).is_err());
+    }
+
+    // the private key of another key pair
+    let other = X25519KeyAgreementKey2019::generate().export(true, true, false);
+    let mut key_pair = x25519_2019.export(true, true, false);
+    key_pair.private_key_base58 = other.private_key_base58;
+    assert!(X25519KeyAgreementKey2019::try_from(&key_pair).is_err());
+
+    let mut key_pair = ed25519_2018.export(true, true, false);
+    key_pair.private_key_base58 =
+        Some(String::from("4GGh2VrEgkH7MVktXDJ9Wcqr6X9rdybhFQuQxYmjs67G"));
+    assert!(Ed25519VerificationKey2018::try_from(&key_pair).is_err());
+}
+
+#[test]
+pub fn test_ed25519_2018_malformed_fingerprint() {
+    for fingerprint in [
+        "",
+        "z",
+        "z1",
+        "6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw",
+        "zé",
+    ] {
+        assert!(Ed25519VerificationKey2018::from_fingerprint(fingerprint).is_err());
+    }
+}
+
+// a key pair that passes its content through without checking it
+struct UncheckedKey {
+    public_key_content: String,
+    private_key_content: Option<String>,
+}
+
+impl VerificationKey for UncheckedKey {
+    fn from_fingerprint(_fingerprint: &str) -> Result<Self, Error> {
+        Err(Error::new("Not supported"))
+    }
+
+    fn get_suite_id() -> &'static str {
+        Ed25519VerificationKey2020::get_suite_id()
+    }
+
+    fn get_current_suite_id(&self) -> &'static str {
+        Ed25519VerificationKey2020::get_suite_id()
+    }
+
+    fn get_suite_context() -> &'static str {
+        Ed25519VerificationKey2020::get_suite_context()
+    }
+
+    fn get_current_suite_context(&self) -> &'static str {
+        Ed25519VerificationKey2020::get_suite_context()
+    }
+
+    fn get_controller(&self) -> &Option<String> {
+        &None
+    }
+
+    fn get_type(&self) -> String {
+        String::from(Ed25519VerificationKey2020::get_suite_id())
+    }
+
+    fn get_private_key_content(&self) -> &Option<String> {
+        &self.private_key_content
+    }
+
+    fn get_public_key_content(&self) -> &String {
+        &self.public_key_content
+    }
+
+    fn export(&self, _public_key: bool, _private_key: bool, _include_context: bool) -> KeyPair {
+        unimplemented!()
+    }
+}
+
+#[test]
+pub fn test_convert_bad_private_key() {
+    let key_pair = UncheckedKey {
+        public_key_content: String::from(PUBLIC_KEY_MULTIBASE),
+        private_key_content: Some(String::from(PRIVATE_KEY_MULTIBASE)),
+    };
+    assert!(X25519KeyAgreementKey2020::from_ed25519_verification_key2020(&key_pair).is_ok());
+
+    for value in [
+        "",
+        "rv3nQ3vxUrShebtbJeB42niZe1oGRnFzGPusycqLLtiJEeSFbDjwS6rvt6uMYYkjGuZMTsqb6mzCgG19WbjcNNsvxq",
+    ] {
+        let key_pair = UncheckedKey {
+            public_key_content: String::from(PUBLIC_KEY_MULTIBASE),
+            private_key_content: Some(String::from(value)),
+        };
+        match X25519KeyAgreementKey2020::from_ed25519_verification_key2020(&key_pair) {
+            Ok(_) => panic!("Expected '{}' to be rejected", value),
+            Err(error) => assert!(error.to_string().contains("privateKeyMultibase")),
+        }
+    }
+
+    let key_pair = UncheckedKey {
+        public_key_content: String::from(PUBLIC_KEY_MULTIBASE),
+        private_key_content: Some(String::from("z")),
+    };
+    assert!(X25519KeyAgreementKey2020::from_ed25519_verification_key2020(&key_pair).is_err());
+
+    for value in ["", "0OIl", "z"] {
+        let key_pair = Ed25519VerificationKey2018::new(
+            None,
+            String::from("FVen3X669xLzsi6N2V91DoiyzHzg1uAgqiT8jZ9nS96Z"),
+            Some(String::from(value)),
+            None,
+        );
+        assert!(
+            X25519KeyAgreementKey2019::from_ed25519_verification_key2018(&key_pair).is_err(),
+            "{}",
+            value
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use fi_key_resolver::resolution::{ResolutionError, ResolutionOptions};
+use fi_key_resolver::{resolve, resolve_did, resolve_did_with_format};
+use serde_json::{json, Value};
+
+#[test]
+pub fn test_resolve_x25519() {
+    let did = "did:key:z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc";
+
+    let did_doc = match resolve_did_with_format(did, None) {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+
+    assert_eq!(did_doc.id, did);
+    assert_eq!(
+        did_doc.context,
+        Vec::from([
+            Value::from("https://www.w3.org/ns/did/v1"),
+            Value::from("https://w3id.org/security/suites/x25519-2020/v1"),
+        ])
+    );
+
+    let key_agreement = &did_doc.key_agreement.unwrap()[0];
+    assert_eq!(key_agreement._type, "X25519KeyAgreementKey2020");
+    assert_eq!(
+        key_agreement.id.as_deref(),
+        Some("did:key:z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc#z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc")
+    );
+    assert_eq!(
+        key_agreement.public_key_multibase.as_deref(),
+        Some("z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc")
+    );
+
+    assert!(did_doc.verification_method.is_none());
+    assert!(did_doc.authentication.is_none());
+    assert!(did_doc.assertion_method.is_none());
+    assert!(did_doc.capability_delegation.is_none());
+    assert!(did_doc.capability_invocation.is_none());
+
+    let key_pair = match resolve_did_with_format(
+        "did:key:z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc#z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc",
+        None,
+    ) {
+        Ok((_, Some(val))) => val,
+        Ok((_, None)) => panic!("Key pair not found"),
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(key_pair._type, "X25519KeyAgreementKey2020");
+}
+
+#[test]
+pub fn test_resolve_x25519_formats() {
+    let did = "did:key:z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc";
+
+    let did_doc = resolve(did, &ResolutionOptions::default())
+        .did_document
+        .expect("DID document not found");
+    assert_eq!(did_doc.key_agreement.unwrap()[0]._type, "Multikey");
+
+    let did_doc = match resolve_did(did, "X25519KeyAgreementKey2019") {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+    let key_agreement = &did_doc.key_agreement.unwrap()[0];
+    assert_eq!(key_agreement._type, "X25519KeyAgreementKey2019");
+    assert_eq!(
+        key_agreement.public_key_base58.as_deref(),
+        Some("JhNWeSVLMYccCk7iopQW4guaSJTojqpMEELgSLhKwRr")
+    );
+
+    let did_doc = match resolve_did(did, "JsonWebKey2020") {
+        Ok((Some(val), _)) => val,
+        Ok((None, _)) => panic!("DID document not found"),
+        Err(error) => panic!("{}", error),
+    };
+    assert_eq!(
+        did_doc.key_agreement.unwrap()[0].public_key_jwk,
+        Some(json!({
+            "kty": "OKP",
+            "crv": "X25519",
+            "x": "BIiFcQEn3dfvB2pjlhOQQour6jXy9d5s2FKEJNTOJik"
+        }))
+    );
+
+    let result = resolve(
+        did,
+        &ResolutionOptions {
+            public_key_format: Some(String::from("Ed25519VerificationKey2020")),
+            ..ResolutionOptions::default()
+        },
+    );
+    assert_eq!(
+        result.did_resolution_metadata.error,
+        Some(ResolutionError::UnsupportedPublicKeyType)
+    );
+}
+
+#[test]
+pub fn test_resolve_invalid_x25519_key() {
+    let result = resolve(
+        "did:key:z2D7FfmVBDzpdoHaiF2z4C5Ccasw6rf3hPziZQsey1bLz7g",
+        &ResolutionOptions::default(),
+    );
+    assert_eq!(
+        result.did_resolution_metadata.error,
+        Some(ResolutionError::InvalidPublicKeyLength)
+    );
+}